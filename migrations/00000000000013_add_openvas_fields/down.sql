@@ -0,0 +1,2 @@
+ALTER TABLE nessus_items DROP COLUMN confidence;
+ALTER TABLE nessus_plugins DROP COLUMN oid;
@@ -0,0 +1,2 @@
+ALTER TABLE nessus_plugins ADD COLUMN oid TEXT;
+ALTER TABLE nessus_items ADD COLUMN confidence INTEGER;
@@ -0,0 +1,2 @@
+ALTER TABLE nessus_items DROP COLUMN last_observed;
+ALTER TABLE nessus_items DROP COLUMN first_discovered;
@@ -0,0 +1,2 @@
+ALTER TABLE nessus_items ADD COLUMN first_discovered TIMESTAMP;
+ALTER TABLE nessus_items ADD COLUMN last_observed TIMESTAMP;
@@ -0,0 +1,20 @@
+ALTER TABLE nessus_items DROP COLUMN threat_intensity_last_28;
+ALTER TABLE nessus_items DROP COLUMN exploit_code_maturity;
+ALTER TABLE nessus_items DROP COLUMN epss_score;
+ALTER TABLE nessus_items DROP COLUMN vpr_score;
+ALTER TABLE nessus_items DROP COLUMN cvss4_vector;
+ALTER TABLE nessus_items DROP COLUMN cvss4_base_score;
+ALTER TABLE nessus_items DROP COLUMN cvss3_temporal_vector;
+ALTER TABLE nessus_items DROP COLUMN cvss3_temporal_score;
+ALTER TABLE nessus_items DROP COLUMN cvss3_vector;
+ALTER TABLE nessus_items DROP COLUMN cvss3_base_score;
+ALTER TABLE nessus_plugins DROP COLUMN threat_intensity_last_28;
+ALTER TABLE nessus_plugins DROP COLUMN exploit_code_maturity;
+ALTER TABLE nessus_plugins DROP COLUMN epss_score;
+ALTER TABLE nessus_plugins DROP COLUMN vpr_score;
+ALTER TABLE nessus_plugins DROP COLUMN cvss4_vector;
+ALTER TABLE nessus_plugins DROP COLUMN cvss4_base_score;
+ALTER TABLE nessus_plugins DROP COLUMN cvss3_temporal_vector;
+ALTER TABLE nessus_plugins DROP COLUMN cvss3_temporal_score;
+ALTER TABLE nessus_plugins DROP COLUMN cvss3_vector;
+ALTER TABLE nessus_plugins DROP COLUMN cvss3_base_score;
@@ -0,0 +1,20 @@
+ALTER TABLE nessus_plugins ADD COLUMN cvss3_base_score REAL;
+ALTER TABLE nessus_plugins ADD COLUMN cvss3_vector TEXT;
+ALTER TABLE nessus_plugins ADD COLUMN cvss3_temporal_score REAL;
+ALTER TABLE nessus_plugins ADD COLUMN cvss3_temporal_vector TEXT;
+ALTER TABLE nessus_plugins ADD COLUMN cvss4_base_score REAL;
+ALTER TABLE nessus_plugins ADD COLUMN cvss4_vector TEXT;
+ALTER TABLE nessus_plugins ADD COLUMN vpr_score REAL;
+ALTER TABLE nessus_plugins ADD COLUMN epss_score REAL;
+ALTER TABLE nessus_plugins ADD COLUMN exploit_code_maturity TEXT;
+ALTER TABLE nessus_plugins ADD COLUMN threat_intensity_last_28 TEXT;
+ALTER TABLE nessus_items ADD COLUMN cvss3_base_score REAL;
+ALTER TABLE nessus_items ADD COLUMN cvss3_vector TEXT;
+ALTER TABLE nessus_items ADD COLUMN cvss3_temporal_score REAL;
+ALTER TABLE nessus_items ADD COLUMN cvss3_temporal_vector TEXT;
+ALTER TABLE nessus_items ADD COLUMN cvss4_base_score REAL;
+ALTER TABLE nessus_items ADD COLUMN cvss4_vector TEXT;
+ALTER TABLE nessus_items ADD COLUMN vpr_score REAL;
+ALTER TABLE nessus_items ADD COLUMN epss_score REAL;
+ALTER TABLE nessus_items ADD COLUMN exploit_code_maturity TEXT;
+ALTER TABLE nessus_items ADD COLUMN threat_intensity_last_28 TEXT;
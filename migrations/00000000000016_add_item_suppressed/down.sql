@@ -0,0 +1 @@
+ALTER TABLE nessus_items DROP COLUMN suppressed;
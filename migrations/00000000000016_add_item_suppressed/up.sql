@@ -0,0 +1 @@
+ALTER TABLE nessus_items ADD COLUMN suppressed BOOLEAN;
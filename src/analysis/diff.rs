@@ -0,0 +1,271 @@
+//! Scan-to-scan comparison.
+//!
+//! Hosts of the two reports are matched by IP address, then FQDN, then MAC
+//! address. Findings are matched on (host, plugin ID, port, protocol) and
+//! classified as new, remediated, persistent or severity-changed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::models::{Host, Item};
+use crate::parser::NessusReport;
+
+/// Classification of a finding between the baseline and current scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiffStatus {
+    /// Only present in the current scan.
+    New,
+    /// Only present in the baseline scan.
+    Remediated,
+    /// Present in both scans with a different severity.
+    SeverityChanged,
+    /// Present in both scans with the same severity.
+    Persistent,
+}
+
+impl DiffStatus {
+    /// All statuses in reporting order.
+    pub const ALL: [DiffStatus; 4] = [
+        DiffStatus::New,
+        DiffStatus::Remediated,
+        DiffStatus::SeverityChanged,
+        DiffStatus::Persistent,
+    ];
+}
+
+impl fmt::Display for DiffStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            DiffStatus::New => "New",
+            DiffStatus::Remediated => "Remediated",
+            DiffStatus::Persistent => "Persistent",
+            DiffStatus::SeverityChanged => "Severity Changed",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single finding compared across two scans.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FindingDiff {
+    pub status: DiffStatus,
+    /// Display name of the host (IP, FQDN or name).
+    pub host: String,
+    pub plugin_id: Option<i32>,
+    pub plugin_name: Option<String>,
+    pub port: Option<i32>,
+    pub protocol: Option<String>,
+    /// Severity in the baseline scan, if the finding was present.
+    pub old_severity: Option<i32>,
+    /// Severity in the current scan, if the finding is present.
+    pub new_severity: Option<i32>,
+}
+
+/// Result of comparing a baseline report with a current report.
+#[derive(Debug, Clone, Default)]
+pub struct ScanDiff {
+    /// Title of the baseline report.
+    pub baseline: Option<String>,
+    /// Title of the current report.
+    pub current: Option<String>,
+    pub findings: Vec<FindingDiff>,
+}
+
+impl ScanDiff {
+    /// Findings with the given status.
+    pub fn with_status(&self, status: DiffStatus) -> impl Iterator<Item = &FindingDiff> {
+        self.findings.iter().filter(move |f| f.status == status)
+    }
+
+    /// Number of findings with the given status.
+    pub fn count(&self, status: DiffStatus) -> usize {
+        self.with_status(status).count()
+    }
+}
+
+/// Key identifying a finding: (matched host, plugin ID, port, protocol).
+type FindingKey = (usize, Option<i32>, Option<i32>, Option<String>);
+
+/// Compare `baseline` against `current`.
+pub fn diff_reports(baseline: &NessusReport, current: &NessusReport) -> ScanDiff {
+    // Baseline hosts keep their index as identity; current hosts take the
+    // identity of the baseline host they match, or a fresh one.
+    let mut by_ip = HashMap::new();
+    let mut by_fqdn = HashMap::new();
+    let mut by_mac = HashMap::new();
+    for (idx, h) in baseline.hosts.iter().enumerate() {
+        if let Some(ip) = normalized(&h.ip) {
+            by_ip.entry(ip).or_insert(idx);
+        }
+        if let Some(fqdn) = normalized(&h.fqdn) {
+            by_fqdn.entry(fqdn).or_insert(idx);
+        }
+        if let Some(mac) = normalized(&h.mac) {
+            by_mac.entry(mac).or_insert(idx);
+        }
+    }
+    let current_identity: Vec<usize> = current
+        .hosts
+        .iter()
+        .enumerate()
+        .map(|(idx, h)| {
+            normalized(&h.ip)
+                .and_then(|ip| by_ip.get(&ip))
+                .or_else(|| normalized(&h.fqdn).and_then(|f| by_fqdn.get(&f)))
+                .or_else(|| normalized(&h.mac).and_then(|m| by_mac.get(&m)))
+                .copied()
+                .unwrap_or(baseline.hosts.len() + idx)
+        })
+        .collect();
+
+    let old = findings(baseline, Some);
+    let new = findings(current, |idx| current_identity.get(idx).copied());
+
+    let mut result = Vec::new();
+    for (key, (item, host)) in &new {
+        let new_sev = item.severity;
+        match old.get(key) {
+            Some((old_item, _)) => {
+                let status = if old_item.severity == new_sev {
+                    DiffStatus::Persistent
+                } else {
+                    DiffStatus::SeverityChanged
+                };
+                result.push(finding(status, key, item, *host, old_item.severity, new_sev));
+            }
+            None => result.push(finding(DiffStatus::New, key, item, *host, None, new_sev)),
+        }
+    }
+    for (key, (item, host)) in &old {
+        if !new.contains_key(key) {
+            result.push(finding(DiffStatus::Remediated, key, item, *host, item.severity, None));
+        }
+    }
+    result.sort_by(|a, b| {
+        a.status
+            .cmp(&b.status)
+            .then_with(|| {
+                let sev = |f: &FindingDiff| f.new_severity.or(f.old_severity).unwrap_or(0);
+                sev(b).cmp(&sev(a))
+            })
+            .then_with(|| a.host.cmp(&b.host))
+            .then_with(|| a.plugin_id.cmp(&b.plugin_id))
+            .then_with(|| a.port.cmp(&b.port))
+    });
+
+    ScanDiff {
+        baseline: baseline.report.title.clone(),
+        current: current.report.title.clone(),
+        findings: result,
+    }
+}
+
+/// Index the findings of a report by [`FindingKey`]. When a key occurs more
+/// than once the most severe item is kept.
+fn findings(
+    report: &NessusReport,
+    identity: impl Fn(usize) -> Option<usize>,
+) -> BTreeMap<FindingKey, (&Item, Option<&Host>)> {
+    let mut map: BTreeMap<FindingKey, (&Item, Option<&Host>)> = BTreeMap::new();
+    for item in &report.items {
+        let Some(host_idx) = item.host_id.and_then(|h| usize::try_from(h).ok()) else {
+            continue;
+        };
+        let Some(id) = identity(host_idx) else {
+            continue;
+        };
+        let key = (id, item.plugin_id, item.port, item.protocol.clone());
+        let host = report.hosts.get(host_idx);
+        map.entry(key)
+            .and_modify(|e| {
+                if item.severity > e.0.severity {
+                    *e = (item, host);
+                }
+            })
+            .or_insert((item, host));
+    }
+    map
+}
+
+fn finding(
+    status: DiffStatus,
+    key: &FindingKey,
+    item: &Item,
+    host: Option<&Host>,
+    old_severity: Option<i32>,
+    new_severity: Option<i32>,
+) -> FindingDiff {
+    FindingDiff {
+        status,
+        host: host.map(host_label).unwrap_or_else(|| "unknown".to_string()),
+        plugin_id: key.1,
+        plugin_name: item.plugin_name.clone(),
+        port: key.2,
+        protocol: key.3.clone(),
+        old_severity,
+        new_severity,
+    }
+}
+
+fn host_label(h: &Host) -> String {
+    h.ip.clone()
+        .or_else(|| h.fqdn.clone())
+        .or_else(|| h.name.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn normalized(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(ip: Option<&str>, fqdn: Option<&str>) -> Host {
+        Host { ip: ip.map(str::to_string), fqdn: fqdn.map(str::to_string), ..Host::default() }
+    }
+
+    fn item(host_id: i32, plugin_id: i32, port: i32, severity: i32) -> Item {
+        Item {
+            host_id: Some(host_id),
+            plugin_id: Some(plugin_id),
+            port: Some(port),
+            protocol: Some("tcp".to_string()),
+            severity: Some(severity),
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn classifies_findings() {
+        let baseline = NessusReport {
+            hosts: vec![host(Some("10.0.0.1"), None), host(None, Some("web.example"))],
+            items: vec![item(0, 1, 22, 2), item(0, 2, 80, 3), item(1, 3, 443, 1)],
+            ..NessusReport::default()
+        };
+        // Host order differs and the second host changed IP but kept its FQDN.
+        let current = NessusReport {
+            hosts: vec![host(Some("10.0.0.9"), Some("WEB.example")), host(Some("10.0.0.1"), None)],
+            items: vec![item(1, 1, 22, 2), item(1, 4, 25, 2), item(0, 3, 443, 4)],
+            ..NessusReport::default()
+        };
+        let diff = diff_reports(&baseline, &current);
+        let status_of = |pid: i32| {
+            diff.findings
+                .iter()
+                .find(|f| f.plugin_id == Some(pid))
+                .map(|f| f.status)
+        };
+        assert_eq!(status_of(1), Some(DiffStatus::Persistent));
+        assert_eq!(status_of(2), Some(DiffStatus::Remediated));
+        assert_eq!(status_of(3), Some(DiffStatus::SeverityChanged));
+        assert_eq!(status_of(4), Some(DiffStatus::New));
+        assert_eq!(diff.findings.len(), 4);
+        assert_eq!(diff.findings[0].status, DiffStatus::New);
+    }
+}
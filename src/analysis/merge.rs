@@ -0,0 +1,397 @@
+//! Asset correlation across scanner imports.
+//!
+//! Hosts are treated as the same machine when they share an IP address, MAC
+//! address, FQDN, NetBIOS name or `bios-uuid` host property. Matching is
+//! transitive, so a Nessus host matched by IP and an Nmap host matched by MAC
+//! collapse into one asset. Placeholders such as an all-zero MAC address or
+//! `localhost` are ignored. Findings are kept as they are; only their host
+//! links change.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::models::{Host, HostProperty};
+use crate::parser::NessusReport;
+
+/// Normalized identifiers that many unrelated machines report, such as an
+/// unset MAC address or the loopback name. They never link two hosts.
+const PLACEHOLDER_IDENTIFIERS: [&str; 10] = [
+    "00:00:00:00:00:00",
+    "ff:ff:ff:ff:ff:ff",
+    "localhost",
+    "localhost.localdomain",
+    "127.0.0.1",
+    "::1",
+    "0.0.0.0",
+    "unknown",
+    "00000000-0000-0000-0000-000000000000",
+    "ffffffff-ffff-ffff-ffff-ffffffffffff",
+];
+
+/// Counts reported by [`merge_hosts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergeSummary {
+    /// Hosts before correlation.
+    pub hosts_before: usize,
+    /// Distinct assets after correlation.
+    pub assets: usize,
+}
+
+/// Concatenate several reports into one and merge their hosts.
+pub fn combine(reports: Vec<NessusReport>) -> (NessusReport, MergeSummary) {
+    let mut merged = crate::parser::concat_reports(reports);
+    let summary = merge_hosts(&mut merged);
+    (merged, summary)
+}
+
+/// Collapse hosts of `report` that describe the same machine.
+///
+/// The first host of each group survives. Its empty fields are filled from the
+/// other hosts, its scan window widens to cover theirs, and their differing
+/// addresses and names are kept as `alias-*` host properties.
+pub fn merge_hosts(report: &mut NessusReport) -> MergeSummary {
+    let hosts_before = report.hosts.len();
+    let groups = correlate(report);
+
+    let mut new_index = vec![0i32; hosts_before];
+    let mut slots: Vec<Option<Host>> = std::mem::take(&mut report.hosts)
+        .into_iter()
+        .map(Some)
+        .collect();
+    let mut aliases: Vec<(i32, &'static str, String)> = Vec::new();
+    for (asset, members) in groups.iter().enumerate() {
+        let asset = asset as i32;
+        let mut survivor = slots[members[0]].take().expect("host taken once");
+        for &m in members {
+            new_index[m] = asset;
+        }
+        for &m in &members[1..] {
+            let other = slots[m].take().expect("host taken once");
+            absorb(&mut survivor, other, |name, value| aliases.push((asset, name, value)));
+        }
+        survivor.id = asset;
+        report.hosts.push(survivor);
+    }
+
+    let remap = |id: &mut Option<i32>| {
+        if let Some(v) = id
+            && let Some(n) = usize::try_from(*v).ok().and_then(|i| new_index.get(i))
+        {
+            *v = *n;
+        }
+    };
+    for it in &mut report.items {
+        remap(&mut it.host_id);
+    }
+    for p in &mut report.patches {
+        remap(&mut p.host_id);
+    }
+    for hp in &mut report.host_properties {
+        remap(&mut hp.host_id);
+    }
+    for sd in &mut report.service_descriptions {
+        remap(&mut sd.host_id);
+    }
+
+    for (host_id, name, value) in aliases {
+        report.host_properties.push(HostProperty {
+            host_id: Some(host_id),
+            name: Some(name.to_string()),
+            value: Some(value),
+            ..HostProperty::default()
+        });
+    }
+    let mut seen = HashSet::new();
+    report
+        .host_properties
+        .retain(|p| seen.insert((p.host_id, p.name.clone(), p.value.clone())));
+    let mut seen = HashSet::new();
+    report
+        .patches
+        .retain(|p| seen.insert((p.host_id, p.name.clone(), p.value.clone())));
+    dedup_services(report);
+
+    MergeSummary {
+        hosts_before,
+        assets: report.hosts.len(),
+    }
+}
+
+/// Group host indices by shared identifiers, ordered by their first member.
+fn correlate(report: &NessusReport) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..report.hosts.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut owner: HashMap<(&'static str, String), usize> = HashMap::new();
+    for (idx, keys) in identifiers(report).into_iter().enumerate() {
+        for key in keys {
+            match owner.get(&key) {
+                Some(&other) => {
+                    let (a, b) = (find(&mut parent, idx), find(&mut parent, other));
+                    // The lower index stays the root so the earliest host survives.
+                    parent[a.max(b)] = a.min(b);
+                }
+                None => {
+                    owner.insert(key, idx);
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of: HashMap<usize, usize> = HashMap::new();
+    for idx in 0..report.hosts.len() {
+        let root = find(&mut parent, idx);
+        let g = *group_of.entry(root).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[g].push(idx);
+    }
+    groups
+}
+
+/// Normalized identifiers of each host.
+fn identifiers(report: &NessusReport) -> Vec<BTreeSet<(&'static str, String)>> {
+    let mut ids: Vec<BTreeSet<(&'static str, String)>> =
+        report.hosts.iter().map(host_identifiers).collect();
+    for p in &report.host_properties {
+        if p.name.as_deref() != Some("bios-uuid") {
+            continue;
+        }
+        let Some(set) = p.host_id.and_then(|h| ids.get_mut(h as usize)) else {
+            continue;
+        };
+        if let Some(uuid) = identifier(p.value.as_deref()) {
+            set.insert(("bios-uuid", uuid));
+        }
+    }
+    ids
+}
+
+fn host_identifiers(h: &Host) -> BTreeSet<(&'static str, String)> {
+    let mut set = BTreeSet::new();
+    if let Some(ip) = identifier(h.ip.as_deref()) {
+        set.insert(("ip", ip));
+    }
+    if let Some(fqdn) = identifier(h.fqdn.as_deref()) {
+        set.insert(("fqdn", fqdn));
+    }
+    if let Some(netbios) = identifier(h.netbios.as_deref()) {
+        set.insert(("netbios", netbios));
+    }
+    // Nessus lists every interface MAC in one field.
+    for mac in h.mac.as_deref().unwrap_or("").split([' ', '\n', ',', ';']) {
+        if let Some(mac) = identifier(Some(mac)) {
+            set.insert(("mac", mac));
+        }
+    }
+    set
+}
+
+/// Fold `other` into `survivor`, reporting identifiers that differ.
+fn absorb(survivor: &mut Host, other: Host, mut alias: impl FnMut(&'static str, String)) {
+    let fields = [
+        ("alias-ip", &mut survivor.ip, other.ip),
+        ("alias-fqdn", &mut survivor.fqdn, other.fqdn),
+        ("alias-netbios-name", &mut survivor.netbios, other.netbios),
+        ("alias-name", &mut survivor.name, other.name),
+        ("alias-mac-address", &mut survivor.mac, other.mac),
+    ];
+    for (name, mine, theirs) in fields {
+        match (mine.as_deref(), theirs) {
+            (_, None) => {}
+            (None, Some(v)) => *mine = Some(v),
+            (Some(m), Some(v)) => {
+                if normalized(Some(m)) != normalized(Some(&v)) {
+                    alias(name, v);
+                }
+            }
+        }
+    }
+    if survivor.os.is_none() {
+        survivor.os = other.os;
+    }
+    if survivor.notes.is_none() {
+        survivor.notes = other.notes;
+    }
+    survivor.start = match (survivor.start, other.start) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    survivor.end = survivor.end.max(other.end);
+    survivor.risk_score = survivor.risk_score.max(other.risk_score);
+}
+
+/// Keep one service description per host, port and protocol, filling empty
+/// names and banners from the duplicates.
+fn dedup_services(report: &mut NessusReport) {
+    let mut first: HashMap<(i32, i32, String), usize> = HashMap::new();
+    let mut keep = Vec::with_capacity(report.service_descriptions.len());
+    for sd in std::mem::take(&mut report.service_descriptions) {
+        let (Some(host), Some(port)) = (sd.host_id, sd.port) else {
+            keep.push(sd);
+            continue;
+        };
+        let proto = sd.protocol.as_deref().unwrap_or("").to_ascii_lowercase();
+        match first.get(&(host, port, proto.clone())) {
+            Some(&idx) => {
+                let existing: &mut crate::models::ServiceDescription = &mut keep[idx];
+                if existing.svc_name.is_none() {
+                    existing.svc_name = sd.svc_name;
+                }
+                if existing.description.as_deref().is_none_or(str::is_empty) {
+                    existing.description = sd.description;
+                }
+            }
+            None => {
+                first.insert((host, port, proto), keep.len());
+                keep.push(sd);
+            }
+        }
+    }
+    report.service_descriptions = keep;
+}
+
+/// [`normalized`] value of an identifier, or `None` for placeholders.
+fn identifier(value: Option<&str>) -> Option<String> {
+    normalized(value).filter(|v| !PLACEHOLDER_IDENTIFIERS.contains(&v.as_str()))
+}
+
+/// Lowercase with surrounding whitespace and trailing dots removed; dashes in
+/// MAC addresses become colons.
+fn normalized(value: Option<&str>) -> Option<String> {
+    let v = value?.trim().trim_end_matches('.');
+    if v.is_empty() {
+        return None;
+    }
+    let v = v.to_ascii_lowercase();
+    let is_mac = v.len() == 17 && v.split(['-', ':']).all(|p| p.len() == 2);
+    Some(if is_mac { v.replace('-', ":") } else { v })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Item;
+
+    fn host(ip: Option<&str>, mac: Option<&str>, fqdn: Option<&str>) -> Host {
+        Host {
+            mac: mac.map(Into::into),
+            ip: ip.map(Into::into),
+            fqdn: fqdn.map(Into::into),
+            ..Host::default()
+        }
+    }
+
+    fn item(host_id: i32, plugin_id: i32) -> Item {
+        Item {
+            host_id: Some(host_id),
+            plugin_id: Some(plugin_id),
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn merges_hosts_transitively() {
+        let mut report = NessusReport {
+            hosts: vec![
+                host(Some("10.0.0.5"), None, Some("web.example.com")),
+                host(Some("10.0.0.9"), None, None),
+                host(Some("10.0.0.6"), Some("00-50-56-AA-BB-CC"), Some("WEB.example.com.")),
+                host(None, Some("00:50:56:aa:bb:cc"), None),
+                host(Some("10.0.0.7"), None, None),
+            ],
+            items: vec![item(0, 1), item(2, 2), item(3, 3), item(4, 4)],
+            host_properties: vec![
+                HostProperty {
+                    host_id: Some(1),
+                    name: Some("bios-uuid".into()),
+                    value: Some("4C4C-1".into()),
+                    ..HostProperty::default()
+                },
+                HostProperty {
+                    host_id: Some(4),
+                    name: Some("bios-uuid".into()),
+                    value: Some("4c4c-1".into()),
+                    ..HostProperty::default()
+                },
+            ],
+            ..NessusReport::default()
+        };
+        let summary = merge_hosts(&mut report);
+        assert_eq!(summary, MergeSummary { hosts_before: 5, assets: 2 });
+        assert_eq!(report.hosts[0].ip.as_deref(), Some("10.0.0.5"));
+        assert_eq!(report.hosts[0].mac.as_deref(), Some("00-50-56-AA-BB-CC"));
+        assert_eq!(report.hosts[1].ip.as_deref(), Some("10.0.0.9"));
+        let host_ids: Vec<_> = report.items.iter().map(|i| i.host_id).collect();
+        assert_eq!(host_ids, [Some(0), Some(0), Some(0), Some(1)]);
+        assert!(report.host_properties.iter().any(|p| {
+            p.host_id == Some(0)
+                && p.name.as_deref() == Some("alias-ip")
+                && p.value.as_deref() == Some("10.0.0.6")
+        }));
+        // Case-only differences are not aliases.
+        assert!(!report
+            .host_properties
+            .iter()
+            .any(|p| p.name.as_deref() == Some("alias-fqdn")));
+    }
+
+    #[test]
+    fn placeholder_identifiers_do_not_link_hosts() {
+        let mut report = NessusReport {
+            hosts: vec![
+                host(Some("10.0.0.5"), Some("00:00:00:00:00:00"), Some("localhost")),
+                host(Some("10.0.0.6"), Some("00-00-00-00-00-00"), Some("LOCALHOST.")),
+                host(Some("10.0.0.7"), None, Some("localhost.localdomain")),
+                host(Some("10.0.0.8"), None, Some("localhost.localdomain")),
+            ],
+            host_properties: [0, 1]
+                .into_iter()
+                .map(|h| HostProperty {
+                    host_id: Some(h),
+                    name: Some("bios-uuid".into()),
+                    value: Some("00000000-0000-0000-0000-000000000000".into()),
+                    ..HostProperty::default()
+                })
+                .collect(),
+            ..NessusReport::default()
+        };
+        let summary = merge_hosts(&mut report);
+        assert_eq!(summary, MergeSummary { hosts_before: 4, assets: 4 });
+    }
+
+    #[test]
+    fn combine_offsets_relationships() {
+        let first = NessusReport {
+            hosts: vec![host(Some("10.0.0.5"), None, None)],
+            items: vec![item(0, 1)],
+            ..NessusReport::default()
+        };
+        let mut second = NessusReport {
+            hosts: vec![
+                host(Some("10.0.0.8"), None, None),
+                host(Some("10.0.0.5"), None, None),
+            ],
+            items: vec![item(0, 7), item(1, 8)],
+            references: vec![crate::models::Reference {
+                item_id: Some(1),
+                ..crate::models::Reference::default()
+            }],
+            ..NessusReport::default()
+        };
+        second.set_scanner("Nmap", None);
+        let (merged, summary) = combine(vec![first, second]);
+        assert_eq!(summary.assets, 2);
+        assert_eq!(merged.items[2].host_id, Some(0));
+        assert_eq!(merged.items[1].host_id, Some(1));
+        assert_eq!(merged.references[0].item_id, Some(2));
+        assert_eq!(merged.scanner.scanner_type, "Nmap");
+    }
+}
@@ -1 +1,4 @@
-pub mod risk;
+pub mod diff;
+pub mod merge;
+pub mod scoring;
+pub mod suppression;
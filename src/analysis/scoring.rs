@@ -0,0 +1,393 @@
+//! Configurable risk scoring of findings, hosts and the whole network.
+//!
+//! A finding's score is the sum of weighted factors:
+//!
+//! - **cvss**: the preferred CVSS base score times the weight for the version
+//!   that supplied it
+//! - **exploit**: points when a public exploit or exploit framework module
+//!   exists
+//! - **malware**: points when the vulnerability is exploited by malware
+//! - **epss** and **vpr**: points per EPSS probability and VPR score, when the
+//!   plugin has them
+//! - **age**: points per year since the vulnerability was published, capped
+//! - **criticality**: the subtotal scaled by the multiplier of the host's
+//!   `asset-criticality` tier, shown as the points it adds or removes
+//!
+//! A host scores the sum of its findings and the network the sum of all
+//! findings. The weights come from the `risk_scoring` configuration section.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use chrono::NaiveDate;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::host_property::ASSET_CRITICALITY;
+use crate::models::{CvssScores, CvssVersion, Plugin};
+use crate::parser::NessusReport;
+use crate::postprocess::ReportIndex;
+
+/// Weight of the CVSS base score by the version that supplied it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CvssWeights {
+    pub v2: f32,
+    pub v3: f32,
+    pub v4: f32,
+}
+
+impl Default for CvssWeights {
+    fn default() -> Self {
+        Self {
+            v2: 0.8,
+            v3: 0.8,
+            v4: 0.8,
+        }
+    }
+}
+
+impl CvssWeights {
+    fn weight(&self, version: CvssVersion) -> f32 {
+        match version {
+            CvssVersion::V2 => self.v2,
+            CvssVersion::V3 => self.v3,
+            CvssVersion::V4 => self.v4,
+        }
+    }
+}
+
+/// Weights of the risk scoring model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskScoring {
+    /// Weight applied to the CVSS base score.
+    pub cvss: CvssWeights,
+    /// Base score assumed for plugins without any CVSS score.
+    pub missing_cvss: f32,
+    /// Points added when an exploit is available.
+    pub exploit: f32,
+    /// Points added when the vulnerability is exploited by malware.
+    pub malware: f32,
+    /// Points per unit of EPSS probability (0 to 1).
+    pub epss: f32,
+    /// Points per VPR point (0 to 10).
+    pub vpr: f32,
+    /// Points per year since the vulnerability was published.
+    pub age_per_year: f32,
+    /// Upper bound of the age points.
+    pub age_max: f32,
+    /// Score multiplier by asset criticality tier. Hosts without a tier, or
+    /// with one missing here, are not scaled.
+    pub criticality: BTreeMap<String, f32>,
+}
+
+impl Default for RiskScoring {
+    fn default() -> Self {
+        Self {
+            cvss: CvssWeights::default(),
+            missing_cvss: 1.0,
+            exploit: 2.0,
+            malware: 2.0,
+            epss: 5.0,
+            vpr: 0.2,
+            age_per_year: 0.5,
+            age_max: 2.0,
+            criticality: [("critical", 1.5), ("high", 1.25), ("medium", 1.0), ("low", 0.75)]
+                .into_iter()
+                .map(|(tier, m)| (tier.to_string(), m))
+                .collect(),
+        }
+    }
+}
+
+/// The facts about a finding that the model weighs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemInputs<'a> {
+    /// Preferred CVSS base score and the version it came from.
+    pub cvss: Option<(CvssVersion, f32)>,
+    pub exploit: bool,
+    pub malware: bool,
+    /// EPSS probability between 0 and 1.
+    pub epss: Option<f32>,
+    pub vpr: Option<f32>,
+    /// Years since the vulnerability was published.
+    pub age_years: Option<f32>,
+    /// Criticality tier of the host.
+    pub criticality: Option<&'a str>,
+}
+
+impl<'a> ItemInputs<'a> {
+    /// Collect the inputs of a finding of `plugin` on a host of the given
+    /// criticality tier.
+    pub fn new(
+        plugin: &Plugin,
+        criticality: Option<&'a str>,
+        cvss_version: CvssVersion,
+        today: NaiveDate,
+    ) -> Self {
+        let flag =
+            |v: &Option<String>| v.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("true"));
+        Self {
+            cvss: plugin.preferred_cvss(cvss_version),
+            exploit: flag(&plugin.exploit_available)
+                || flag(&plugin.exploit_framework_metasploit)
+                || flag(&plugin.exploit_framework_core)
+                || flag(&plugin.exploit_framework_canvas),
+            malware: flag(&plugin.exploited_by_malware),
+            // Some exports give EPSS as a percentage.
+            epss: plugin
+                .epss_score
+                .map(|e| if e > 1.0 { e / 100.0 } else { e }),
+            vpr: plugin.vpr_score,
+            age_years: plugin
+                .vuln_publication_date
+                .map(|d| ((today - d.date()).num_days().max(0) as f32) / 365.25),
+            criticality,
+        }
+    }
+}
+
+/// Points contributed by each factor. Hosts and the network add up the
+/// explanations of their findings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Explanation {
+    pub cvss: f32,
+    pub exploit: f32,
+    pub malware: f32,
+    pub epss: f32,
+    pub vpr: f32,
+    pub age: f32,
+    pub criticality: f32,
+}
+
+impl Explanation {
+    /// Factor names and their points, in a fixed order.
+    pub fn factors(&self) -> [(&'static str, f32); 7] {
+        [
+            ("cvss", self.cvss),
+            ("exploit", self.exploit),
+            ("malware", self.malware),
+            ("epss", self.epss),
+            ("vpr", self.vpr),
+            ("age", self.age),
+            ("criticality", self.criticality),
+        ]
+    }
+
+    pub fn total(&self) -> f32 {
+        self.factors().iter().map(|(_, points)| points).sum()
+    }
+
+    pub fn add(&mut self, other: &Explanation) {
+        self.cvss += other.cvss;
+        self.exploit += other.exploit;
+        self.malware += other.malware;
+        self.epss += other.epss;
+        self.vpr += other.vpr;
+        self.age += other.age;
+        self.criticality += other.criticality;
+    }
+}
+
+impl RiskScoring {
+    /// Score a finding factor by factor.
+    pub fn explain(&self, inputs: &ItemInputs) -> Explanation {
+        let cvss = match inputs.cvss {
+            Some((version, score)) => score * self.cvss.weight(version),
+            None => self.missing_cvss * self.cvss.weight(CvssVersion::V2),
+        };
+        let mut e = Explanation {
+            cvss,
+            exploit: if inputs.exploit { self.exploit } else { 0.0 },
+            malware: if inputs.malware { self.malware } else { 0.0 },
+            epss: inputs.epss.map_or(0.0, |p| p * self.epss),
+            vpr: inputs.vpr.map_or(0.0, |v| v * self.vpr),
+            age: inputs
+                .age_years
+                .map_or(0.0, |y| (y * self.age_per_year).min(self.age_max)),
+            criticality: 0.0,
+        };
+        e.criticality = e.total() * (self.criticality_multiplier(inputs.criticality) - 1.0);
+        e
+    }
+
+    /// Multiplier for a criticality tier; 1 when the tier is unknown.
+    pub fn criticality_multiplier(&self, tier: Option<&str>) -> f32 {
+        tier.and_then(|t| self.criticality.get(&t.trim().to_ascii_lowercase()))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// One line per factor of a finding, with the inputs that produced it.
+    pub fn describe(
+        &self,
+        inputs: &ItemInputs,
+        e: &Explanation,
+    ) -> Vec<(&'static str, f32, String)> {
+        let cvss = match inputs.cvss {
+            Some((version, score)) => {
+                format!("{version} base {score:.1} x {}", self.cvss.weight(version))
+            }
+            None => format!(
+                "no CVSS score, assumed {:.1} x {}",
+                self.missing_cvss, self.cvss.v2
+            ),
+        };
+        let either = |b: bool, yes: &str, no: &str| if b { yes } else { no }.to_string();
+        let age = match inputs.age_years {
+            Some(y) => format!(
+                "{y:.1} years x {} (max {})",
+                self.age_per_year, self.age_max
+            ),
+            None => "no publication date".to_string(),
+        };
+        let tier = match inputs.criticality {
+            Some(t) => format!("tier {t} x {}", self.criticality_multiplier(Some(t))),
+            None => "no criticality tier".to_string(),
+        };
+        vec![
+            ("cvss", e.cvss, cvss),
+            ("exploit", e.exploit, either(inputs.exploit, "exploit available", "no known exploit")),
+            ("malware", e.malware, either(inputs.malware, "used by malware", "not used by malware")),
+            ("epss", e.epss, match inputs.epss {
+                Some(p) => format!("EPSS {p:.3} x {}", self.epss),
+                None => "no EPSS score".to_string(),
+            }),
+            ("vpr", e.vpr, match inputs.vpr {
+                Some(v) => format!("VPR {v:.1} x {}", self.vpr),
+                None => "no VPR score".to_string(),
+            }),
+            ("age", e.age, age),
+            ("criticality", e.criticality, tier),
+        ]
+    }
+}
+
+/// Criticality tiers of the hosts of `report`, keyed by host ID.
+pub fn host_criticality(report: &NessusReport) -> HashMap<i32, &str> {
+    report
+        .host_properties
+        .iter()
+        .filter(|hp| hp.name.as_deref() == Some(ASSET_CRITICALITY))
+        .filter_map(|hp| Some((hp.host_id?, hp.value.as_deref()?)))
+        .collect()
+}
+
+/// Explanations of every finding, host and the network of a report.
+#[derive(Debug, Default)]
+pub struct ReportRisk {
+    /// By position in `report.items`; `None` for suppressed findings and those
+    /// without a plugin.
+    pub items: Vec<Option<Explanation>>,
+    /// By position in `report.hosts`.
+    pub hosts: Vec<Explanation>,
+    pub network: Explanation,
+}
+
+/// Score every finding of `report` with its `risk_scoring` weights, looking
+/// plugins and host findings up through `index`.
+pub fn explain_report(report: &NessusReport, index: &ReportIndex, today: NaiveDate) -> ReportRisk {
+    let model = &report.risk_scoring;
+    let tiers = host_criticality(report);
+    let items: Vec<Option<Explanation>> = report
+        .items
+        .par_iter()
+        .map(|item| {
+            // Accepted risks do not count towards host or network risk.
+            if item.suppressed == Some(true) {
+                return None;
+            }
+            let plugin = &report.plugins[index.plugin_index(item.plugin_id?)?];
+            let tier = item.host_id.and_then(|h| tiers.get(&h).copied());
+            Some(model.explain(&ItemInputs::new(plugin, tier, report.cvss_version, today)))
+        })
+        .collect();
+
+    let sum = |positions: &[usize]| {
+        let mut e = Explanation::default();
+        for item in positions.iter().filter_map(|&idx| items[idx].as_ref()) {
+            e.add(item);
+        }
+        e
+    };
+    let mut network = Explanation::default();
+    for e in items.iter().flatten() {
+        network.add(e);
+    }
+    // Findings without a host belong to the only host of a single-host scan.
+    let unhosted = sum(index.items_for_host(None));
+    let hosts = report
+        .hosts
+        .iter()
+        .map(|h| {
+            let mut e = sum(index.items_for_host(Some(h.id)));
+            if report.hosts.len() <= 1 {
+                e.add(&unhosted);
+            }
+            e
+        })
+        .collect();
+    ReportRisk {
+        items,
+        hosts,
+        network,
+    }
+}
+
+/// Write the factor table of an explanation.
+pub fn write_factors(
+    w: &mut dyn Write,
+    rows: impl IntoIterator<Item = (&'static str, f32, String)>,
+) -> io::Result<()> {
+    for (name, points, detail) in rows {
+        writeln!(w, "  {name:<12} {points:>8.2}  {detail}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+    }
+
+    #[test]
+    fn exploitable_findings_score_higher() {
+        let model = RiskScoring::default();
+        let mut plugin = Plugin {
+            cvss3_base_score: Some(7.5),
+            ..Plugin::default()
+        };
+        let plain = model.explain(&ItemInputs::new(&plugin, None, CvssVersion::V3, today()));
+        plugin.exploit_available = Some("true".into());
+        plugin.exploited_by_malware = Some("true".into());
+        let exploited = model.explain(&ItemInputs::new(&plugin, None, CvssVersion::V3, today()));
+        assert_eq!(plain.total(), 6.0);
+        assert_eq!(exploited.total(), 10.0);
+    }
+
+    #[test]
+    fn weighs_epss_age_and_criticality() {
+        let model = RiskScoring::default();
+        let plugin = Plugin {
+            cvss_base_score: Some(5.0),
+            epss_score: Some(0.5),
+            vuln_publication_date: NaiveDate::from_ymd_opt(2014, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0),
+            ..Plugin::default()
+        };
+        let inputs = ItemInputs::new(&plugin, Some("Critical"), CvssVersion::V2, today());
+        let e = model.explain(&inputs);
+        assert_eq!(e.cvss, 4.0);
+        assert_eq!(e.epss, 2.5);
+        // Ten years at 0.5 a year is capped at 2.
+        assert_eq!(e.age, 2.0);
+        assert_eq!(e.criticality, 4.25);
+        assert_eq!(e.total(), 12.75);
+        assert_eq!(model.describe(&inputs, &e)[6].2, "tier Critical x 1.5");
+    }
+}
@@ -0,0 +1,414 @@
+//! Accepted-risk register.
+//!
+//! A suppressions file lists findings a client formally accepted. Each
+//! `[[suppression]]` entry matches a plugin ID and optionally a host (IP,
+//! CIDR or name), a port and a plugin output regex, and records who approved
+//! it, why, and until when. Matching findings are marked `suppressed` and
+//! hidden like rolled-up findings: their severity moves to `real_severity`
+//! and `severity` becomes -1. Once an entry expires its findings are reported
+//! again.
+
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use ipnet::IpNet;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::models::{Host, Item};
+use crate::parser::NessusReport;
+
+/// Days before expiry from which a suppression is warned about.
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+struct SuppressionDef {
+    id: Option<String>,
+    plugin_id: i32,
+    host: Option<String>,
+    port: Option<i32>,
+    output: Option<String>,
+    justification: String,
+    approver: String,
+    expires: toml::value::Datetime,
+}
+
+#[derive(Debug, Deserialize)]
+struct SuppressionsFile {
+    #[serde(default)]
+    suppression: Vec<SuppressionDef>,
+}
+
+#[derive(Debug, Clone)]
+enum HostMatch {
+    Network(IpNet),
+    Name(String),
+}
+
+/// One accepted risk.
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    /// Identifier from the file, or `#n` for the n-th entry.
+    pub id: String,
+    pub plugin_id: i32,
+    /// Host as written in the file; `None` matches every host.
+    pub host: Option<String>,
+    pub port: Option<i32>,
+    /// Plugin output pattern as written in the file.
+    pub output: Option<String>,
+    pub justification: String,
+    pub approver: String,
+    /// Last day the suppression applies.
+    pub expires: NaiveDate,
+    host_match: Option<HostMatch>,
+    output_re: Option<Regex>,
+}
+
+impl Suppression {
+    fn from_def(n: usize, def: SuppressionDef) -> Result<Self, String> {
+        let expires = def.expires.date.ok_or("expires must be a date")?;
+        let expires =
+            NaiveDate::from_ymd_opt(expires.year.into(), expires.month.into(), expires.day.into())
+                .ok_or("expires is not a valid date")?;
+        let host_match = def.host.as_deref().map(|h| {
+            let h = h.trim();
+            if let Ok(net) = h.parse::<IpNet>() {
+                HostMatch::Network(net)
+            } else if let Ok(ip) = h.parse::<IpAddr>() {
+                HostMatch::Network(ip.into())
+            } else {
+                HostMatch::Name(h.to_ascii_lowercase())
+            }
+        });
+        let output_re = def
+            .output
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("output: {e}"))?;
+        Ok(Self {
+            id: def.id.unwrap_or_else(|| format!("#{n}")),
+            plugin_id: def.plugin_id,
+            host: def.host,
+            port: def.port,
+            output: def.output,
+            justification: def.justification,
+            approver: def.approver,
+            expires,
+            host_match,
+            output_re,
+        })
+    }
+
+    /// Whether the suppression no longer applies on `today`.
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        today > self.expires
+    }
+
+    /// Whether `item`, found on `host`, is covered by this entry.
+    pub fn matches(&self, item: &Item, host: Option<&Host>) -> bool {
+        if item.plugin_id != Some(self.plugin_id) {
+            return false;
+        }
+        if self.port.is_some_and(|p| item.port != Some(p)) {
+            return false;
+        }
+        let host_ok = match &self.host_match {
+            None => true,
+            Some(HostMatch::Network(net)) => host
+                .and_then(|h| h.ip.as_deref())
+                .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+                .is_some_and(|ip| net.contains(&ip)),
+            Some(HostMatch::Name(name)) => host.is_some_and(|h| {
+                [&h.name, &h.fqdn, &h.netbios, &h.ip]
+                    .into_iter()
+                    .flatten()
+                    .any(|n| n.eq_ignore_ascii_case(name))
+            }),
+        };
+        host_ok
+            && self.output_re.as_ref().is_none_or(|re| {
+                item.plugin_output.as_deref().is_some_and(|o| re.is_match(o))
+            })
+    }
+}
+
+/// Locate the suppressions file: `RISU_SUPPRESSIONS_FILE`, then
+/// `./suppressions.toml`, then `~/.risu/suppressions.toml`.
+pub fn find_suppressions_file() -> Option<PathBuf> {
+    crate::config::find_config_file("RISU_SUPPRESSIONS_FILE", "suppressions.toml")
+}
+
+/// Read a suppressions file. Entries with an invalid date or regex are
+/// reported and skipped, so their findings stay in the report.
+pub fn load(path: &Path) -> Result<Vec<Suppression>, Error> {
+    let txt = fs::read_to_string(path)?;
+    let file: SuppressionsFile = toml::from_str(&txt)
+        .map_err(|e| Error::Config(format!("suppressions file '{}': {e}", path.display())))?;
+    let mut out = Vec::new();
+    for (n, def) in file.suppression.into_iter().enumerate() {
+        match Suppression::from_def(n + 1, def) {
+            Ok(s) => out.push(s),
+            Err(e) => eprintln!("Skipping suppression {} in '{}': {e}", n + 1, path.display()),
+        }
+    }
+    Ok(out)
+}
+
+fn item_host<'a>(report: &'a NessusReport, item: &Item) -> Option<&'a Host> {
+    match item.host_id {
+        // Host ids survive `sort_hosts`; positions in `report.hosts` do not.
+        Some(hid) => report.hosts.iter().find(|h| h.id == hid),
+        // Findings without a host belong to the only host of a single-host scan.
+        None if report.hosts.len() == 1 => report.hosts.first(),
+        None => None,
+    }
+}
+
+/// The first entry of `report.suppressions` covering `item`, expired or not.
+pub fn find_for<'a>(report: &'a NessusReport, item: &Item) -> Option<&'a Suppression> {
+    let host = item_host(report, item);
+    report.suppressions.iter().find(|s| s.matches(item, host))
+}
+
+/// Number of findings in `report` covered by `suppression`.
+pub fn matching_items(report: &NessusReport, suppression: &Suppression) -> usize {
+    report
+        .items
+        .iter()
+        .filter(|item| suppression.matches(item, item_host(report, item)))
+        .count()
+}
+
+/// Mark the findings covered by an active entry of `report.suppressions` as
+/// suppressed, and report again those whose entry has expired or is gone.
+/// Rolled-up findings are left alone. Returns the number of suppressed
+/// findings.
+pub fn apply(report: &mut NessusReport, today: NaiveDate) -> usize {
+    let active: Vec<bool> = report
+        .suppressions
+        .iter()
+        .map(|s| !s.is_expired(today))
+        .collect();
+    let covered: Vec<bool> = report
+        .items
+        .iter()
+        .map(|item| {
+            let host = item_host(report, item);
+            report
+                .suppressions
+                .iter()
+                .zip(&active)
+                .any(|(s, active)| *active && s.matches(item, host))
+        })
+        .collect();
+    let mut count = 0;
+    for (item, covered) in report.items.iter_mut().zip(covered) {
+        let suppressed = item.suppressed == Some(true);
+        // Rolled-up findings already keep their severity in `real_severity`
+        // and are reported through their rollup.
+        if !suppressed && item.severity == Some(-1) {
+            continue;
+        }
+        if covered && !suppressed {
+            item.suppressed = Some(true);
+            item.real_severity = item.severity;
+            item.severity = Some(-1);
+        } else if !covered && suppressed {
+            item.suppressed = None;
+            item.severity = item.real_severity.take();
+        }
+        count += usize::from(covered);
+    }
+    count
+}
+
+/// Warnings for entries that have expired or expire within `within_days`.
+pub fn expiry_warnings(
+    suppressions: &[Suppression],
+    today: NaiveDate,
+    within_days: i64,
+) -> Vec<String> {
+    suppressions
+        .iter()
+        .filter_map(|s| {
+            let left = (s.expires - today).num_days();
+            if left < 0 {
+                Some(format!(
+                    "suppression {} (plugin {}) expired on {}; its findings are reported again",
+                    s.id, s.plugin_id, s.expires
+                ))
+            } else if left <= within_days {
+                Some(format!(
+                    "suppression {} (plugin {}) expires on {} ({left} days left)",
+                    s.id, s.plugin_id, s.expires
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Load the suppressions file, if there is one, into `report`, print expiry
+/// warnings and mark the covered findings.
+pub fn apply_register(report: &mut NessusReport, today: NaiveDate) {
+    let Some(path) = find_suppressions_file() else {
+        return;
+    };
+    match load(&path) {
+        Ok(list) => {
+            for warning in expiry_warnings(&list, today, EXPIRY_WARNING_DAYS) {
+                eprintln!("Warning: {warning}");
+            }
+            report.suppressions = list;
+            let count = apply(report, today);
+            tracing::info!("Suppressed {count} findings from {}", path.display());
+        }
+        Err(e) => eprintln!("Failed to load suppressions: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register(toml: &str) -> Vec<Suppression> {
+        let file: SuppressionsFile = toml::from_str(toml).unwrap();
+        file.suppression
+            .into_iter()
+            .enumerate()
+            .map(|(n, d)| Suppression::from_def(n + 1, d).unwrap())
+            .collect()
+    }
+
+    fn host(ip: &str, name: &str) -> Host {
+        Host { name: Some(name.to_string()), ip: Some(ip.to_string()), ..Host::default() }
+    }
+
+    fn item(host_id: i32, plugin_id: i32, port: i32, output: &str) -> Item {
+        Item {
+            host_id: Some(host_id),
+            plugin_id: Some(plugin_id),
+            port: Some(port),
+            plugin_output: Some(output.to_string()),
+            severity: Some(2),
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn suppresses_matching_findings_until_expiry() {
+        let mut report = NessusReport {
+            hosts: vec![host("10.0.0.5", "web01"), host("10.1.0.5", "db01")],
+            items: vec![
+                item(0, 100, 443, "TLSv1.0 enabled"),
+                item(0, 100, 8443, "TLSv1.0 enabled"),
+                item(1, 100, 443, "TLSv1.0 enabled"),
+                item(1, 200, 22, "weak mac"),
+            ],
+            ..NessusReport::default()
+        };
+        report.hosts[1].id = 1;
+        report.suppressions = register(
+            r#"
+            [[suppression]]
+            id = "AR-1"
+            plugin_id = 100
+            host = "10.0.0.0/24"
+            port = 443
+            output = "TLSv1\\.0"
+            justification = "Legacy clients"
+            approver = "CISO"
+            expires = 2026-06-30
+
+            [[suppression]]
+            plugin_id = 200
+            host = "DB01"
+            justification = "Vendor appliance"
+            approver = "CISO"
+            expires = 2026-01-31
+            "#,
+        );
+
+        let today = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(apply(&mut report, today), 2);
+        let severities: Vec<_> = report.items.iter().map(|i| i.severity).collect();
+        assert_eq!(severities, [Some(-1), Some(2), Some(2), Some(-1)]);
+        assert_eq!(report.items[0].real_severity, Some(2));
+        assert_eq!(report.suppressions[1].id, "#2");
+        assert_eq!(expiry_warnings(&report.suppressions, today, 30).len(), 1);
+
+        // After the second entry expires its finding is reported again.
+        let later = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        assert_eq!(apply(&mut report, later), 1);
+        assert_eq!(report.items[3].severity, Some(2));
+        assert_eq!(report.items[3].suppressed, None);
+        let warnings = expiry_warnings(&report.suppressions, later, 30);
+        assert!(warnings[0].contains("#2") && warnings[0].contains("expired on 2026-01-31"));
+    }
+
+    #[test]
+    fn host_scoped_suppression_follows_sorted_hosts() {
+        let mut report = NessusReport {
+            hosts: vec![host("10.0.0.9", "dc01"), host("10.0.0.1", "web01")],
+            items: vec![item(0, 1002, 443, ""), item(1, 1002, 443, "")],
+            ..NessusReport::default()
+        };
+        report.hosts[1].id = 1;
+        crate::postprocess::sort_hosts::sort(&mut report);
+        assert_eq!(report.hosts[0].ip.as_deref(), Some("10.0.0.1"));
+        report.suppressions = register(
+            r#"
+            [[suppression]]
+            plugin_id = 1002
+            host = "10.0.0.9"
+            justification = "Accepted"
+            approver = "CISO"
+            expires = 2026-06-30
+            "#,
+        );
+
+        let today = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(apply(&mut report, today), 1);
+        assert_eq!(report.items[0].suppressed, Some(true));
+        assert_eq!(report.items[1].suppressed, None);
+    }
+
+    #[test]
+    fn leaves_loaded_rolled_up_findings_alone() {
+        let report = NessusReport {
+            hosts: vec![host("10.0.0.5", "web01")],
+            items: vec![Item {
+                severity: Some(-1),
+                real_severity: Some(3),
+                ..item(0, 100, 443, "")
+            }],
+            ..NessusReport::default()
+        };
+        let mut conn = crate::db::establish(":memory:", "sqlite").unwrap();
+        crate::db::run_pending_migrations(&mut conn).unwrap();
+        let id = crate::persist::to_database(&mut conn, &report).unwrap();
+        let mut report = crate::loader::load_report(&mut conn, Some(id)).unwrap();
+        report.suppressions = register(
+            r#"
+            [[suppression]]
+            plugin_id = 100
+            justification = "Accepted"
+            approver = "CISO"
+            expires = 2026-01-31
+            "#,
+        );
+
+        for today in [(2026, 1, 15), (2026, 2, 1)] {
+            let today = NaiveDate::from_ymd_opt(today.0, today.1, today.2).unwrap();
+            assert_eq!(apply(&mut report, today), 0);
+            assert_eq!(report.items[0].severity, Some(-1));
+            assert_eq!(report.items[0].real_severity, Some(3));
+            assert_eq!(report.items[0].suppressed, None);
+        }
+    }
+}
@@ -17,10 +17,24 @@
 //! # Override plugin severities keyed by plugin ID
 //! severity_overrides:
 //!   41028: 0
+//! # CVSS version preferred for risk scores and templates (2, 3 or 4)
+//! cvss_version: 3
+//! # Weights of the risk score factors; omitted keys keep their defaults
+//! risk_scoring:
+//!   cvss: { v2: 0.8, v3: 0.8, v4: 0.8 }
+//!   exploit: 2.0
+//!   criticality: { critical: 1.5, high: 1.25, medium: 1.0, low: 0.75 }
 //! ```
 
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::Path};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::analysis::scoring::RiskScoring;
+use crate::models::CvssVersion;
 
 /// Application configuration loaded from a YAML file.
 #[derive(Debug, Serialize, Deserialize)]
@@ -61,6 +75,12 @@ pub struct Config {
     /// Override plugin severities keyed by plugin ID
     #[serde(default)]
     pub severity_overrides: HashMap<i32, i32>,
+    /// CVSS version preferred for risk scores and templates
+    #[serde(default)]
+    pub cvss_version: CvssVersion,
+    /// Weights of the risk scoring model
+    #[serde(default)]
+    pub risk_scoring: RiskScoring,
 }
 
 impl Default for Config {
@@ -78,6 +98,8 @@ impl Default for Config {
             report_prefix: None,
             template_settings: HashMap::new(),
             severity_overrides: HashMap::new(),
+            cvss_version: CvssVersion::default(),
+            risk_scoring: RiskScoring::default(),
         }
     }
 }
@@ -102,6 +124,28 @@ fn default_template_paths() -> Vec<String> {
     vec!["./templates".to_string()]
 }
 
+/// Locate a data file such as `rules.toml`: the path in `env_var` when it
+/// exists, then `./name`, then `~/.risu/name`.
+pub fn find_config_file(env_var: &str, name: &str) -> Option<PathBuf> {
+    if let Ok(p) = std::env::var(env_var) {
+        let pb = PathBuf::from(p);
+        if pb.exists() {
+            return Some(pb);
+        }
+    }
+    let cwd = PathBuf::from(name);
+    if cwd.exists() {
+        return Some(cwd);
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        let user = PathBuf::from(home).join(".risu").join(name);
+        if user.exists() {
+            return Some(user);
+        }
+    }
+    None
+}
+
 /// Write a configuration file containing default values to the given path.
 pub fn create_config(path: &Path) -> Result<(), crate::error::Error> {
     if path.exists() {
@@ -127,6 +171,12 @@ pub fn create_config(path: &Path) -> Result<(), crate::error::Error> {
             output.push_str("# Override plugin severities keyed by plugin ID\n");
             output.push_str("# severity_overrides:\n#   41028: 0\n");
         }
+        if line.starts_with("cvss_version:") {
+            output.push_str("# CVSS version preferred for risk scores and templates (2, 3 or 4)\n");
+        }
+        if line.starts_with("risk_scoring:") {
+            output.push_str("# Weights of the risk score factors (see `risu-rs risk`)\n");
+        }
         output.push_str(line);
         output.push('\n');
     }
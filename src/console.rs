@@ -1,13 +1,9 @@
-use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
 use rustyline::{DefaultEditor, error::ReadlineError};
-use std::path::Path;
 
-use crate::{config, error, models};
+use crate::{config, db, error, models};
 
-pub fn run(config_path: &Path) -> Result<(), error::Error> {
-    let cfg = config::load_config(config_path).unwrap_or_default();
-    let mut dconn = SqliteConnection::establish(&cfg.database_url)?;
+pub fn run(cfg: &config::Config) -> Result<(), error::Error> {
+    let mut dconn = db::establish(&cfg.database_url, &cfg.database_backend)?;
     let db_path = cfg
         .database_url
         .strip_prefix("sqlite://")
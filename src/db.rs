@@ -0,0 +1,132 @@
+//! Backend-neutral database connection.
+//!
+//! [`DbConnection`] wraps the connection types of every enabled Diesel
+//! backend so that persistence, loading and template helpers can share one
+//! code path. SQLite is always available; PostgreSQL and MySQL variants exist
+//! when the `postgres` and `mysql` features are enabled.
+
+use diesel::connection::Connection;
+use diesel::sql_types::BigInt;
+use diesel::sqlite::SqliteConnection;
+use diesel::{QueryResult, QueryableByName, RunQueryDsl};
+use diesel_migrations::MigrationHarness;
+
+use crate::error::Error;
+use crate::migrate::MIGRATIONS;
+
+// `derive(MultiConnection)` does not understand `#[cfg]` on variants, so the
+// enum is declared once per feature combination.
+macro_rules! db_connection {
+    ($($variant:ident($conn:ty)),+ $(,)?) => {
+        /// Connection to any of the compiled-in database backends.
+        #[derive(diesel::MultiConnection)]
+        pub enum DbConnection {
+            $($variant($conn)),+
+        }
+    };
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql")))]
+db_connection!(Sqlite(SqliteConnection));
+#[cfg(all(feature = "postgres", not(feature = "mysql")))]
+db_connection!(Sqlite(SqliteConnection), Postgres(diesel::pg::PgConnection));
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+db_connection!(Sqlite(SqliteConnection), Mysql(diesel::mysql::MysqlConnection));
+#[cfg(all(feature = "postgres", feature = "mysql"))]
+db_connection!(
+    Sqlite(SqliteConnection),
+    Postgres(diesel::pg::PgConnection),
+    Mysql(diesel::mysql::MysqlConnection),
+);
+
+impl From<SqliteConnection> for DbConnection {
+    fn from(conn: SqliteConnection) -> Self {
+        DbConnection::Sqlite(conn)
+    }
+}
+
+#[derive(QueryableByName)]
+struct LastId {
+    #[diesel(sql_type = BigInt)]
+    id: i64,
+}
+
+/// Open a connection for `backend` (`sqlite`, `postgres` or `mysql`).
+///
+/// Unknown backend names fall back to SQLite, matching [`crate::migrate::run`].
+pub fn establish(database_url: &str, backend: &str) -> Result<DbConnection, Error> {
+    match backend {
+        "postgres" => {
+            #[cfg(feature = "postgres")]
+            {
+                Ok(DbConnection::Postgres(diesel::pg::PgConnection::establish(
+                    database_url,
+                )?))
+            }
+            #[cfg(not(feature = "postgres"))]
+            {
+                Err(Error::Config("postgres feature not enabled".into()))
+            }
+        }
+        "mysql" => {
+            #[cfg(feature = "mysql")]
+            {
+                Ok(DbConnection::Mysql(diesel::mysql::MysqlConnection::establish(
+                    database_url,
+                )?))
+            }
+            #[cfg(not(feature = "mysql"))]
+            {
+                Err(Error::Config("mysql feature not enabled".into()))
+            }
+        }
+        _ => Ok(DbConnection::Sqlite(SqliteConnection::establish(
+            database_url,
+        )?)),
+    }
+}
+
+/// Apply any migrations that have not yet been run on `conn`.
+pub fn run_pending_migrations(conn: &mut DbConnection) -> Result<(), Error> {
+    match conn {
+        DbConnection::Sqlite(c) => c.run_pending_migrations(MIGRATIONS).map(|_| ()),
+        #[cfg(feature = "postgres")]
+        DbConnection::Postgres(c) => c.run_pending_migrations(MIGRATIONS).map(|_| ()),
+        #[cfg(feature = "mysql")]
+        DbConnection::Mysql(c) => c.run_pending_migrations(MIGRATIONS).map(|_| ()),
+    }
+    .map_err(Error::Migration)
+}
+
+/// Return the ID generated by the most recent `INSERT` on this connection.
+///
+/// Each backend exposes this differently, so the query is chosen per variant.
+pub fn last_insert_id(conn: &mut DbConnection) -> QueryResult<i32> {
+    let row: LastId = match conn {
+        DbConnection::Sqlite(c) => {
+            diesel::sql_query("SELECT last_insert_rowid() AS id").get_result(c)?
+        }
+        #[cfg(feature = "postgres")]
+        DbConnection::Postgres(c) => diesel::sql_query("SELECT lastval() AS id").get_result(c)?,
+        #[cfg(feature = "mysql")]
+        DbConnection::Mysql(c) => {
+            diesel::sql_query("SELECT CAST(LAST_INSERT_ID() AS SIGNED) AS id").get_result(c)?
+        }
+    };
+    Ok(row.id as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqlite_last_insert_id() {
+        let mut conn = establish(":memory:", "sqlite").unwrap();
+        run_pending_migrations(&mut conn).unwrap();
+        diesel::sql_query("INSERT INTO scanners (scanner_type) VALUES ('Nessus')")
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(last_insert_id(&mut conn).unwrap(), 1);
+    }
+}
@@ -16,12 +16,14 @@ pub enum Error {
     Regex(#[from] regex::Error),
     #[error(transparent)]
     Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
     #[error("invalid document: {0}")]
     InvalidDocument(String),
     #[error("migration error: {0}")]
     Migration(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("template error: {0}")]
-    Template(#[source] Box<dyn std::error::Error>),
+    Template(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("configuration error: {0}")]
     Config(String),
 }
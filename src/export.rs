@@ -0,0 +1,116 @@
+//! Machine-readable export of a parsed report.
+//!
+//! Records keep the in-memory IDs used throughout risu-rs: `hosts[n].id` is
+//! `n`, `items[].host_id` indexes `hosts`, `references[].item_id` and
+//! `service_descriptions[].item_id` index `items`, and `items[].plugin_id`
+//! matches `plugins[].plugin_id`.
+
+mod sarif;
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::models::{
+    Attachment, CvssVersion, FamilySelection, Host, HostProperty, Item, Patch, Plugin,
+    PluginPreference, Policy, PolicyPlugin, Reference, Report, Scanner, ServerPreference,
+    ServiceDescription,
+};
+use crate::parser::{Filters, NessusReport};
+
+pub use sarif::write_sarif;
+
+/// Version of the JSON document layout. Bumped on incompatible changes.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    schema_version: u32,
+    generator: String,
+    /// Version string of the source document, e.g. the Nessus format.
+    source_version: &'a str,
+    report: &'a Report,
+    scanner: &'a Scanner,
+    filters: &'a Filters,
+    cvss_version: CvssVersion,
+    hosts: &'a [Host],
+    host_properties: &'a [HostProperty],
+    items: &'a [Item],
+    plugins: &'a [Plugin],
+    references: &'a [Reference],
+    service_descriptions: &'a [ServiceDescription],
+    patches: &'a [Patch],
+    attachments: &'a [Attachment],
+    policies: &'a [Policy],
+    policy_plugins: &'a [PolicyPlugin],
+    family_selections: &'a [FamilySelection],
+    plugin_preferences: &'a [PluginPreference],
+    server_preferences: &'a [ServerPreference],
+}
+
+/// Serialize the whole report as pretty-printed JSON.
+pub fn write_json(report: &NessusReport, writer: &mut dyn Write) -> Result<(), Error> {
+    let export = JsonExport {
+        schema_version: JSON_SCHEMA_VERSION,
+        generator: format!("risu-rs {}", env!("CARGO_PKG_VERSION")),
+        source_version: &report.version,
+        report: &report.report,
+        scanner: &report.scanner,
+        filters: &report.filters,
+        cvss_version: report.cvss_version,
+        hosts: &report.hosts,
+        host_properties: &report.host_properties,
+        items: &report.items,
+        plugins: &report.plugins,
+        references: &report.references,
+        service_descriptions: &report.service_descriptions,
+        patches: &report.patches,
+        attachments: &report.attachments,
+        policies: &report.policies,
+        policy_plugins: &report.policy_plugins,
+        family_selections: &report.family_selections,
+        plugin_preferences: &report.plugin_preferences,
+        server_preferences: &report.server_preferences,
+    };
+    serde_json::to_writer_pretty(writer, &export)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_relationships() {
+        let report = NessusReport {
+            hosts: vec![Host {
+                name: Some("web".into()),
+                ip: Some("10.0.0.1".into()),
+                ..Host::default()
+            }],
+            items: vec![Item {
+                host_id: Some(0),
+                plugin_id: Some(42),
+                ..Item::default()
+            }],
+            references: vec![Reference {
+                item_id: Some(0),
+                plugin_id: Some(42),
+                source: Some("cve".into()),
+                value: Some("CVE-2024-0001".into()),
+                ..Reference::default()
+            }],
+            ..NessusReport::default()
+        };
+        let mut out = Vec::new();
+        write_json(&report, &mut out).unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(v["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(v["cvss_version"], "v2");
+        assert_eq!(v["hosts"][0]["ip"], "10.0.0.1");
+        assert_eq!(v["items"][0]["host_id"], 0);
+        assert_eq!(v["references"][0]["item_id"], 0);
+        assert_eq!(v["references"][0]["value"], "CVE-2024-0001");
+    }
+}
@@ -0,0 +1,416 @@
+//! SARIF 2.1.0 output for code-scanning and security dashboards.
+//!
+//! Each plugin becomes a `reportingDescriptor` rule and each item a `result`
+//! whose logical location is the host and port. CVE and CWE references are
+//! emitted as taxonomies that rules and results point into. Findings hidden
+//! by an accepted-risk suppression carry a SARIF `suppressions` entry.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::suppression;
+use crate::error::Error;
+use crate::models::{CvssScores, Host, Item, Plugin};
+use crate::parser::NessusReport;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Taxonomies emitted, as (reference source, organization, description).
+const TAXONOMIES: &[(&str, &str, &str)] = &[
+    ("CWE", "MITRE", "The MITRE Common Weakness Enumeration"),
+    ("CVE", "MITRE", "Common Vulnerabilities and Exposures"),
+];
+
+/// Serialize the report as a SARIF 2.1.0 log with a single run.
+pub fn write_sarif(report: &NessusReport, writer: &mut dyn Write) -> Result<(), Error> {
+    serde_json::to_writer_pretty(writer, &sarif_log(report))?;
+    Ok(())
+}
+
+fn sarif_log(report: &NessusReport) -> Value {
+    let plugins: HashMap<i32, &Plugin> = report
+        .plugins
+        .iter()
+        .filter_map(|p| p.plugin_id.map(|id| (id, p)))
+        .collect();
+
+    // Taxa referenced by each item, keyed by taxonomy name.
+    let mut item_taxa: HashMap<i32, Vec<(&'static str, String)>> = HashMap::new();
+    for r in &report.references {
+        let (Some(item_id), Some(source), Some(value)) =
+            (r.item_id, r.source.as_deref(), r.value.as_deref())
+        else {
+            continue;
+        };
+        if let Some(taxon) = taxon(source, value) {
+            let taxa = item_taxa.entry(item_id).or_default();
+            if !taxa.contains(&taxon) {
+                taxa.push(taxon);
+            }
+        }
+    }
+
+    // Host ids survive `sort_hosts`; positions in `report.hosts` do not.
+    let hosts: HashMap<i32, &Host> = report.hosts.iter().map(|h| (h.id, h)).collect();
+
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_index: HashMap<i32, usize> = HashMap::new();
+    let mut rule_taxa: Vec<BTreeSet<(&'static str, String)>> = Vec::new();
+    let mut results = Vec::new();
+    for (idx, item) in report.items.iter().enumerate() {
+        let plugin_id = item.plugin_id.unwrap_or(0);
+        let plugin = plugins.get(&plugin_id).copied();
+        let rule = *rule_index.entry(plugin_id).or_insert_with(|| {
+            rules.push(rule(report, plugin_id, plugin, item));
+            rule_taxa.push(BTreeSet::new());
+            rules.len() - 1
+        });
+        let taxa = item_taxa.get(&(idx as i32)).cloned().unwrap_or_default();
+        rule_taxa[rule].extend(taxa.iter().cloned());
+        let host = item.host_id.and_then(|h| hosts.get(&h).copied());
+        results.push(result(report, item, plugin, host, rule, &taxa));
+    }
+    for (rule, taxa) in rules.iter_mut().zip(&rule_taxa) {
+        if !taxa.is_empty() {
+            let relationships: Vec<Value> = taxa
+                .iter()
+                .map(|(name, id)| {
+                    json!({
+                        "target": { "id": id, "toolComponent": { "name": name } },
+                        "kinds": ["relevant"],
+                    })
+                })
+                .collect();
+            rule["relationships"] = Value::Array(relationships);
+        }
+    }
+
+    let taxonomies: Vec<Value> = TAXONOMIES
+        .iter()
+        .filter_map(|(name, organization, description)| {
+            let ids: BTreeSet<&String> = rule_taxa
+                .iter()
+                .flatten()
+                .filter(|(n, _)| n == name)
+                .map(|(_, id)| id)
+                .collect();
+            if ids.is_empty() {
+                return None;
+            }
+            let taxa: Vec<Value> = ids.into_iter().map(|id| json!({ "id": id })).collect();
+            Some(json!({
+                "name": name,
+                "organization": organization,
+                "shortDescription": { "text": description },
+                "taxa": taxa,
+            }))
+        })
+        .collect();
+
+    let mut driver = Map::new();
+    let scanner = report.scanner.scanner_type.as_str();
+    driver.insert(
+        "name".into(),
+        json!(if scanner.is_empty() { "risu-rs" } else { scanner }),
+    );
+    if let Some(version) = &report.scanner.scanner_version {
+        driver.insert("version".into(), json!(version));
+    }
+    driver.insert("informationUri".into(), json!("https://github.com/hammackj/risu-rs"));
+    driver.insert("rules".into(), Value::Array(rules));
+    if !taxonomies.is_empty() {
+        let supported: Vec<Value> = taxonomies
+            .iter()
+            .map(|t| json!({ "name": t["name"] }))
+            .collect();
+        driver.insert("supportedTaxonomies".into(), Value::Array(supported));
+    }
+
+    let mut run = Map::new();
+    run.insert("tool".into(), json!({ "driver": driver }));
+    if !taxonomies.is_empty() {
+        run.insert("taxonomies".into(), Value::Array(taxonomies));
+    }
+    run.insert("results".into(), Value::Array(results));
+    let mut properties = Map::new();
+    properties.insert(
+        "generator".into(),
+        json!(format!("risu-rs {}", env!("CARGO_PKG_VERSION"))),
+    );
+    if let Some(title) = &report.report.title {
+        properties.insert("reportTitle".into(), json!(title));
+    }
+    run.insert("properties".into(), Value::Object(properties));
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    })
+}
+
+fn rule(report: &NessusReport, plugin_id: i32, plugin: Option<&Plugin>, item: &Item) -> Value {
+    let name = plugin
+        .and_then(|p| p.plugin_name.clone())
+        .or_else(|| item.plugin_name.clone())
+        .unwrap_or_else(|| format!("Plugin {plugin_id}"));
+    let description = plugin
+        .and_then(|p| p.description.clone())
+        .or_else(|| item.description.clone());
+    let synopsis = plugin
+        .and_then(|p| p.synopsis.clone())
+        .or_else(|| item.synopsis.clone());
+    let solution = plugin
+        .and_then(|p| p.solution.clone())
+        .or_else(|| item.solution.clone());
+
+    let mut rule = Map::new();
+    rule.insert("id".into(), json!(plugin_id.to_string()));
+    rule.insert("name".into(), json!(name));
+    rule.insert(
+        "shortDescription".into(),
+        json!({ "text": synopsis.clone().unwrap_or_else(|| name.clone()) }),
+    );
+    if let Some(text) = &description {
+        rule.insert("fullDescription".into(), json!({ "text": text }));
+    }
+    let help: Vec<String> = [solution.map(|s| format!("Solution: {s}")), description]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !help.is_empty() {
+        rule.insert("help".into(), json!({ "text": help.join("\n\n") }));
+    }
+
+    let mut properties = Map::new();
+    let mut tags = vec![json!("security")];
+    if let Some(family) = plugin.and_then(|p| p.family_name.as_deref()) {
+        tags.push(json!(family));
+    }
+    properties.insert("tags".into(), Value::Array(tags));
+    let cvss = match plugin {
+        Some(p) => p.preferred_cvss(report.cvss_version),
+        None => item.preferred_cvss(report.cvss_version),
+    };
+    if let Some((version, score)) = cvss {
+        // Dashboards read `security-severity` as a string holding a 0.0-10.0 score.
+        properties.insert("security-severity".into(), json!(format!("{score:.1}")));
+        properties.insert("cvssVersion".into(), json!(version.to_string()));
+    }
+    rule.insert("properties".into(), Value::Object(properties));
+    Value::Object(rule)
+}
+
+fn result(
+    report: &NessusReport,
+    item: &Item,
+    plugin: Option<&Plugin>,
+    host: Option<&Host>,
+    rule: usize,
+    taxa: &[(&'static str, String)],
+) -> Value {
+    let plugin_id = item.plugin_id.unwrap_or(0);
+    let host_name = host.map(host_display).unwrap_or_else(|| "unknown".to_string());
+    let location = match (item.port, item.protocol.as_deref()) {
+        (Some(port), Some(proto)) if port > 0 => format!("{host_name}:{port}/{proto}"),
+        (Some(port), None) if port > 0 => format!("{host_name}:{port}"),
+        _ => host_name.clone(),
+    };
+    let name = item
+        .plugin_name
+        .clone()
+        .or_else(|| plugin.and_then(|p| p.plugin_name.clone()))
+        .unwrap_or_else(|| format!("Plugin {plugin_id}"));
+
+    let mut result = Map::new();
+    result.insert("ruleId".into(), json!(plugin_id.to_string()));
+    result.insert("ruleIndex".into(), json!(rule));
+    // Suppressed findings keep their scanner severity in `real_severity`.
+    let suppressed = item.suppressed == Some(true);
+    let severity = if suppressed { item.real_severity } else { item.severity }.unwrap_or(0);
+    result.insert("level".into(), json!(level(severity)));
+    result.insert("message".into(), json!({ "text": format!("{name} on {location}") }));
+    result.insert(
+        "locations".into(),
+        json!([{
+            "logicalLocations": [{
+                "name": location,
+                "fullyQualifiedName": location,
+                "kind": "host",
+            }]
+        }]),
+    );
+    // A stable fingerprint lets dashboards track a finding across uploads.
+    let key = format!(
+        "{host_name}|{plugin_id}|{}|{}",
+        item.port.unwrap_or(0),
+        item.protocol.as_deref().unwrap_or("")
+    );
+    let digest = Sha256::digest(key.as_bytes());
+    let fingerprint: String = digest.iter().take(16).map(|b| format!("{b:02x}")).collect();
+    result.insert("partialFingerprints".into(), json!({ "risuFinding/v1": fingerprint }));
+    if !taxa.is_empty() {
+        let taxa: Vec<Value> = taxa
+            .iter()
+            .map(|(name, id)| json!({ "id": id, "toolComponent": { "name": name } }))
+            .collect();
+        result.insert("taxa".into(), Value::Array(taxa));
+    }
+
+    if suppressed {
+        let mut entry = Map::new();
+        entry.insert("kind".into(), json!("external"));
+        entry.insert("status".into(), json!("accepted"));
+        if let Some(s) = suppression::find_for(report, item) {
+            let text = format!(
+                "{} (approved by {}, expires {})",
+                s.justification, s.approver, s.expires
+            );
+            entry.insert("justification".into(), json!(text));
+            entry.insert("properties".into(), json!({ "id": s.id }));
+        }
+        result.insert("suppressions".into(), json!([entry]));
+    }
+
+    let mut properties = Map::new();
+    properties.insert("severity".into(), json!(severity));
+    let risk = item
+        .risk_factor
+        .as_deref()
+        .or(plugin.and_then(|p| p.risk_factor.as_deref()));
+    if let Some(risk) = risk {
+        properties.insert("riskFactor".into(), json!(risk));
+    }
+    if let Some(output) = &item.plugin_output {
+        properties.insert("pluginOutput".into(), json!(output));
+    }
+    if let Some(cvss) = item.preferred_cvss(report.cvss_version) {
+        properties.insert("security-severity".into(), json!(format!("{:.1}", cvss.1)));
+    }
+    result.insert("properties".into(), Value::Object(properties));
+    Value::Object(result)
+}
+
+/// Nessus severity 0-4 onto SARIF levels.
+fn level(severity: i32) -> &'static str {
+    match severity {
+        3.. => "error",
+        2 => "warning",
+        1 => "note",
+        _ => "none",
+    }
+}
+
+/// Normalize a CVE or CWE reference into a (taxonomy, id) pair.
+fn taxon(source: &str, value: &str) -> Option<(&'static str, String)> {
+    let value = value.trim();
+    match source.trim().to_ascii_uppercase().as_str() {
+        "CVE" => Some(("CVE", value.to_ascii_uppercase())),
+        "CWE" => {
+            let id = value
+                .trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == '-' || c == ':');
+            (!id.is_empty()).then(|| ("CWE", id.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn host_display(host: &Host) -> String {
+    host.ip
+        .clone()
+        .or_else(|| host.fqdn.clone())
+        .or_else(|| host.name.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Reference;
+
+    #[test]
+    fn maps_plugins_items_and_taxonomies() {
+        let report = NessusReport {
+            hosts: vec![Host { ip: Some("10.0.0.5".into()), ..Host::default() }],
+            plugins: vec![Plugin {
+                plugin_id: Some(42),
+                plugin_name: Some("Weak TLS".into()),
+                family_name: Some("General".into()),
+                description: Some("TLS 1.0 is enabled.".into()),
+                solution: Some("Disable TLS 1.0.".into()),
+                cvss_base_score: Some(5.0),
+                cvss3_base_score: Some(7.5),
+                ..Plugin::default()
+            }],
+            items: vec![Item {
+                host_id: Some(0),
+                plugin_id: Some(42),
+                port: Some(443),
+                protocol: Some("tcp".into()),
+                severity: Some(3),
+                ..Item::default()
+            }],
+            references: vec![
+                Reference {
+                    item_id: Some(0),
+                    source: Some("cwe".into()),
+                    value: Some("CWE-326".into()),
+                    ..Reference::default()
+                },
+                Reference {
+                    item_id: Some(0),
+                    source: Some("CVE".into()),
+                    value: Some("cve-2011-3389".into()),
+                    ..Reference::default()
+                },
+            ],
+            cvss_version: crate::models::CvssVersion::V3,
+            ..NessusReport::default()
+        };
+        let log = sarif_log(&report);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        let rule = &run["tool"]["driver"]["rules"][0];
+        assert_eq!(rule["id"], "42");
+        assert_eq!(rule["properties"]["security-severity"], "7.5");
+        assert!(rule["help"]["text"].as_str().unwrap().starts_with("Solution: Disable"));
+        assert_eq!(rule["relationships"][0]["target"]["toolComponent"]["name"], "CVE");
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(
+            result["locations"][0]["logicalLocations"][0]["name"],
+            "10.0.0.5:443/tcp"
+        );
+        assert_eq!(result["taxa"][0]["id"], "326");
+        assert_eq!(result["taxa"][1]["id"], "CVE-2011-3389");
+        assert_eq!(run["taxonomies"][0]["name"], "CWE");
+        assert_eq!(run["taxonomies"][1]["taxa"][0]["id"], "CVE-2011-3389");
+    }
+
+    #[test]
+    fn locates_results_on_hosts_by_id() {
+        let report = NessusReport {
+            hosts: vec![
+                Host { id: 1, ip: Some("10.0.0.9".into()), ..Host::default() },
+                Host { id: 0, ip: Some("10.0.0.1".into()), ..Host::default() },
+            ],
+            items: vec![Item {
+                host_id: Some(0),
+                plugin_id: Some(42),
+                port: Some(22),
+                protocol: Some("tcp".into()),
+                ..Item::default()
+            }],
+            ..NessusReport::default()
+        };
+        let log = sarif_log(&report);
+        assert_eq!(
+            log["runs"][0]["results"][0]["locations"][0]["logicalLocations"][0]["name"],
+            "10.0.0.1:22/tcp"
+        );
+    }
+}
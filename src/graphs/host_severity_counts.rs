@@ -3,7 +3,7 @@ use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 use plotters::prelude::*;
 
 use crate::schema::nessus_items::dsl::{
@@ -26,10 +26,10 @@ impl HostSeverityCountsGraph {
     }
 
     pub fn generate(
-        conn: &mut SqliteConnection,
+        conn: &mut DbConnection,
         dir: &Path,
         scanner: Option<i32>,
-    ) -> Result<PathBuf, Box<dyn Error>> {
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
         let mut query = nessus_items
             .select((host_id, severity))
             .filter(
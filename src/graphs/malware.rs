@@ -9,7 +9,7 @@ use crate::template::malware_helper::has_malware_finding;
 
 /// Generate a pie chart showing infected versus non-infected hosts.
 /// Returns the path to the generated PNG file.
-pub fn malware(report: &NessusReport, dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+pub fn malware(report: &NessusReport, dir: &Path) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let total_hosts = report.hosts.len();
     if total_hosts == 0 {
         return Err("no host data".into());
@@ -112,6 +112,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: Default::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         }
     }
@@ -28,7 +28,7 @@ pub fn top_vulnerabilities(
     report: &NessusReport,
     dir: &Path,
     n: usize,
-) -> Result<PathBuf, Box<dyn Error>> {
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let mut counts: HashMap<String, i32> = HashMap::new();
     for item in &report.items {
         if let Some(name) = &item.plugin_name {
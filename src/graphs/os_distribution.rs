@@ -3,7 +3,7 @@ use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 use plotters::prelude::*;
 
 use crate::parser::NessusReport;
@@ -26,7 +26,10 @@ pub fn count_os(report: &NessusReport) -> HashMap<String, usize> {
 }
 
 /// Generate an OS distribution graph from a [`NessusReport`].
-pub fn os_distribution(report: &NessusReport, dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+pub fn os_distribution(
+    report: &NessusReport,
+    dir: &Path,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let counts = count_os(report);
 
     if counts.is_empty() {
@@ -79,10 +82,10 @@ impl OsDistributionGraph {
     }
 
     pub fn generate(
-        conn: &mut SqliteConnection,
+        conn: &mut DbConnection,
         dir: &Path,
         scanner: Option<i32>,
-    ) -> Result<PathBuf, Box<dyn Error>> {
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
         let mut query = nessus_hosts.select(host_os).into_boxed();
         if let Some(sid) = scanner {
             query = query.filter(host_scanner_id.eq(sid));
@@ -3,7 +3,7 @@ use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 use plotters::prelude::*;
 
 use crate::schema::nessus_items::dsl::{nessus_items, plugin_name, rollup_finding};
@@ -15,11 +15,11 @@ pub struct TopVulnGraph;
 impl TopVulnGraph {
     /// Query the database for the top `limit` vulnerability names and render a bar chart.
     pub fn generate(
-        conn: &mut SqliteConnection,
+        conn: &mut DbConnection,
         dir: &Path,
         limit: usize,
         scanner: Option<i32>,
-    ) -> Result<PathBuf, Box<dyn Error>> {
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
         let mut query = nessus_items
             .select(plugin_name)
             .filter(
@@ -3,7 +3,7 @@ use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 use plotters::prelude::*;
 
 use crate::schema::nessus_items::dsl::{
@@ -19,11 +19,11 @@ pub struct VulnCategoryGraph;
 
 impl VulnCategoryGraph {
     pub fn generate(
-        conn: &mut SqliteConnection,
+        conn: &mut DbConnection,
         dir: &Path,
         limit: usize,
         scanner: Option<i32>,
-    ) -> Result<PathBuf, Box<dyn Error>> {
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
         let mut query = nessus_items
             .inner_join(nessus_plugins.on(item_pid.eq(plugin_pid)))
             .select(family_name)
@@ -3,7 +3,7 @@ use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 use plotters::prelude::*;
 
 use crate::schema::nessus_hosts::dsl::{nessus_hosts, os};
@@ -33,7 +33,10 @@ impl WindowsOsGraph {
     }
 
     /// Query the database for host operating system information and render a pie chart.
-    pub fn generate(conn: &mut SqliteConnection, dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    pub fn generate(
+        conn: &mut DbConnection,
+        dir: &Path,
+    ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
         let results: Vec<Option<String>> = nessus_hosts
             .select(os)
             .filter(os.like("Windows%"))
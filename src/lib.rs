@@ -1,10 +1,14 @@
 pub mod banner;
 pub mod config;
+pub mod db;
 pub mod error;
+pub mod export;
 pub mod graphs;
+pub mod loader;
 pub mod migrate;
 pub mod models;
 pub mod parser;
+pub mod persist;
 pub mod postprocess;
 pub mod renderers;
 pub use renderers as renderer;
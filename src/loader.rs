@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use diesel::prelude::*;
 
+use crate::db::DbConnection;
 use crate::error::Error;
 use crate::models::{
     Attachment, FamilySelection, Host, HostProperty, Item, Patch, Plugin, PluginPreference,
@@ -7,22 +10,34 @@ use crate::models::{
 };
 use crate::schema;
 
-/// Load a NessusReport from the SQLite database and normalize IDs so in-memory
+/// Most IDs bound in one `IN (...)` list. SQLite limits the number of
+/// variables in a statement (999 in older builds).
+const ID_CHUNK: usize = 900;
+
+/// Run `load` over `ids` in chunks of [`ID_CHUNK`] and merge the rows in
+/// `key` order, as a single `ORDER BY` would.
+fn load_chunked<T>(
+    ids: &[i32],
+    mut load: impl FnMut(&[i32]) -> QueryResult<Vec<T>>,
+    key: fn(&T) -> i32,
+) -> QueryResult<Vec<T>> {
+    let mut rows = Vec::new();
+    for chunk in ids.chunks(ID_CHUNK) {
+        rows.extend(load(chunk)?);
+    }
+    rows.sort_by_key(key);
+    Ok(rows)
+}
+
+/// Load a NessusReport from the database and normalize IDs so in-memory
 /// relationships match parser-produced reports (host/item indices rather than DB IDs).
 pub fn load_report(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     report_id_opt: Option<i32>,
 ) -> Result<crate::parser::NessusReport, Error> {
-    use schema::nessus_reports::dsl as rep;
     use schema::nessus_hosts::dsl as hosts;
-    use schema::nessus_items::dsl as items;
-    use schema::nessus_plugins::dsl as plugs;
-    use schema::nessus_attachments::dsl as atts;
-    use schema::nessus_host_properties::dsl as hprops;
-    use schema::nessus_service_descriptions::dsl as sdesc;
-    use schema::nessus_references::dsl as refs;
-    use schema::nessus_patches::dsl as patches;
-    use schema::scanners::dsl as scn;
+    use schema::nessus_policies::dsl as pols;
+    use schema::nessus_reports::dsl as rep;
 
     // Pick report id: use provided or latest
     let report_id = match report_id_opt {
@@ -38,18 +53,76 @@ pub fn load_report(
         .filter(rep::id.eq(report_id))
         .first::<Report>(conn)?;
 
-    // Load hosts for this report
-    let mut hosts_db: Vec<Host> = hosts::nessus_hosts
+    // Load hosts and policies for this report
+    let hosts_db: Vec<Host> = hosts::nessus_hosts
         .filter(hosts::nessus_report_id.eq(report_id))
         .order(hosts::id.asc())
         .load::<Host>(conn)?;
+    let policies: Vec<Policy> = pols::nessus_policies
+        .filter(pols::nessus_report_id.eq(report_id))
+        .order(pols::id.asc())
+        .load::<Policy>(conn)?;
+
+    assemble(conn, db_report, hosts_db, policies)
+}
+
+/// Load every host and policy in the database as a single report.
+///
+/// Nessus SQLite exports hold one scan and do not always link hosts to a
+/// `nessus_reports` row, so nothing is filtered by report. The first report
+/// row, if any, supplies the title and other report fields.
+pub fn load_all(conn: &mut DbConnection) -> Result<crate::parser::NessusReport, Error> {
+    use schema::nessus_hosts::dsl as hosts;
+    use schema::nessus_policies::dsl as pols;
+    use schema::nessus_reports::dsl as rep;
+
+    let db_report = rep::nessus_reports
+        .order(rep::id.asc())
+        .first::<Report>(conn)
+        .optional()?
+        .unwrap_or_default();
+    let hosts_db = hosts::nessus_hosts
+        .order(hosts::id.asc())
+        .load::<Host>(conn)?;
+    let policies = pols::nessus_policies
+        .order(pols::id.asc())
+        .load::<Policy>(conn)?;
+
+    assemble(conn, db_report, hosts_db, policies)
+}
+
+/// Load the rows related to `hosts_db` and `policies` and remap every DB ID
+/// to the vector-index convention used by the parsers.
+fn assemble(
+    conn: &mut DbConnection,
+    db_report: Report,
+    mut hosts_db: Vec<Host>,
+    mut policies: Vec<Policy>,
+) -> Result<crate::parser::NessusReport, Error> {
+    use schema::nessus_attachments::dsl as atts;
+    use schema::nessus_family_selections::dsl as famsel;
+    use schema::nessus_host_properties::dsl as hprops;
+    use schema::nessus_items::dsl as items;
+    use schema::nessus_patches::dsl as patches;
+    use schema::nessus_plugin_preferences::dsl as plgprefs;
+    use schema::nessus_plugins::dsl as plugs;
+    use schema::nessus_policy_plugins::dsl as polplugs;
+    use schema::nessus_references::dsl as refs;
+    use schema::nessus_server_preferences::dsl as srvprefs;
+    use schema::nessus_service_descriptions::dsl as sdesc;
+    use schema::scanners::dsl as scn;
 
     // Map DB host_id -> index
-    let mut host_index_map = std::collections::HashMap::new();
+    let mut host_index_map = HashMap::new();
     for (idx, h) in hosts_db.iter_mut().enumerate() {
         host_index_map.insert(h.id, idx as i32);
         h.id = idx as i32; // normalize id to vector index
     }
+    let remap_host = |hid: &mut Option<i32>| {
+        if let Some(new_idx) = hid.and_then(|db_hid| host_index_map.get(&db_hid)) {
+            *hid = Some(*new_idx);
+        }
+    };
 
     // Load plugins for scanner(s) referenced by these hosts/items
     // We may not yet know scanner id; try to get from first host or later items
@@ -69,132 +142,193 @@ pub fn load_report(
     };
 
     // Build map: plugin table id (DB) -> external plugin_id
-    let mut plugin_db_to_external: std::collections::HashMap<i32, i32> =
-        std::collections::HashMap::new();
-    for p in &plugins_db {
-        if let (Some(db_id), Some(ext)) = (Some(p.id), p.plugin_id) {
-            plugin_db_to_external.insert(db_id, ext);
-        }
-    }
+    let plugin_db_to_external: HashMap<i32, i32> = plugins_db
+        .iter()
+        .filter_map(|p| p.plugin_id.map(|ext| (p.id, ext)))
+        .collect();
 
-    // Load attachments
-    let mut attachments: Vec<Attachment> = atts::nessus_attachments
-        .order(atts::id.asc())
-        .load::<Attachment>(conn)?;
-    let mut attachment_id_map = std::collections::HashMap::new();
+    // Load items related to hosts of this report
+    let host_db_ids: Vec<i32> = host_index_map.keys().copied().collect();
+    let mut items_db: Vec<Item> = load_chunked(
+        &host_db_ids,
+        |ids| {
+            items::nessus_items
+                .filter(items::host_id.eq_any(ids))
+                .order(items::id.asc())
+                .load::<Item>(conn)
+        },
+        |r| r.id,
+    )?;
+
+    // Load the attachments those items point at
+    let mut attachment_db_ids: Vec<i32> =
+        items_db.iter().filter_map(|i| i.attachment_id).collect();
+    attachment_db_ids.sort_unstable();
+    attachment_db_ids.dedup();
+    let mut attachments: Vec<Attachment> = load_chunked(
+        &attachment_db_ids,
+        |ids| {
+            atts::nessus_attachments
+                .filter(atts::id.eq_any(ids))
+                .order(atts::id.asc())
+                .load::<Attachment>(conn)
+        },
+        |r| r.id,
+    )?;
+    let mut attachment_id_map = HashMap::new();
     for (idx, a) in attachments.iter_mut().enumerate() {
         attachment_id_map.insert(a.id, idx as i32);
         a.id = idx as i32; // normalize to index
     }
 
-    // Load items related to hosts of this report
-    let host_db_ids: Vec<i32> = host_index_map.keys().copied().collect();
-    let mut items_db: Vec<Item> = if host_db_ids.is_empty() {
-        Vec::new()
-    } else {
-        items::nessus_items
-            .filter(items::host_id.eq_any(&host_db_ids))
-            .order(items::id.asc())
-            .load::<Item>(conn)?
-    };
     // Build map from DB item id -> index in vector
-    let mut item_index_map = std::collections::HashMap::new();
+    let mut item_index_map = HashMap::new();
     for (idx, it) in items_db.iter_mut().enumerate() {
         // Remap host_id (DB) -> index
-        if let Some(db_hid) = it.host_id {
-            if let Some(new_idx) = host_index_map.get(&db_hid) {
-                it.host_id = Some(*new_idx);
-            }
-        }
+        remap_host(&mut it.host_id);
         // Remap plugin_id (DB fk) -> external plugin id
-        if let Some(db_pid) = it.plugin_id {
-            if let Some(ext) = plugin_db_to_external.get(&db_pid) {
-                it.plugin_id = Some(*ext);
-            }
+        if let Some(ext) = it.plugin_id.and_then(|db_pid| plugin_db_to_external.get(&db_pid)) {
+            it.plugin_id = Some(*ext);
         }
         // Remap attachment id (DB) -> index
-        if let Some(db_aid) = it.attachment_id {
-            if let Some(new_aid) = attachment_id_map.get(&db_aid) {
-                it.attachment_id = Some(*new_aid);
-            }
+        if let Some(new_aid) = it.attachment_id.and_then(|aid| attachment_id_map.get(&aid)) {
+            it.attachment_id = Some(*new_aid);
         }
         item_index_map.insert(it.id, idx as i32);
         it.id = idx as i32; // normalize id to index
     }
+    let item_db_ids: Vec<i32> = item_index_map.keys().copied().collect();
 
     // Load host properties and remap host_id
-    let mut host_properties: Vec<HostProperty> = if host_db_ids.is_empty() {
-        Vec::new()
-    } else {
-        hprops::nessus_host_properties
-            .filter(hprops::host_id.eq_any(&host_db_ids))
-            .order(hprops::id.asc())
-            .load::<HostProperty>(conn)?
-    };
+    let mut host_properties: Vec<HostProperty> = load_chunked(
+        &host_db_ids,
+        |ids| {
+            hprops::nessus_host_properties
+                .filter(hprops::host_id.eq_any(ids))
+                .order(hprops::id.asc())
+                .load::<HostProperty>(conn)
+        },
+        |r| r.id,
+    )?;
     for hp in &mut host_properties {
-        if let Some(db_hid) = hp.host_id {
-            if let Some(new_idx) = host_index_map.get(&db_hid) {
-                hp.host_id = Some(*new_idx);
-            }
-        }
+        remap_host(&mut hp.host_id);
     }
 
     // Load service descriptions and remap host_id/item_id
-    let mut service_descriptions: Vec<ServiceDescription> = if host_db_ids.is_empty() {
-        Vec::new()
-    } else {
-        sdesc::nessus_service_descriptions
-            .filter(sdesc::host_id.eq_any(&host_db_ids))
-            .order(sdesc::id.asc())
-            .load::<ServiceDescription>(conn)?
-    };
+    let mut service_descriptions: Vec<ServiceDescription> = load_chunked(
+        &host_db_ids,
+        |ids| {
+            sdesc::nessus_service_descriptions
+                .filter(sdesc::host_id.eq_any(ids))
+                .order(sdesc::id.asc())
+                .load::<ServiceDescription>(conn)
+        },
+        |r| r.id,
+    )?;
     for sd in &mut service_descriptions {
-        if let Some(db_hid) = sd.host_id {
-            if let Some(new_idx) = host_index_map.get(&db_hid) {
-                sd.host_id = Some(*new_idx);
-            }
-        }
-        if let Some(db_iid) = sd.item_id {
-            if let Some(new_idx) = item_index_map.get(&db_iid) {
-                sd.item_id = Some(*new_idx);
-            }
+        remap_host(&mut sd.host_id);
+        if let Some(new_idx) = sd.item_id.and_then(|db_iid| item_index_map.get(&db_iid)) {
+            sd.item_id = Some(*new_idx);
         }
     }
 
-    // Load references and remap plugin_id (db->external) and item_id (db->index)
-    let mut references: Vec<Reference> = if host_db_ids.is_empty() {
-        refs::nessus_references.order(refs::id.asc()).load::<Reference>(conn)?
-    } else {
-        refs::nessus_references.order(refs::id.asc()).load::<Reference>(conn)?
-    };
+    // Load references of these items and remap plugin_id (db->external) and
+    // item_id (db->index)
+    let mut references: Vec<Reference> = load_chunked(
+        &item_db_ids,
+        |ids| {
+            refs::nessus_references
+                .filter(refs::item_id.eq_any(ids))
+                .order(refs::id.asc())
+                .load::<Reference>(conn)
+        },
+        |r| r.id,
+    )?;
     for r in &mut references {
-        if let Some(db_iid) = r.item_id {
-            if let Some(new_idx) = item_index_map.get(&db_iid) {
-                r.item_id = Some(*new_idx);
-            }
+        if let Some(new_idx) = r.item_id.and_then(|db_iid| item_index_map.get(&db_iid)) {
+            r.item_id = Some(*new_idx);
         }
-        if let Some(db_pid) = r.plugin_id {
-            if let Some(ext) = plugin_db_to_external.get(&db_pid) {
-                r.plugin_id = Some(*ext);
-            }
+        if let Some(ext) = r.plugin_id.and_then(|db_pid| plugin_db_to_external.get(&db_pid)) {
+            r.plugin_id = Some(*ext);
         }
     }
 
     // Load patches and remap host_id
-    let mut patches_vec: Vec<Patch> = if host_db_ids.is_empty() {
-        Vec::new()
-    } else {
-        patches::nessus_patches
-            .filter(patches::host_id.eq_any(&host_db_ids))
-            .order(patches::id.asc())
-            .load::<Patch>(conn)?
-    };
+    let mut patches_vec: Vec<Patch> = load_chunked(
+        &host_db_ids,
+        |ids| {
+            patches::nessus_patches
+                .filter(patches::host_id.eq_any(ids))
+                .order(patches::id.asc())
+                .load::<Patch>(conn)
+        },
+        |r| r.id,
+    )?;
     for p in &mut patches_vec {
-        if let Some(db_hid) = p.host_id {
-            if let Some(new_idx) = host_index_map.get(&db_hid) {
-                p.host_id = Some(*new_idx);
-            }
-        }
+        remap_host(&mut p.host_id);
+    }
+
+    // Policies and their settings; policy_id becomes the policy's index
+    let mut policy_index_map = HashMap::new();
+    for (idx, p) in policies.iter_mut().enumerate() {
+        policy_index_map.insert(p.id, idx as i32);
+        p.id = idx as i32;
+    }
+    let policy_db_ids: Vec<i32> = policy_index_map.keys().copied().collect();
+    let remap_policy = |pid: &mut Option<i32>| {
+        *pid = pid.and_then(|db_pid| policy_index_map.get(&db_pid).copied());
+    };
+    let mut policy_plugins: Vec<PolicyPlugin> = load_chunked(
+        &policy_db_ids,
+        |ids| {
+            polplugs::nessus_policy_plugins
+                .filter(polplugs::policy_id.eq_any(ids))
+                .order(polplugs::id.asc())
+                .load::<PolicyPlugin>(conn)
+        },
+        |r| r.id,
+    )?;
+    for pp in &mut policy_plugins {
+        remap_policy(&mut pp.policy_id);
+    }
+    let mut family_selections: Vec<FamilySelection> = load_chunked(
+        &policy_db_ids,
+        |ids| {
+            famsel::nessus_family_selections
+                .filter(famsel::policy_id.eq_any(ids))
+                .order(famsel::id.asc())
+                .load::<FamilySelection>(conn)
+        },
+        |r| r.id,
+    )?;
+    for fs in &mut family_selections {
+        remap_policy(&mut fs.policy_id);
+    }
+    let mut plugin_preferences: Vec<PluginPreference> = load_chunked(
+        &policy_db_ids,
+        |ids| {
+            plgprefs::nessus_plugin_preferences
+                .filter(plgprefs::policy_id.eq_any(ids))
+                .order(plgprefs::id.asc())
+                .load::<PluginPreference>(conn)
+        },
+        |r| r.id,
+    )?;
+    for pf in &mut plugin_preferences {
+        remap_policy(&mut pf.policy_id);
+    }
+    let mut server_preferences: Vec<ServerPreference> = load_chunked(
+        &policy_db_ids,
+        |ids| {
+            srvprefs::nessus_server_preferences
+                .filter(srvprefs::policy_id.eq_any(ids))
+                .order(srvprefs::id.asc())
+                .load::<ServerPreference>(conn)
+        },
+        |r| r.id,
+    )?;
+    for sp in &mut server_preferences {
+        remap_policy(&mut sp.policy_id);
     }
 
     // Choose scanner
@@ -222,13 +356,17 @@ pub fn load_report(
         host_properties,
         service_descriptions,
         references,
-        policies: Vec::new(),
-        policy_plugins: Vec::new(),
-        family_selections: Vec::new(),
-        plugin_preferences: Vec::new(),
-        server_preferences: Vec::new(),
+        policies,
+        policy_plugins,
+        family_selections,
+        plugin_preferences,
+        server_preferences,
         scanner,
         filters: crate::parser::Filters::default(),
+        cvss_version: Default::default(),
+        risk_scoring: Default::default(),
+        diff: None,
+        suppressions: Vec::new(),
     };
     // Propagate scanner id to entries
     let sc_type = report.scanner.scanner_type.clone();
@@ -236,3 +374,44 @@ pub fn load_report(
     report.set_scanner(&sc_type, sc_ver);
     Ok(report)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Reference;
+    use crate::parser::NessusReport;
+
+    #[test]
+    fn loads_more_items_than_sqlite_binds_in_one_statement() {
+        const ITEMS: usize = 33_000;
+        let mut report = NessusReport {
+            hosts: vec![Host { ip: Some("10.0.0.1".into()), ..Host::default() }],
+            plugins: vec![Plugin { plugin_id: Some(10), ..Plugin::default() }],
+            ..NessusReport::default()
+        };
+        for n in 0..ITEMS {
+            report.items.push(Item {
+                host_id: Some(0),
+                plugin_id: Some(10),
+                port: Some(n as i32),
+                ..Item::default()
+            });
+        }
+        report.references.push(Reference {
+            item_id: Some(ITEMS as i32 - 1),
+            plugin_id: Some(10),
+            source: Some("cve".into()),
+            value: Some("CVE-2024-0001".into()),
+            ..Reference::default()
+        });
+
+        let mut conn = crate::db::establish(":memory:", "sqlite").unwrap();
+        crate::db::run_pending_migrations(&mut conn).unwrap();
+        let id = crate::persist::to_database(&mut conn, &report).unwrap();
+        let loaded = load_report(&mut conn, Some(id)).unwrap();
+
+        assert_eq!(loaded.items.len(), ITEMS);
+        assert!(loaded.items.windows(2).all(|w| w[0].port < w[1].port));
+        assert_eq!(loaded.references.len(), 1);
+    }
+}
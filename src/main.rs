@@ -13,7 +13,9 @@
 mod banner;
 mod config;
 mod console;
+mod db;
 mod error;
+mod export;
 mod graphs;
 mod migrate;
 mod models;
@@ -44,11 +46,25 @@ enum DbAction {
     Console,
 }
 
+#[derive(Subcommand)]
+enum RollupsAction {
+    /// Check a rollups file for duplicate and unknown plugin IDs
+    Lint {
+        /// Rollups file; defaults to the one post-processing loads
+        file: Option<std::path::PathBuf>,
+        /// Plugin index to check plugin IDs against (see `plugin-index`)
+        #[arg(long, value_name = "db", default_value = plugin_index::INDEX_DB)]
+        index: std::path::PathBuf,
+        /// Rewrite the file with each rollup's plugin IDs sorted and
+        /// deduplicated, keeping the rollups in file order
+        #[arg(long)]
+        write: bool,
+    },
+}
+
 use chrono::{Duration, Utc};
 use clap::{Parser, Subcommand};
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
-use diesel_migrations::MigrationHarness;
 use ipnet::IpNet;
 use std::collections::{HashMap, HashSet};
 use tracing::error;
@@ -93,6 +109,9 @@ struct Cli {
     /// Parse a Nessus SQLite export and run post-processing plugins
     #[arg(long = "nessus-sqlite", value_name = "path")]
     nessus_sqlite: Option<std::path::PathBuf>,
+    /// CVSS version preferred for risk scores and templates (2, 3 or 4)
+    #[arg(long = "cvss-version", value_name = "version")]
+    cvss_version: Option<models::CvssVersion>,
     /// Path to configuration file
     #[arg(long = "config-file", value_name = "path")]
     config_file: Option<std::path::PathBuf>,
@@ -107,16 +126,17 @@ struct Cli {
 enum Commands {
     /// Parse an input file and post process it
     Parse {
-        /// File to parse
-        file: std::path::PathBuf,
+        /// Files or directories to parse; directories are searched recursively
+        #[arg(required = true)]
+        files: Vec<std::path::PathBuf>,
         /// Output file for generated document
         #[arg(short, long, default_value = "output.pdf")]
         output: std::path::PathBuf,
         /// Template to use for rendering
         #[arg(short, long, default_value = "simple")]
         template: String,
-        /// Renderer to use (pdf, csv, rtf, typst, nil)
-        #[arg(long, value_parser = ["pdf", "csv", "rtf", "typst", "nil"])]
+        /// Renderer to use; defaults to the one matching the output extension
+        #[arg(long, value_parser = renderer_names())]
         renderer: Option<String>,
         /// Template-specific arguments as `key=value` pairs
         #[arg(long = "template-arg", value_name = "key=value", value_parser = parse_key_val::<String, String>)]
@@ -127,6 +147,34 @@ enum Commands {
         /// Run post-processing plugins on the parsed data
         #[arg(long)]
         post_process: bool,
+        /// Skip input files that fail to parse instead of stopping
+        #[arg(long)]
+        continue_on_error: bool,
+    },
+    /// Parse an input file and store it in the configured database
+    Import {
+        /// File to import
+        file: std::path::PathBuf,
+        /// Run post-processing plugins before storing the data
+        #[arg(long)]
+        post_process: bool,
+        /// Stream a Nessus file into the database host by host instead of
+        /// parsing it into memory first; the report is stored without
+        /// post-processing, so it cannot be combined with --post-process
+        #[arg(long, conflicts_with = "post_process")]
+        stream: bool,
+        /// Hosts written per database transaction when streaming
+        #[arg(long, default_value_t = persist::DEFAULT_BATCH_HOSTS, requires = "stream")]
+        batch_hosts: usize,
+    },
+    /// Merge reports from the database into one report with one host per asset
+    Merge {
+        /// Report IDs to merge
+        #[arg(required = true)]
+        report_ids: Vec<i32>,
+        /// Title of the merged report (defaults to the first report's title)
+        #[arg(long)]
+        title: Option<String>,
     },
     /// Database management commands
     Database {
@@ -147,8 +195,8 @@ enum Commands {
         /// Output file for generated document
         #[arg(short, long, default_value = "output.pdf")]
         output: std::path::PathBuf,
-        /// Renderer to use (pdf, csv, rtf, nil)
-        #[arg(long, value_parser = ["pdf", "csv", "rtf", "typst", "nil"])]
+        /// Renderer to use; defaults to the one matching the output extension
+        #[arg(long, value_parser = renderer_names())]
         renderer: Option<String>,
         /// Template-specific arguments as `key=value` pairs
         #[arg(long = "template-arg", value_name = "key=value", value_parser = parse_key_val::<String, String>)]
@@ -168,12 +216,88 @@ enum Commands {
         /// Only include items matching this plugin ID (external)
         #[arg(long = "plugin-id", value_name = "id")]
         plugin_id: Option<i32>,
+        /// Only include hosts carrying this asset-context tag
+        #[arg(long, value_name = "tag")]
+        tag: Option<String>,
+    },
+    /// Compare two scans and render new, remediated and changed findings
+    Diff {
+        /// Baseline scan: an input file or a report ID in the database
+        baseline: String,
+        /// Current scan: an input file or a report ID in the database
+        current: String,
+        /// Output file for generated document
+        #[arg(short, long, default_value = "scan_diff.pdf")]
+        output: std::path::PathBuf,
+        /// Template to use for rendering
+        #[arg(short, long, default_value = "scan_diff")]
+        template: String,
+        /// Renderer to use; defaults to the one matching the output extension
+        #[arg(long, value_parser = renderer_names())]
+        renderer: Option<String>,
+        /// Template-specific arguments as `key=value` pairs
+        #[arg(long = "template-arg", value_name = "key=value", value_parser = parse_key_val::<String, String>)]
+        template_args: Vec<(String, String)>,
+    },
+    /// Explain the risk score of the network, a host or a finding
+    Risk {
+        /// Scan to score: an input file or a report ID in the database
+        scan: String,
+        /// Explain one host, matched by IP, name, FQDN or NetBIOS name
+        #[arg(long)]
+        host: Option<String>,
+        /// Explain the findings of this plugin ID (on `--host` when given)
+        #[arg(long = "plugin-id", value_name = "id")]
+        plugin_id: Option<i32>,
+        /// Number of highest scoring hosts or findings to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Export a scan as machine-readable data
+    Export {
+        /// Input file to export; exports a report from the database when omitted
+        file: Option<std::path::PathBuf>,
+        /// Report ID to export from the database (defaults to latest if omitted)
+        #[arg(long = "report-id", conflicts_with = "file")]
+        report_id: Option<i32>,
+        /// Output format (json or sarif)
+        #[arg(long, default_value = "json", value_parser = ["json", "sarif"])]
+        format: String,
+        /// Output file; writes to standard output when omitted
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+        /// Only include findings older than the specified number of days
+        #[arg(long = "older-than", value_name = "days")]
+        older_than: Option<i64>,
+        /// Only include hosts whose IP matches the CIDR
+        #[arg(long = "host-ip", value_name = "cidr")]
+        host_ip: Option<IpNet>,
+        /// Only include hosts with the specified MAC address
+        #[arg(long = "host-mac", value_name = "addr")]
+        host_mac: Option<String>,
+        /// Only include the host with the given internal ID (in-memory index)
+        #[arg(long = "host-id", value_name = "id")]
+        host_id: Option<i32>,
+        /// Only include items matching this plugin ID (external)
+        #[arg(long = "plugin-id", value_name = "id")]
+        plugin_id: Option<i32>,
+        /// Only include hosts carrying this asset-context tag
+        #[arg(long, value_name = "tag")]
+        tag: Option<String>,
+        /// Run post-processing plugins before exporting
+        #[arg(long)]
+        post_process: bool,
     },
     /// Index NASL plugins and store metadata
     PluginIndex {
         /// Directory containing NASL plugins
         dir: std::path::PathBuf,
     },
+    /// Rollup definition commands
+    Rollups {
+        #[command(subcommand)]
+        action: RollupsAction,
+    },
     /// Create a new template skeleton source file
     CreateTemplate {
         /// Template name
@@ -181,12 +305,17 @@ enum Commands {
         /// Template author
         #[arg(long)]
         author: Option<String>,
-        /// Renderer type (pdf, csv, rtf, nil)
-        #[arg(long, value_parser = ["pdf", "csv", "rtf", "typst", "nil"])]
+        /// Renderer type
+        #[arg(long, value_parser = renderer_names())]
         renderer: Option<String>,
     },
 }
 
+/// `--renderer` values, taken from the renderer registry.
+fn renderer_names() -> clap::builder::PossibleValuesParser {
+    clap::builder::PossibleValuesParser::new(renderer::RENDERERS.iter().map(|(name, _)| *name))
+}
+
 fn parse_key_val<T, U>(s: &str) -> Result<(T, U), String>
 where
     T: std::str::FromStr,
@@ -202,6 +331,317 @@ where
     Ok((key, value))
 }
 
+/// Parse every input file and concatenate the results, printing a line per
+/// file. With `continue_on_error`, files that fail are reported and skipped.
+fn parse_inputs(
+    paths: &[std::path::PathBuf],
+    continue_on_error: bool,
+) -> Result<parser::NessusReport, error::Error> {
+    let files = parser::collect_input_files(paths)?;
+    if files.is_empty() {
+        return Err(error::Error::InvalidDocument("no input files found".into()));
+    }
+    let mut reports = Vec::new();
+    let mut failed = 0;
+    for (file, parsed) in files.iter().zip(parser::parse_files(&files)) {
+        match parsed {
+            Ok(report) => {
+                println!(
+                    "{}: {} hosts, {} items",
+                    file.display(),
+                    report.hosts.len(),
+                    report.items.len()
+                );
+                reports.push(report);
+            }
+            Err(e) if continue_on_error => {
+                error!("{}: {e}", file.display());
+                println!("{}: skipped", file.display());
+                failed += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if reports.is_empty() {
+        return Err(error::Error::InvalidDocument("no input files could be parsed".into()));
+    }
+    if files.len() > 1 {
+        println!("Parsed {} of {} files", files.len() - failed, files.len());
+    }
+    Ok(parser::concat_reports(reports))
+}
+
+fn host_label(host: &models::Host) -> String {
+    host.ip
+        .clone()
+        .or_else(|| host.name.clone())
+        .or_else(|| host.fqdn.clone())
+        .unwrap_or_else(|| format!("host {}", host.id))
+}
+
+/// Print the problems of a rollups file, rewriting it normalized first when
+/// `write` is set. Fails when problems remain.
+fn lint_rollups(
+    file: Option<std::path::PathBuf>,
+    index: &std::path::Path,
+    write: bool,
+) -> Result<(), error::Error> {
+    use postprocess::rollups_toml;
+
+    let path = file
+        .or_else(rollups_toml::find_rollups_file)
+        .ok_or_else(|| error::Error::Config("no rollups file found".to_string()))?;
+    let known = plugin_index::known_plugin_ids(index);
+    match &known {
+        Some(ids) => {
+            println!("Checking plugin IDs against {} ({} plugins)", index.display(), ids.len())
+        }
+        None => println!("No plugin index at {}; skipping the plugin ID check", index.display()),
+    }
+    if write {
+        std::fs::write(&path, rollups_toml::normalize(&path)?)?;
+        println!("Normalized {}", path.display());
+    }
+    let problems = rollups_toml::lint(&path, known.as_ref())?;
+    for problem in &problems {
+        println!("{}: {problem}", path.display());
+    }
+    if problems.is_empty() {
+        println!("{}: no problems found", path.display());
+        Ok(())
+    } else {
+        Err(error::Error::Config(format!(
+            "{} problems found in {}",
+            problems.len(),
+            path.display()
+        )))
+    }
+}
+
+/// Print the factors behind the risk score of the network, of `host`, or of
+/// the findings of `plugin_id`.
+fn explain_risk(
+    report: &parser::NessusReport,
+    host: Option<&str>,
+    plugin_id: Option<i32>,
+    top: usize,
+) -> Result<(), error::Error> {
+    use analysis::scoring::{self, ItemInputs};
+    use std::io::Write;
+
+    let today = chrono::Local::now().naive_local().date();
+    let index = postprocess::ReportIndex::new(report);
+    let risk = scoring::explain_report(report, &index, today);
+    let model = &report.risk_scoring;
+    let out = &mut std::io::stdout().lock();
+    let shares = |e: &scoring::Explanation| {
+        let total = e.total();
+        e.factors().map(|(name, points)| {
+            let share = if total != 0.0 { points / total * 100.0 } else { 0.0 };
+            (name, points, format!("{share:.0}%"))
+        })
+    };
+
+    let host_idx = match host {
+        Some(wanted) => {
+            let matches = |v: &Option<String>| {
+                v.as_deref().is_some_and(|v| v.eq_ignore_ascii_case(wanted))
+            };
+            let idx = report
+                .hosts
+                .iter()
+                .position(|h| {
+                    matches(&h.ip) || matches(&h.name) || matches(&h.fqdn) || matches(&h.netbios)
+                })
+                .ok_or_else(|| {
+                    error::Error::InvalidDocument(format!("no host matches '{wanted}'"))
+                })?;
+            Some(idx)
+        }
+        None => None,
+    };
+    let on_host = |item: &models::Item| {
+        host_idx.is_none_or(|idx| {
+            item.host_id == Some(report.hosts[idx].id)
+                || (item.host_id.is_none() && report.hosts.len() <= 1)
+        })
+    };
+    let label = |item: &models::Item| {
+        let host = item
+            .host_id
+            .and_then(|id| report.hosts.iter().find(|h| h.id == id))
+            .map_or_else(|| "-".to_string(), host_label);
+        format!(
+            "{host}:{}/{}",
+            item.port.unwrap_or(0),
+            item.protocol.as_deref().unwrap_or("tcp")
+        )
+    };
+
+    if let Some(pid) = plugin_id {
+        let tiers = scoring::host_criticality(report);
+        let plugin = index
+            .plugin_index(pid)
+            .map(|idx| &report.plugins[idx])
+            .ok_or_else(|| error::Error::InvalidDocument(format!("no plugin {pid} in the scan")))?;
+        let mut found = 0;
+        for (item, e) in report.items.iter().zip(&risk.items) {
+            let Some(e) = e else { continue };
+            if item.plugin_id != Some(pid) || !on_host(item) {
+                continue;
+            }
+            found += 1;
+            let tier = item.host_id.and_then(|h| tiers.get(&h).copied());
+            let inputs = ItemInputs::new(plugin, tier, report.cvss_version, today);
+            writeln!(
+                out,
+                "Finding {pid} {} on {}: {:.2}",
+                plugin.plugin_name.as_deref().unwrap_or(""),
+                label(item),
+                e.total()
+            )?;
+            scoring::write_factors(out, model.describe(&inputs, e))?;
+        }
+        if found == 0 {
+            writeln!(out, "No findings of plugin {pid}")?;
+        }
+        return Ok(());
+    }
+
+    let mut ranked: Vec<(String, f32)> = match host_idx {
+        Some(idx) => {
+            let h = &report.hosts[idx];
+            let tier = scoring::host_criticality(report).get(&h.id).map(|t| t.to_string());
+            let count = report
+                .items
+                .iter()
+                .zip(&risk.items)
+                .filter(|(item, e)| e.is_some() && on_host(item))
+                .count();
+            writeln!(
+                out,
+                "Host {} risk: {:.2} ({count} findings, criticality {})",
+                host_label(h),
+                risk.hosts[idx].total(),
+                tier.as_deref().unwrap_or("not set")
+            )?;
+            scoring::write_factors(out, shares(&risk.hosts[idx]))?;
+            writeln!(out, "Highest scoring findings:")?;
+            report
+                .items
+                .iter()
+                .zip(&risk.items)
+                .filter(|(item, _)| on_host(item))
+                .filter_map(|(item, e)| {
+                    let name = item.plugin_name.as_deref().unwrap_or("");
+                    let pid = item.plugin_id.unwrap_or(0);
+                    Some((format!("{pid} {name} {}", label(item)), e.as_ref()?.total()))
+                })
+                .collect()
+        }
+        None => {
+            let count = risk.items.iter().flatten().count();
+            writeln!(
+                out,
+                "Network risk: {:.2} ({count} findings on {} hosts)",
+                risk.network.total(),
+                report.hosts.len()
+            )?;
+            scoring::write_factors(out, shares(&risk.network))?;
+            writeln!(out, "Highest scoring hosts:")?;
+            report
+                .hosts
+                .iter()
+                .zip(&risk.hosts)
+                .map(|(h, e)| (host_label(h), e.total()))
+                .collect()
+        }
+    };
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (name, score) in ranked.iter().take(top) {
+        writeln!(out, "  {score:>8.2}  {name}")?;
+    }
+    Ok(())
+}
+
+/// Load a scan for `diff` from an input file, or from the database when
+/// `spec` is a report ID.
+fn load_scan(spec: &str, cfg: &config::Config) -> Result<parser::NessusReport, error::Error> {
+    let path = std::path::Path::new(spec);
+    let mut report = if path.exists() {
+        parser::parse_file(path)?
+    } else if let Ok(id) = spec.parse::<i32>() {
+        let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
+        loader::load_report(&mut conn, Some(id))?
+    } else {
+        return Err(error::Error::InvalidDocument(format!(
+            "'{spec}' is neither an input file nor a report ID"
+        )));
+    };
+    parser::apply_severity_overrides(&mut report, &cfg.severity_overrides);
+    report.cvss_version = cfg.cvss_version;
+    report.risk_scoring = cfg.risk_scoring.clone();
+    Ok(report)
+}
+
+/// Build the template manager with every built-in template registered and
+/// external templates loaded from the configured paths.
+fn template_manager(cfg: &config::Config) -> Result<template::TemplateManager, error::Error> {
+    let paths = cfg
+        .template_paths
+        .iter()
+        .map(std::path::PathBuf::from)
+        .collect();
+    let mut manager = template::TemplateManager::new(paths);
+    manager.register(Box::new(template::SimpleTemplate));
+    manager.register(Box::new(templates::TemplateTemplate));
+    manager.register(Box::new(templates::AssetsTemplate));
+    manager.register(Box::new(templates::HostSummaryTemplate));
+    manager.register(Box::new(templates::MSPatchSummaryTemplate));
+    manager.register(Box::new(templates::PCIComplianceTemplate));
+    manager.register(Box::new(templates::StigFindingsSummaryTemplate));
+    manager.register(Box::new(templates::StigDetailedTemplate));
+    manager.register(Box::new(templates::SslMediumStrCipherSupportTemplate));
+    manager.register(Box::new(templates::SslSummaryTemplate));
+    manager.register(Box::new(templates::AuthenticationSummaryTemplate));
+    manager.register(Box::new(templates::RemoteLocalSummaryTemplate));
+    manager.register(Box::new(templates::CoverSheetTemplate));
+    manager.register(Box::new(templates::ExecSummaryTemplate));
+    manager.register(Box::new(templates::ExecutiveSummaryDetailedTemplate));
+    manager.register(Box::new(templates::ExploitablitySummaryTemplate));
+    manager.register(Box::new(templates::FailedAuditsTemplate));
+    manager.register(Box::new(templates::GraphsTemplate));
+    manager.register(Box::new(templates::PluginSummaryTemplate));
+    manager.register(Box::new(templates::RollupSummaryTemplate));
+    manager.register(Box::new(templates::TalkingPointsTemplate));
+    manager.register(Box::new(templates::TechnicalFindingsTemplate));
+    manager.register(Box::new(templates::MSUpdateSummaryTemplate));
+    manager.register(Box::new(templates::NotableTemplate));
+    manager.register(Box::new(templates::NotableDetailedTemplate));
+    manager.register(Box::new(templates::FindingStatisticsTemplate));
+    manager.register(Box::new(templates::HostFindingsCsvTemplate));
+    manager.register(Box::new(templates::HostFindingsCsvOlderThanTemplate));
+    manager.register(Box::new(templates::FixListTemplate));
+    manager.register(Box::new(templates::Top25Template));
+    manager.register(Box::new(templates::SansTopTemplate));
+    manager.register(Box::new(templates::ScanDiffTemplate));
+    manager.register(Box::new(templates::SuppressionsTemplate));
+    manager.register(Box::new(templates::FindingsHostTemplate));
+    manager.register(Box::new(templates::FindingsSummaryTemplate));
+    manager.register(Box::new(templates::FindingsSummaryWithPluginIdTemplate));
+    manager.register(Box::new(templates::MaliciousProcessDetectionTemplate));
+    manager.register(Box::new(templates::MissingRootCausesTemplate));
+    manager.register(Box::new(
+        templates::MicrosoftWindowsUnquotedServicePathEnumerationTemplate,
+    ));
+    manager.register(Box::new(templates::MSWSUSFindingsTemplate));
+    manager.register(Box::new(templates::ServiceInventoryTemplate));
+    manager.register(Box::new(templates::UnsupportedOsTemplate));
+    manager.register(Box::new(templates::VirtualMachineSummaryTemplate));
+    manager.load_templates().map_err(error::Error::Template)?;
+    Ok(manager)
+}
+
 fn main() {
     if let Err(e) = run() {
         match e {
@@ -218,8 +658,9 @@ fn main() {
 
 fn run() -> Result<(), error::Error> {
     let args: Vec<String> = std::env::args().collect();
+    // stdout is kept for data, e.g. `export` without `-o`.
     if !args.iter().any(|a| a == "--no-banner") {
-        println!("{}", banner::random());
+        eprintln!("{}", banner::random());
     }
     let cli = Cli::parse_from(args);
 
@@ -262,9 +703,12 @@ fn run() -> Result<(), error::Error> {
     }
 
     let mut cfg = config::load_config(&config_path).unwrap_or_default();
+    if let Some(version) = cli.cvss_version {
+        cfg.cvss_version = version;
+    }
 
     if cfg.database_backend == "sqlite" {
-        if let Ok(mut conn) = SqliteConnection::establish(&cfg.database_url) {
+        if let Ok(mut conn) = db::establish(&cfg.database_url, &cfg.database_backend) {
             version::warn_on_mismatch(&mut conn);
         }
     }
@@ -275,55 +719,7 @@ fn run() -> Result<(), error::Error> {
     }
 
     if cli.list_templates {
-        let paths = cfg
-            .template_paths
-            .iter()
-            .map(std::path::PathBuf::from)
-            .collect();
-        let mut manager = template::TemplateManager::new(paths);
-        manager.register(Box::new(template::SimpleTemplate));
-        manager.register(Box::new(templates::TemplateTemplate));
-        manager.register(Box::new(templates::AssetsTemplate));
-        manager.register(Box::new(templates::HostSummaryTemplate));
-        manager.register(Box::new(templates::MSPatchSummaryTemplate));
-        manager.register(Box::new(templates::PCIComplianceTemplate));
-        manager.register(Box::new(templates::StigFindingsSummaryTemplate));
-        manager.register(Box::new(templates::StigDetailedTemplate));
-        manager.register(Box::new(templates::SslMediumStrCipherSupportTemplate));
-        manager.register(Box::new(templates::SslSummaryTemplate));
-        manager.register(Box::new(templates::AuthenticationSummaryTemplate));
-        manager.register(Box::new(templates::RemoteLocalSummaryTemplate));
-        manager.register(Box::new(templates::CoverSheetTemplate));
-        manager.register(Box::new(templates::ExecSummaryTemplate));
-        manager.register(Box::new(templates::ExecutiveSummaryDetailedTemplate));
-        manager.register(Box::new(templates::ExploitablitySummaryTemplate));
-        manager.register(Box::new(templates::FailedAuditsTemplate));
-        manager.register(Box::new(templates::GraphsTemplate));
-        manager.register(Box::new(templates::PluginSummaryTemplate));
-        manager.register(Box::new(templates::RollupSummaryTemplate));
-        manager.register(Box::new(templates::TalkingPointsTemplate));
-        manager.register(Box::new(templates::TechnicalFindingsTemplate));
-        manager.register(Box::new(templates::MSUpdateSummaryTemplate));
-        manager.register(Box::new(templates::NotableTemplate));
-        manager.register(Box::new(templates::NotableDetailedTemplate));
-        manager.register(Box::new(templates::FindingStatisticsTemplate));
-        manager.register(Box::new(templates::HostFindingsCsvTemplate));
-        manager.register(Box::new(templates::HostFindingsCsvOlderThanTemplate));
-        manager.register(Box::new(templates::Top25Template));
-        manager.register(Box::new(templates::SansTopTemplate));
-        manager.register(Box::new(templates::FindingsHostTemplate));
-        manager.register(Box::new(templates::FindingsSummaryTemplate));
-        manager.register(Box::new(templates::FindingsSummaryWithPluginIdTemplate));
-        manager.register(Box::new(templates::MaliciousProcessDetectionTemplate));
-        manager.register(Box::new(templates::MissingRootCausesTemplate));
-        manager.register(Box::new(
-            templates::MicrosoftWindowsUnquotedServicePathEnumerationTemplate,
-        ));
-        manager.register(Box::new(templates::MSWSUSFindingsTemplate));
-        manager.register(Box::new(templates::ServiceInventoryTemplate));
-        manager.register(Box::new(templates::UnsupportedOsTemplate));
-        manager.register(Box::new(templates::VirtualMachineSummaryTemplate));
-        manager.load_templates().map_err(error::Error::Template)?;
+        let manager = template_manager(&cfg)?;
         manager.display();
         return Ok(());
     }
@@ -331,7 +727,7 @@ fn run() -> Result<(), error::Error> {
     if let Some(keyword) = cli.search_output.as_deref() {
         use crate::schema::nessus_hosts::dsl::{ip, nessus_hosts};
 
-        let mut conn = SqliteConnection::establish(&cfg.database_url)?;
+        let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
         let items = models::Item::search_plugin_output(&mut conn, keyword, None)?;
         for item in items {
             let host = item
@@ -357,6 +753,8 @@ fn run() -> Result<(), error::Error> {
 
     if let Some(path) = cli.nessus_sqlite {
         let mut report = parser::parse_nessus_sqlite(&path)?;
+        report.cvss_version = cfg.cvss_version;
+        report.risk_scoring = cfg.risk_scoring.clone();
         postprocess::process(
             &mut report,
             &HashSet::new(),
@@ -375,18 +773,21 @@ fn run() -> Result<(), error::Error> {
 
     match cli.command {
         Some(Commands::Parse {
-            file,
+            files,
             output,
             template: tmpl_name,
             renderer: renderer_opt,
             template_args,
             older_than,
             post_process,
+            continue_on_error,
         }) => {
             let blacklist: HashSet<i32> = cli.blacklist.iter().cloned().collect();
             let whitelist: HashSet<i32> = cli.whitelist.iter().cloned().collect();
-            let mut report = parser::parse_file(&file)?;
+            let mut report = parse_inputs(&files, continue_on_error)?;
             parser::apply_severity_overrides(&mut report, &cfg.severity_overrides);
+            report.cvss_version = cfg.cvss_version;
+            report.risk_scoring = cfg.risk_scoring.clone();
             report.filters = parser::Filters::default();
             if post_process {
                 postprocess::process(
@@ -405,60 +806,11 @@ fn run() -> Result<(), error::Error> {
                 report.attachments.len()
             );
 
-            let mut conn = SqliteConnection::establish(":memory:")?;
-            conn
-                .run_pending_migrations(migrate::MIGRATIONS)
-                .map_err(error::Error::Migration)?;
-            persist::to_sqlite(&mut conn, &report)?;
+            let mut conn = db::establish(":memory:", "sqlite")?;
+            db::run_pending_migrations(&mut conn)?;
+            persist::to_database(&mut conn, &report)?;
 
-            let paths = cfg
-                .template_paths
-                .iter()
-                .map(std::path::PathBuf::from)
-                .collect();
-            let mut manager = template::TemplateManager::new(paths);
-            manager.register(Box::new(template::SimpleTemplate));
-            manager.register(Box::new(templates::TemplateTemplate));
-            manager.register(Box::new(templates::AssetsTemplate));
-            manager.register(Box::new(templates::HostSummaryTemplate));
-            manager.register(Box::new(templates::MSPatchSummaryTemplate));
-            manager.register(Box::new(templates::PCIComplianceTemplate));
-            manager.register(Box::new(templates::StigFindingsSummaryTemplate));
-            manager.register(Box::new(templates::StigDetailedTemplate));
-            manager.register(Box::new(templates::SslMediumStrCipherSupportTemplate));
-            manager.register(Box::new(templates::SslSummaryTemplate));
-            manager.register(Box::new(templates::AuthenticationSummaryTemplate));
-            manager.register(Box::new(templates::RemoteLocalSummaryTemplate));
-            manager.register(Box::new(templates::CoverSheetTemplate));
-            manager.register(Box::new(templates::ExecSummaryTemplate));
-            manager.register(Box::new(templates::ExecutiveSummaryDetailedTemplate));
-            manager.register(Box::new(templates::ExploitablitySummaryTemplate));
-            manager.register(Box::new(templates::FailedAuditsTemplate));
-            manager.register(Box::new(templates::GraphsTemplate));
-            manager.register(Box::new(templates::PluginSummaryTemplate));
-            manager.register(Box::new(templates::RollupSummaryTemplate));
-            manager.register(Box::new(templates::TalkingPointsTemplate));
-            manager.register(Box::new(templates::TechnicalFindingsTemplate));
-            manager.register(Box::new(templates::MSUpdateSummaryTemplate));
-            manager.register(Box::new(templates::NotableTemplate));
-            manager.register(Box::new(templates::NotableDetailedTemplate));
-            manager.register(Box::new(templates::FindingStatisticsTemplate));
-            manager.register(Box::new(templates::HostFindingsCsvTemplate));
-            manager.register(Box::new(templates::HostFindingsCsvOlderThanTemplate));
-            manager.register(Box::new(templates::FixListTemplate));
-            manager.register(Box::new(templates::Top25Template));
-            manager.register(Box::new(templates::SansTopTemplate));
-            manager.register(Box::new(templates::FindingsHostTemplate));
-            manager.register(Box::new(templates::FindingsSummaryTemplate));
-            manager.register(Box::new(templates::FindingsSummaryWithPluginIdTemplate));
-            manager.register(Box::new(templates::MaliciousProcessDetectionTemplate));
-            manager.register(Box::new(templates::MissingRootCausesTemplate));
-            manager.register(Box::new(templates::MicrosoftWindowsUnquotedServicePathEnumerationTemplate));
-            manager.register(Box::new(templates::MSWSUSFindingsTemplate));
-            manager.register(Box::new(templates::ServiceInventoryTemplate));
-            manager.register(Box::new(templates::UnsupportedOsTemplate));
-            manager.register(Box::new(templates::VirtualMachineSummaryTemplate));
-            manager.load_templates().map_err(error::Error::Template)?;
+            let manager = template_manager(&cfg)?;
 
             let mut template_args_map: HashMap<String, String> = cfg
                 .template_settings
@@ -480,9 +832,219 @@ fn run() -> Result<(), error::Error> {
                 .generate(&report, renderer_opt.as_deref(), &template_args_map)
                 .map_err(error::Error::Template)?;
         }
+        Some(Commands::Import {
+            file,
+            post_process: _,
+            stream: true,
+            batch_hosts,
+        }) => {
+            let blacklist: HashSet<i32> = cli.blacklist.iter().cloned().collect();
+            let whitelist: HashSet<i32> = cli.whitelist.iter().cloned().collect();
+            let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
+            db::run_pending_migrations(&mut conn)?;
+            // One outer transaction, so a file that fails partway through
+            // leaves no partial report behind; each batch is a savepoint.
+            let (hosts, items, report_id) = conn.transaction::<_, error::Error, _>(|conn| {
+                let mut sink = persist::DatabaseSink::new(conn)
+                    .batch_hosts(batch_hosts)
+                    .severity_overrides(cfg.severity_overrides.clone())
+                    .plugin_filters(whitelist, blacklist);
+                parser::stream_nessus(&file, &mut sink)?;
+                Ok((sink.hosts_written(), sink.items_written(), sink.report_id()))
+            })?;
+            println!("Streamed {hosts} hosts, {items} items");
+            if let Some(report_id) = report_id {
+                println!("Imported report ID: {report_id}");
+            }
+        }
+        Some(Commands::Import {
+            file,
+            post_process,
+            stream: false,
+            ..
+        }) => {
+            let blacklist: HashSet<i32> = cli.blacklist.iter().cloned().collect();
+            let whitelist: HashSet<i32> = cli.whitelist.iter().cloned().collect();
+            let mut report = parser::parse_file(&file)?;
+            parser::apply_severity_overrides(&mut report, &cfg.severity_overrides);
+            report.cvss_version = cfg.cvss_version;
+            report.risk_scoring = cfg.risk_scoring.clone();
+            if post_process {
+                postprocess::process(
+                    &mut report,
+                    &whitelist,
+                    &blacklist,
+                    &parser::Filters::default(),
+                );
+            } else {
+                parser::filter_report(
+                    &mut report,
+                    &whitelist,
+                    &blacklist,
+                    &parser::Filters::default(),
+                );
+            }
+
+            println!(
+                "Parsed {} hosts, {} items, {} plugins, {} attachments",
+                report.hosts.len(),
+                report.items.len(),
+                report.plugins.len(),
+                report.attachments.len()
+            );
+
+            let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
+            db::run_pending_migrations(&mut conn)?;
+            let report_id = persist::to_database(&mut conn, &report)?;
+            println!("Imported report ID: {report_id}");
+        }
+        Some(Commands::Merge { report_ids, title }) => {
+            let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
+            db::run_pending_migrations(&mut conn)?;
+            let reports = report_ids
+                .iter()
+                .map(|id| loader::load_report(&mut conn, Some(*id)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let (mut report, summary) = analysis::merge::combine(reports);
+            if title.is_some() {
+                report.report.title = title;
+            }
+            println!(
+                "Merged {} reports: {} hosts correlated into {} assets",
+                report_ids.len(),
+                summary.hosts_before,
+                summary.assets
+            );
+            let report_id = persist::to_database(&mut conn, &report)?;
+            println!("Merged report ID: {report_id}");
+        }
+        Some(Commands::Risk {
+            scan,
+            host,
+            plugin_id,
+            top,
+        }) => {
+            let mut report = load_scan(&scan, &cfg)?;
+            let blacklist: HashSet<i32> = cli.blacklist.iter().cloned().collect();
+            let whitelist: HashSet<i32> = cli.whitelist.iter().cloned().collect();
+            let filters = parser::Filters::default();
+            parser::filter_report(&mut report, &whitelist, &blacklist, &filters);
+            explain_risk(&report, host.as_deref(), plugin_id, top)?;
+        }
+        Some(Commands::Diff {
+            baseline,
+            current,
+            output,
+            template: tmpl_name,
+            renderer: renderer_opt,
+            template_args,
+        }) => {
+            let baseline = load_scan(&baseline, &cfg)?;
+            let mut report = load_scan(&current, &cfg)?;
+            let diff = analysis::diff::diff_reports(&baseline, &report);
+            println!(
+                "{} new, {} remediated, {} severity changed, {} persistent",
+                diff.count(analysis::diff::DiffStatus::New),
+                diff.count(analysis::diff::DiffStatus::Remediated),
+                diff.count(analysis::diff::DiffStatus::SeverityChanged),
+                diff.count(analysis::diff::DiffStatus::Persistent)
+            );
+            report.diff = Some(diff);
+
+            let mut conn = db::establish(":memory:", "sqlite")?;
+            db::run_pending_migrations(&mut conn)?;
+            persist::to_database(&mut conn, &report)?;
+
+            let manager = template_manager(&cfg)?;
+            let mut template_args_map: HashMap<String, String> = cfg
+                .template_settings
+                .get(&tmpl_name)
+                .cloned()
+                .unwrap_or_default();
+            template_args_map.extend(template_args.into_iter());
+
+            let mut templater =
+                template::templater::Templater::new(tmpl_name, &mut conn, output, manager);
+            templater
+                .generate(&report, renderer_opt.as_deref(), &template_args_map)
+                .map_err(error::Error::Template)?;
+        }
+        Some(Commands::Export {
+            file,
+            report_id,
+            format,
+            output,
+            older_than,
+            host_ip,
+            host_mac,
+            host_id,
+            plugin_id,
+            tag,
+            post_process,
+        }) => {
+            let blacklist: HashSet<i32> = cli.blacklist.iter().cloned().collect();
+            let whitelist: HashSet<i32> = cli.whitelist.iter().cloned().collect();
+            let mut report = match file {
+                Some(file) => parser::parse_file(&file)?,
+                None => {
+                    let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
+                    loader::load_report(&mut conn, report_id)?
+                }
+            };
+            parser::apply_severity_overrides(&mut report, &cfg.severity_overrides);
+            report.cvss_version = cfg.cvss_version;
+            report.risk_scoring = cfg.risk_scoring.clone();
+            if post_process {
+                postprocess::process(
+                    &mut report,
+                    &whitelist,
+                    &blacklist,
+                    &parser::Filters::default(),
+                );
+            }
+            let filters = parser::Filters {
+                host_ip,
+                host_mac,
+                host_id,
+                plugin_id,
+                older_than: older_than
+                    .map(|days| (Utc::now() - Duration::days(days)).naive_utc()),
+                tag,
+            };
+            report.filters = filters.clone();
+            parser::filter_report(&mut report, &whitelist, &blacklist, &filters);
+
+            type Writer =
+                fn(&parser::NessusReport, &mut dyn std::io::Write) -> Result<(), error::Error>;
+            let write: Writer = match format.as_str() {
+                "json" => export::write_json,
+                "sarif" => export::write_sarif,
+                other => {
+                    let msg = format!("unsupported export format '{other}'");
+                    return Err(error::Error::Config(msg));
+                }
+            };
+            match output {
+                Some(path) => {
+                    let mut f = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                    write(&report, &mut f)?;
+                    std::io::Write::flush(&mut f)?;
+                    println!(
+                        "Exported {} hosts, {} items to {}",
+                        report.hosts.len(),
+                        report.items.len(),
+                        path.display()
+                    );
+                }
+                None => write(&report, &mut std::io::stdout().lock())?,
+            }
+        }
         Some(Commands::PluginIndex { dir }) => {
             plugin_index::run(&dir)?;
         }
+        Some(Commands::Rollups { action }) => match action {
+            RollupsAction::Lint { file, index, write } => lint_rollups(file, &index, write)?,
+        },
         Some(Commands::Render {
             report_id,
             template: tmpl_name,
@@ -494,69 +1056,31 @@ fn run() -> Result<(), error::Error> {
             host_mac,
             host_id,
             plugin_id,
+            tag,
         }) => {
-            let mut conn = SqliteConnection::establish(&cfg.database_url)?;
+            let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
             let mut report = loader::load_report(&mut conn, report_id)?;
             parser::apply_severity_overrides(&mut report, &cfg.severity_overrides);
+            report.cvss_version = cfg.cvss_version;
+            report.risk_scoring = cfg.risk_scoring.clone();
+            // Re-check the register so entries that expired since the import
+            // no longer hide their findings.
+            analysis::suppression::apply_register(
+                &mut report,
+                chrono::Local::now().naive_local().date(),
+            );
             let filters = parser::Filters {
                 host_ip,
                 host_mac,
                 host_id,
                 plugin_id,
+                tag,
+                ..parser::Filters::default()
             };
             report.filters = filters.clone();
             parser::filter_report(&mut report, &HashSet::new(), &HashSet::new(), &filters);
 
-            let paths = cfg
-                .template_paths
-                .iter()
-                .map(std::path::PathBuf::from)
-                .collect();
-            let mut manager = template::TemplateManager::new(paths);
-            manager.register(Box::new(template::SimpleTemplate));
-            manager.register(Box::new(templates::TemplateTemplate));
-            manager.register(Box::new(templates::AssetsTemplate));
-            manager.register(Box::new(templates::HostSummaryTemplate));
-            manager.register(Box::new(templates::MSPatchSummaryTemplate));
-            manager.register(Box::new(templates::PCIComplianceTemplate));
-            manager.register(Box::new(templates::StigFindingsSummaryTemplate));
-            manager.register(Box::new(templates::StigDetailedTemplate));
-            manager.register(Box::new(templates::SslMediumStrCipherSupportTemplate));
-            manager.register(Box::new(templates::SslSummaryTemplate));
-            manager.register(Box::new(templates::AuthenticationSummaryTemplate));
-            manager.register(Box::new(templates::RemoteLocalSummaryTemplate));
-            manager.register(Box::new(templates::CoverSheetTemplate));
-            manager.register(Box::new(templates::ExecSummaryTemplate));
-            manager.register(Box::new(templates::ExecutiveSummaryDetailedTemplate));
-            manager.register(Box::new(templates::ExploitablitySummaryTemplate));
-            manager.register(Box::new(templates::FailedAuditsTemplate));
-            manager.register(Box::new(templates::GraphsTemplate));
-            manager.register(Box::new(templates::PluginSummaryTemplate));
-            manager.register(Box::new(templates::RollupSummaryTemplate));
-            manager.register(Box::new(templates::TalkingPointsTemplate));
-            manager.register(Box::new(templates::TechnicalFindingsTemplate));
-            manager.register(Box::new(templates::MSUpdateSummaryTemplate));
-            manager.register(Box::new(templates::NotableTemplate));
-            manager.register(Box::new(templates::NotableDetailedTemplate));
-            manager.register(Box::new(templates::FindingStatisticsTemplate));
-            manager.register(Box::new(templates::HostFindingsCsvTemplate));
-            manager.register(Box::new(templates::HostFindingsCsvOlderThanTemplate));
-            manager.register(Box::new(templates::FixListTemplate));
-            manager.register(Box::new(templates::Top25Template));
-            manager.register(Box::new(templates::SansTopTemplate));
-            manager.register(Box::new(templates::FindingsHostTemplate));
-            manager.register(Box::new(templates::FindingsSummaryTemplate));
-            manager.register(Box::new(templates::FindingsSummaryWithPluginIdTemplate));
-            manager.register(Box::new(templates::MaliciousProcessDetectionTemplate));
-            manager.register(Box::new(templates::MissingRootCausesTemplate));
-            manager.register(Box::new(
-                templates::MicrosoftWindowsUnquotedServicePathEnumerationTemplate,
-            ));
-            manager.register(Box::new(templates::MSWSUSFindingsTemplate));
-            manager.register(Box::new(templates::ServiceInventoryTemplate));
-            manager.register(Box::new(templates::UnsupportedOsTemplate));
-            manager.register(Box::new(templates::VirtualMachineSummaryTemplate));
-            manager.load_templates().map_err(error::Error::Template)?;
+            let manager = template_manager(&cfg)?;
 
             let mut template_args_map: HashMap<String, String> = cfg
                 .template_settings
@@ -609,56 +1133,16 @@ fn run() -> Result<(), error::Error> {
                     println!("Migration complete");
                 }
                 DbAction::TestConnection => {
-                    match cfg.database_backend.as_str() {
-                        "postgres" => {
-                            #[cfg(feature = "postgres")]
-                            {
-                                match diesel::pg::PgConnection::establish(&cfg.database_url) {
-                                    Ok(_) => println!("Database connection successful"),
-                                    Err(e) => {
-                                        println!("Database connection failed: {e}");
-                                        return Err(e.into());
-                                    }
-                                }
-                            }
-                            #[cfg(not(feature = "postgres"))]
-                            {
-                                println!("PostgreSQL support not enabled");
-                            }
+                    match db::establish(&cfg.database_url, &cfg.database_backend) {
+                        Ok(_) => println!("Database connection successful"),
+                        Err(e) => {
+                            println!("Database connection failed: {e}");
+                            return Err(e);
                         }
-                        "mysql" => {
-                            #[cfg(feature = "mysql")]
-                            {
-                                match diesel::mysql::MysqlConnection::establish(&cfg.database_url)
-                                {
-                                    Ok(_) => println!("Database connection successful"),
-                                    Err(e) => {
-                                        println!("Database connection failed: {e}");
-                                        return Err(e.into());
-                                    }
-                                }
-                            }
-                            #[cfg(not(feature = "mysql"))]
-                            {
-                                println!("MySQL support not enabled");
-                            }
-                        }
-                        _ => match SqliteConnection::establish(&cfg.database_url) {
-                            Ok(_) => println!("Database connection successful"),
-                            Err(e) => {
-                                println!("Database connection failed: {e}");
-                                return Err(e.into());
-                            }
-                        },
                     }
                 }
                 DbAction::Version => {
-                    if cfg.database_backend != "sqlite" {
-                        return Err(error::Error::Config(
-                            "db version is only supported with the sqlite backend".to_string(),
-                        ));
-                    }
-                    let mut conn = SqliteConnection::establish(&cfg.database_url)?;
+                    let mut conn = db::establish(&cfg.database_url, &cfg.database_backend)?;
                     match version::db_version(&mut conn)? {
                         Some(v) => println!("{v}"),
                         None => println!("unknown"),
@@ -672,7 +1156,7 @@ fn run() -> Result<(), error::Error> {
                         );
                         return Ok(());
                     }
-                    console::run(&config_path)?;
+                    console::run(&cfg)?;
                 }
             }
         }
@@ -684,7 +1168,10 @@ fn run() -> Result<(), error::Error> {
 fn init_logging(level: &str, format: &str) {
     let filter = tracing_subscriber::EnvFilter::try_new(level)
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    // Log to stderr so commands such as `export` can write data to stdout.
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
     match format {
         "json" => builder.json().init(),
         _ => builder.init(),
@@ -5,6 +5,7 @@
 //! implemented at the moment.
 
 pub mod attachment;
+pub mod cvss;
 pub mod family_selection;
 pub mod host;
 pub mod host_property;
@@ -22,6 +23,7 @@ pub mod scanner;
 pub mod version;
 
 pub use attachment::Attachment;
+pub use cvss::{CvssScores, CvssVersion};
 pub use family_selection::FamilySelection;
 pub use host_property::HostProperty;
 pub use item::Item;
@@ -37,12 +39,13 @@ pub use service_description::ServiceDescription;
 pub use scanner::Scanner;
 
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
 use std::net::IpAddr;
 
+use crate::db::DbConnection;
 use crate::schema::{nessus_hosts, nessus_plugins};
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize, Default)]
 #[diesel(belongs_to(Report, foreign_key = nessus_report_id))]
 #[diesel(table_name = nessus_hosts)]
 pub struct Host {
@@ -63,7 +66,7 @@ pub struct Host {
     pub scanner_id: Option<i32>,
 }
 
-#[derive(Debug, Queryable, Identifiable)]
+#[derive(Debug, Queryable, Identifiable, Serialize)]
 #[diesel(table_name = nessus_plugins)]
 pub struct Plugin {
     pub id: i32,
@@ -113,6 +116,17 @@ pub struct Plugin {
     pub engagement_id: Option<i32>,
     pub policy_id: Option<i32>,
     pub scanner_id: Option<i32>,
+    pub oid: Option<String>,
+    pub cvss3_base_score: Option<f32>,
+    pub cvss3_vector: Option<String>,
+    pub cvss3_temporal_score: Option<f32>,
+    pub cvss3_temporal_vector: Option<String>,
+    pub cvss4_base_score: Option<f32>,
+    pub cvss4_vector: Option<String>,
+    pub vpr_score: Option<f32>,
+    pub epss_score: Option<f32>,
+    pub exploit_code_maturity: Option<String>,
+    pub threat_intensity_last_28: Option<String>,
 }
 
 impl Default for Plugin {
@@ -165,12 +179,23 @@ impl Default for Plugin {
             engagement_id: None,
             policy_id: None,
             scanner_id: None,
+            oid: None,
+            cvss3_base_score: None,
+            cvss3_vector: None,
+            cvss3_temporal_score: None,
+            cvss3_temporal_vector: None,
+            cvss4_base_score: None,
+            cvss4_vector: None,
+            vpr_score: None,
+            epss_score: None,
+            exploit_code_maturity: None,
+            threat_intensity_last_28: None,
         }
     }
 }
 
 impl Host {
-    pub fn sorted(conn: &mut SqliteConnection, scanner: Option<i32>) -> QueryResult<Vec<Host>> {
+    pub fn sorted(conn: &mut DbConnection, scanner: Option<i32>) -> QueryResult<Vec<Host>> {
         use crate::schema::nessus_hosts::dsl::*;
         let mut query = nessus_hosts.filter(ip.is_not_null()).into_boxed();
         if let Some(sid) = scanner {
@@ -185,7 +210,7 @@ impl Host {
         Ok(results)
     }
 
-    pub fn ip_list(conn: &mut SqliteConnection, scanner: Option<i32>) -> QueryResult<String> {
+    pub fn ip_list(conn: &mut DbConnection, scanner: Option<i32>) -> QueryResult<String> {
         let hosts = Host::sorted(conn, scanner)?;
         Ok(hosts
             .into_iter()
@@ -233,6 +258,7 @@ mod tests {
             .execute(&mut conn)
             .unwrap();
 
+        let mut conn = DbConnection::from(conn);
         let list = Host::ip_list(&mut conn, None).unwrap();
         assert_eq!(list, "10.0.0.1\n10.0.0.2");
     }
@@ -1,8 +1,9 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::schema::nessus_attachments;
 
-#[derive(Debug, Queryable, Identifiable)]
+#[derive(Debug, Queryable, Identifiable, Serialize)]
 #[diesel(table_name = nessus_attachments)]
 pub struct Attachment {
     pub id: i32,
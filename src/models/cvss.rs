@@ -0,0 +1,143 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Item, Plugin};
+
+/// CVSS version whose scores are preferred when ranking and reporting findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "RawVersion", into = "String")]
+pub enum CvssVersion {
+    #[default]
+    V2,
+    V3,
+    V4,
+}
+
+/// Configuration files may spell the version as a number (`3`, `3.1`) or a
+/// string (`"v3"`).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawVersion {
+    Number(f64),
+    Text(String),
+}
+
+impl TryFrom<RawVersion> for CvssVersion {
+    type Error = String;
+
+    fn try_from(raw: RawVersion) -> Result<Self, Self::Error> {
+        match raw {
+            RawVersion::Number(n) => n.to_string().parse(),
+            RawVersion::Text(s) => s.parse(),
+        }
+    }
+}
+
+impl From<CvssVersion> for String {
+    fn from(v: CvssVersion) -> Self {
+        v.to_string()
+    }
+}
+
+impl CvssVersion {
+    /// The preferred version followed by the remaining versions, newest first.
+    fn fallback_order(self) -> [CvssVersion; 3] {
+        match self {
+            CvssVersion::V2 => [CvssVersion::V2, CvssVersion::V4, CvssVersion::V3],
+            CvssVersion::V3 => [CvssVersion::V3, CvssVersion::V4, CvssVersion::V2],
+            CvssVersion::V4 => [CvssVersion::V4, CvssVersion::V3, CvssVersion::V2],
+        }
+    }
+}
+
+impl fmt::Display for CvssVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CvssVersion::V2 => "v2",
+            CvssVersion::V3 => "v3",
+            CvssVersion::V4 => "v4",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for CvssVersion {
+    type Err = String;
+
+    /// Accepts `2`, `3`, `4` with an optional `v`/`cvss` prefix and minor
+    /// version (`v3.1`, `cvss4.0`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let v = lower
+            .trim_start_matches("cvss")
+            .trim_start_matches(['v', ' ', '_', '-']);
+        match v.split('.').next() {
+            Some("2") => Ok(CvssVersion::V2),
+            Some("3") => Ok(CvssVersion::V3),
+            Some("4") => Ok(CvssVersion::V4),
+            _ => Err(format!("unknown CVSS version '{s}' (expected 2, 3 or 4)")),
+        }
+    }
+}
+
+/// Base scores of a finding for each CVSS version.
+pub trait CvssScores {
+    /// Base score recorded for `version`.
+    fn cvss_base(&self, version: CvssVersion) -> Option<f32>;
+
+    /// Base score for the preferred version, falling back to the newest
+    /// other version that has one. Returns the version actually used.
+    fn preferred_cvss(&self, preferred: CvssVersion) -> Option<(CvssVersion, f32)> {
+        preferred
+            .fallback_order()
+            .into_iter()
+            .find_map(|v| self.cvss_base(v).map(|s| (v, s)))
+    }
+}
+
+impl CvssScores for Plugin {
+    fn cvss_base(&self, version: CvssVersion) -> Option<f32> {
+        match version {
+            CvssVersion::V2 => self.cvss_base_score,
+            CvssVersion::V3 => self.cvss3_base_score,
+            CvssVersion::V4 => self.cvss4_base_score,
+        }
+    }
+}
+
+impl CvssScores for Item {
+    fn cvss_base(&self, version: CvssVersion) -> Option<f32> {
+        match version {
+            CvssVersion::V2 => self.cvss_base_score,
+            CvssVersion::V3 => self.cvss3_base_score,
+            CvssVersion::V4 => self.cvss4_base_score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_names() {
+        assert_eq!("3".parse::<CvssVersion>(), Ok(CvssVersion::V3));
+        assert_eq!("v3.1".parse::<CvssVersion>(), Ok(CvssVersion::V3));
+        assert_eq!("CVSS4.0".parse::<CvssVersion>(), Ok(CvssVersion::V4));
+        assert!("5".parse::<CvssVersion>().is_err());
+    }
+
+    #[test]
+    fn falls_back_to_newest_available_score() {
+        let plugin = Plugin {
+            cvss_base_score: Some(5.0),
+            cvss3_base_score: Some(7.5),
+            ..Plugin::default()
+        };
+        assert_eq!(plugin.preferred_cvss(CvssVersion::V2), Some((CvssVersion::V2, 5.0)));
+        assert_eq!(plugin.preferred_cvss(CvssVersion::V4), Some((CvssVersion::V3, 7.5)));
+        assert_eq!(Plugin::default().preferred_cvss(CvssVersion::V3), None);
+    }
+}
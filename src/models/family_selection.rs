@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::schema::nessus_family_selections;
 use super::Policy;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(Policy, foreign_key = policy_id))]
 #[diesel(table_name = nessus_family_selections)]
 pub struct FamilySelection {
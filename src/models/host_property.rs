@@ -1,9 +1,15 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::models::Host;
 use crate::schema::nessus_host_properties;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+/// Property holding a business-context tag of a host; a host may have several.
+pub const ASSET_TAG: &str = "asset-tag";
+/// Property holding the criticality tier of a host.
+pub const ASSET_CRITICALITY: &str = "asset-criticality";
+
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(Host, foreign_key = host_id))]
 #[diesel(table_name = nessus_host_properties)]
 pub struct HostProperty {
@@ -1,9 +1,13 @@
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use diesel::sql_types::{Nullable, Text};
+use serde::Serialize;
 
+use crate::db::DbConnection;
 use crate::schema::nessus_items;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+define_sql_function!(fn lower(x: Nullable<Text>) -> Nullable<Text>);
+
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(super::Host, foreign_key = host_id))]
 #[diesel(belongs_to(super::Plugin, foreign_key = plugin_id))]
 #[diesel(table_name = nessus_items)]
@@ -54,6 +58,21 @@ pub struct Item {
     pub engagement_id: Option<i32>,
     pub rollup_finding: Option<bool>,
     pub scanner_id: Option<i32>,
+    pub confidence: Option<i32>,
+    pub first_discovered: Option<chrono::NaiveDateTime>,
+    pub last_observed: Option<chrono::NaiveDateTime>,
+    pub cvss3_base_score: Option<f32>,
+    pub cvss3_vector: Option<String>,
+    pub cvss3_temporal_score: Option<f32>,
+    pub cvss3_temporal_vector: Option<String>,
+    pub cvss4_base_score: Option<f32>,
+    pub cvss4_vector: Option<String>,
+    pub vpr_score: Option<f32>,
+    pub epss_score: Option<f32>,
+    pub exploit_code_maturity: Option<String>,
+    pub threat_intensity_last_28: Option<String>,
+    /// Hidden by an accepted-risk suppression; see `analysis::suppression`.
+    pub suppressed: Option<bool>,
 }
 
 impl Default for Item {
@@ -105,26 +124,44 @@ impl Default for Item {
             engagement_id: None,
             rollup_finding: Some(false),
             scanner_id: None,
+            confidence: None,
+            first_discovered: None,
+            last_observed: None,
+            cvss3_base_score: None,
+            cvss3_vector: None,
+            cvss3_temporal_score: None,
+            cvss3_temporal_vector: None,
+            cvss4_base_score: None,
+            cvss4_vector: None,
+            vpr_score: None,
+            epss_score: None,
+            exploit_code_maturity: None,
+            threat_intensity_last_28: None,
+            suppressed: None,
         }
     }
 }
 
 impl Item {
     pub fn search_plugin_output(
-        conn: &mut SqliteConnection,
+        conn: &mut DbConnection,
         keyword: &str,
         scanner: Option<i32>,
     ) -> QueryResult<Vec<Self>> {
         use crate::schema::nessus_items::dsl::*;
 
-        let pattern = format!("%{}%", keyword);
+        // Compare lowercased values so the search is case-insensitive on
+        // every backend, not only SQLite.
+        let pattern = format!("%{}%", keyword.to_lowercase());
         let mut query = nessus_items
             .filter(plugin_output.is_not_null())
             .into_boxed();
         if let Some(sid) = scanner {
             query = query.filter(scanner_id.eq(sid));
         }
-        query.filter(plugin_output.like(pattern)).load::<Item>(conn)
+        query
+            .filter(lower(plugin_output).like(pattern))
+            .load::<Item>(conn)
     }
 }
 
@@ -178,6 +215,7 @@ mod tests {
             .execute(&mut conn)
             .unwrap();
 
+        let mut conn = DbConnection::from(conn);
         let lower = Item::search_plugin_output(&mut conn, "foo", None).unwrap();
         assert_eq!(lower.len(), 2);
         let upper = Item::search_plugin_output(&mut conn, "FOO", None).unwrap();
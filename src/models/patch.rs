@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::models::Host;
 use crate::schema::nessus_patches;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(Host, foreign_key = host_id))]
 #[diesel(table_name = nessus_patches)]
 pub struct Patch {
@@ -1,9 +1,10 @@
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
 
+use crate::db::DbConnection;
 use crate::schema::nessus_plugin_metadata;
 
-#[derive(Debug, Queryable, Identifiable)]
+#[derive(Debug, Queryable, Identifiable, Serialize)]
 #[diesel(table_name = nessus_plugin_metadata)]
 pub struct NessusPluginMetadata {
     pub id: i32,
@@ -26,7 +27,7 @@ impl Default for NessusPluginMetadata {
 }
 
 impl NessusPluginMetadata {
-    pub fn by_plugin_id(conn: &mut SqliteConnection, pid: i32) -> QueryResult<Option<Self>> {
+    pub fn by_plugin_id(conn: &mut DbConnection, pid: i32) -> QueryResult<Option<Self>> {
         use crate::schema::nessus_plugin_metadata::dsl::*;
         nessus_plugin_metadata
             .filter(script_id.eq(pid))
@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::models::Report;
 use crate::schema::nessus_policies;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(Report, foreign_key = nessus_report_id))]
 #[diesel(table_name = nessus_policies)]
 pub struct Policy {
@@ -1,8 +1,9 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::schema::nessus_references;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(crate::models::Plugin, foreign_key = plugin_id))]
 #[diesel(belongs_to(crate::models::Item, foreign_key = item_id))]
 #[diesel(table_name = nessus_references)]
@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::models::Host;
 use crate::schema::nessus_reports;
 
-#[derive(Debug, Queryable, Identifiable)]
+#[derive(Debug, Queryable, Identifiable, Serialize)]
 #[diesel(table_name = nessus_reports)]
 pub struct Report {
     pub id: i32,
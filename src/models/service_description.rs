@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use serde::Serialize;
 
 use crate::models::{Host, Item};
 use crate::schema::nessus_service_descriptions;
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(belongs_to(Host, foreign_key = host_id))]
 #[diesel(belongs_to(Item, foreign_key = item_id))]
 #[diesel(table_name = nessus_service_descriptions)]
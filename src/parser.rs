@@ -1,5 +1,6 @@
 //! Utilities for parsing vulnerability scan reports into in-memory models.
 
+mod json_findings;
 mod nessus_sqlite;
 mod nexpose;
 mod nmap;
@@ -17,16 +18,22 @@ use std::path::{Path, PathBuf};
 use ipnet::IpNet;
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use rayon::prelude::*;
 use tracing::{debug, info};
 
+use crate::analysis::diff::ScanDiff;
+use crate::analysis::scoring::RiskScoring;
+use crate::analysis::suppression::Suppression;
 use crate::models::{
-    Attachment, FamilySelection, Host, HostProperty, Item, Patch, Plugin, PluginPreference, Policy,
-    PolicyPlugin, Reference, Report, Scanner, ServerPreference, ServiceDescription,
+    Attachment, CvssVersion, FamilySelection, Host, HostProperty, Item, Patch, Plugin,
+    PluginPreference, Policy, PolicyPlugin, Reference, Report, Scanner, ServerPreference,
+    ServiceDescription,
 };
 use base64::{Engine, engine::general_purpose};
 use chrono::{NaiveDate, NaiveDateTime};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 
 /// XML element names that map directly to reference sources.
@@ -152,15 +159,28 @@ pub struct NessusReport {
     pub server_preferences: Vec<ServerPreference>,
     pub scanner: Scanner,
     pub filters: Filters,
+    /// CVSS version preferred by risk scoring and templates.
+    pub cvss_version: CvssVersion,
+    /// Weights used by the `risk_score` post-process plugin.
+    pub risk_scoring: RiskScoring,
+    /// Comparison with a baseline scan, set by the `diff` command.
+    pub diff: Option<ScanDiff>,
+    /// Accepted-risk register the findings were checked against.
+    pub suppressions: Vec<Suppression>,
 }
 
 /// Filters applied to a parsed report.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize)]
 pub struct Filters {
     pub host_ip: Option<IpNet>,
     pub host_mac: Option<String>,
     pub host_id: Option<i32>,
     pub plugin_id: Option<i32>,
+    /// Keep only findings last observed (or whose host scan ended) before
+    /// this time.
+    pub older_than: Option<NaiveDateTime>,
+    /// Keep only hosts carrying this asset-context tag.
+    pub tag: Option<String>,
 }
 
 impl std::ops::Deref for NessusReport {
@@ -198,6 +218,50 @@ impl NessusReport {
     }
 }
 
+/// Receives a report host by host while it is parsed, so large scans can be
+/// handled without holding every host in memory.
+pub trait ReportSink {
+    /// Take one completed host. `chunk` holds the host at index 0 with its
+    /// items, references, properties, patches, services and attachments, all
+    /// indexed from 0. `header` is the rest of the report parsed so far: the
+    /// version, scanner, policies and every plugin seen up to this host.
+    fn host(&mut self, header: &NessusReport, chunk: NessusReport) -> Result<(), crate::error::Error>;
+
+    /// Take the final header once the whole file has been read.
+    fn finish(&mut self, header: NessusReport) -> Result<(), crate::error::Error>;
+}
+
+/// Collecting every host yields the complete in-memory report.
+impl ReportSink for NessusReport {
+    fn host(&mut self, _header: &NessusReport, chunk: NessusReport) -> Result<(), crate::error::Error> {
+        append_report(self, chunk);
+        Ok(())
+    }
+
+    fn finish(&mut self, header: NessusReport) -> Result<(), crate::error::Error> {
+        let hosts = std::mem::replace(self, header);
+        append_report(self, hosts);
+        let scanner_type = self.scanner.scanner_type.clone();
+        let scanner_version = self.scanner.scanner_version.clone();
+        self.set_scanner(&scanner_type, scanner_version);
+        Ok(())
+    }
+}
+
+/// Parse a `.nessus` file host by host into `sink`.
+///
+/// Attachments are written next to the input file and their base64 data is not
+/// kept, so memory use is bounded by the largest host rather than the file.
+pub fn stream_nessus(path: &Path, sink: &mut dyn ReportSink) -> Result<(), crate::error::Error> {
+    match root_element_name(path)?.as_deref() {
+        Some("NessusClientData_v2") => parse_nessus_into(path, "Nessus", sink, false),
+        _ => Err(crate::error::Error::InvalidDocument(format!(
+            "{}: streaming supports only Nessus v2 (.nessus) files",
+            path.display()
+        ))),
+    }
+}
+
 /// Detect file type and parse accordingly.
 pub fn parse_file(path: &Path) -> Result<NessusReport, crate::error::Error> {
     match path
@@ -207,9 +271,13 @@ pub fn parse_file(path: &Path) -> Result<NessusReport, crate::error::Error> {
         .as_deref()
     {
         Some("csv") => {
+            if security_center::is_security_center_csv(path)? {
+                return security_center::parse_csv(path);
+            }
             let report = simple_nexpose::parse_file(path)?;
             Ok(report.into())
         }
+        Some("json") => json_findings::parse_file(path),
         Some("nmap") => {
             return nmap::parse_file(path);
         }
@@ -226,16 +294,25 @@ pub fn parse_file(path: &Path) -> Result<NessusReport, crate::error::Error> {
             return security_center::parse_file(path);
         }
         _ => {
+            if json_findings::looks_like_json(path)? {
+                return json_findings::parse_file(path);
+            }
             let root = root_element_name(path)?;
             match root.as_deref() {
                 Some("NeXposeSimpleXML") => nexpose::nexpose_document::parse_file(path),
                 Some("nmaprun") => nmap::parse_file(path),
                 Some("NessusClientData_v2") => parse_nessus(path, "Nessus"),
-                Some("openvas-report") | Some("openvas") => openvas::parse_file(path),
+                Some("openvas-report")
+                | Some("openvas")
+                | Some("report")
+                | Some("get_reports_response") => openvas::parse_file(path),
                 Some("SecurityCenter") | Some("security_center") => {
                     security_center::parse_file(path)
                 }
-                Some("QUALYS") | Some("SCAN") => qualys::parse_file(path),
+                Some("QUALYS")
+                | Some("SCAN")
+                | Some("ASSET_DATA_REPORT")
+                | Some("HOST_LIST_VM_DETECTION_OUTPUT") => qualys::parse_file(path),
                 Some("SaintReport") => saint::parse_file(path),
                 Some(other) => Err(crate::error::Error::InvalidDocument(format!(
                     "{}: unsupported root element '{}'",
@@ -251,6 +328,165 @@ pub fn parse_file(path: &Path) -> Result<NessusReport, crate::error::Error> {
     }
 }
 
+/// Concatenate several reports into one, re-basing host, item, attachment
+/// and policy indices.
+///
+/// The first report supplies the report fields and CVSS preference. A plugin
+/// ID already defined by an earlier report keeps that earlier definition.
+pub fn concat_reports(mut reports: Vec<NessusReport>) -> NessusReport {
+    if reports.len() == 1 {
+        return reports.remove(0);
+    }
+    let mut merged = NessusReport::default();
+    let mut scanners: Vec<(String, Option<String>)> = Vec::new();
+    for (n, mut r) in reports.into_iter().enumerate() {
+        if n == 0 {
+            merged.report = std::mem::take(&mut r.report);
+            merged.version = std::mem::take(&mut r.version);
+            merged.cvss_version = r.cvss_version;
+        }
+        let scanner = (r.scanner.scanner_type.clone(), r.scanner.scanner_version.clone());
+        if !scanner.0.is_empty() && !scanners.contains(&scanner) {
+            scanners.push(scanner);
+        }
+        append_report(&mut merged, r);
+    }
+
+    // A single scanner keeps its version; a mix is recorded as e.g. "Nessus + Nmap".
+    match scanners.as_slice() {
+        [(ty, version)] => merged.set_scanner(ty, version.clone()),
+        _ => {
+            let types: Vec<&str> = scanners.iter().map(|(t, _)| t.as_str()).collect();
+            let mut unique = Vec::new();
+            for t in types {
+                if !unique.contains(&t) {
+                    unique.push(t);
+                }
+            }
+            merged.set_scanner(&unique.join(" + "), None);
+        }
+    }
+    merged
+}
+
+/// Append `from` to `into`, re-basing its host, item, attachment and policy
+/// indices. Plugins whose ID `into` already has are dropped.
+pub(crate) fn append_report(into: &mut NessusReport, from: NessusReport) {
+    let host_offset = into.hosts.len() as i32;
+    let item_offset = into.items.len() as i32;
+    let attachment_offset = into.attachments.len() as i32;
+    let policy_offset = into.policies.len() as i32;
+    let shift = |id: &mut Option<i32>, offset: i32| {
+        if let Some(v) = id {
+            *v += offset;
+        }
+    };
+
+    for mut h in from.hosts {
+        h.id = into.hosts.len() as i32;
+        into.hosts.push(h);
+    }
+    for mut it in from.items {
+        it.id = into.items.len() as i32;
+        shift(&mut it.host_id, host_offset);
+        shift(&mut it.attachment_id, attachment_offset);
+        into.items.push(it);
+    }
+    for mut a in from.attachments {
+        a.id = into.attachments.len() as i32;
+        into.attachments.push(a);
+    }
+    if !from.plugins.is_empty() {
+        let known: HashSet<i32> = into.plugins.iter().filter_map(|p| p.plugin_id).collect();
+        for p in from.plugins {
+            if p.plugin_id.is_none_or(|id| !known.contains(&id)) {
+                into.plugins.push(p);
+            }
+        }
+    }
+    for mut rf in from.references {
+        shift(&mut rf.item_id, item_offset);
+        into.references.push(rf);
+    }
+    for mut sd in from.service_descriptions {
+        shift(&mut sd.host_id, host_offset);
+        shift(&mut sd.item_id, item_offset);
+        into.service_descriptions.push(sd);
+    }
+    for mut hp in from.host_properties {
+        shift(&mut hp.host_id, host_offset);
+        into.host_properties.push(hp);
+    }
+    for mut p in from.patches {
+        shift(&mut p.host_id, host_offset);
+        into.patches.push(p);
+    }
+    for mut p in from.policies {
+        p.id += policy_offset;
+        into.policies.push(p);
+    }
+    for mut p in from.policy_plugins {
+        shift(&mut p.policy_id, policy_offset);
+        into.policy_plugins.push(p);
+    }
+    for mut f in from.family_selections {
+        shift(&mut f.policy_id, policy_offset);
+        into.family_selections.push(f);
+    }
+    for mut p in from.plugin_preferences {
+        shift(&mut p.policy_id, policy_offset);
+        into.plugin_preferences.push(p);
+    }
+    for mut p in from.server_preferences {
+        shift(&mut p.policy_id, policy_offset);
+        into.server_preferences.push(p);
+    }
+}
+
+/// File extensions picked up when a directory is given as input.
+pub const INPUT_EXTENSIONS: &[&str] = &[
+    "nessus", "xml", "csv", "json", "nmap", "openvas", "qualys", "saint", "sc",
+    "securitycenter",
+];
+
+/// Expand `paths` into the files to parse.
+///
+/// Directories are walked recursively and contribute the files with one of
+/// [`INPUT_EXTENSIONS`], sorted by path. Files named directly are always kept.
+pub fn collect_input_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, crate::error::Error> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut found = Vec::new();
+        for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+            let entry = entry.map_err(std::io::Error::from)?;
+            let wanted = entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| INPUT_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+            if wanted {
+                found.push(entry.into_path());
+            }
+        }
+        if found.is_empty() {
+            info!("{}: no scan files found", path.display());
+        }
+        files.extend(found);
+    }
+    Ok(files)
+}
+
+/// Parse each of `files` on the rayon thread pool. Results are returned in
+/// the order of `files`.
+pub fn parse_files(files: &[PathBuf]) -> Vec<Result<NessusReport, crate::error::Error>> {
+    files.par_iter().map(|f| parse_file(f)).collect()
+}
+
 /// Parse a Nessus SQLite database export.
 pub fn parse_nessus_sqlite(path: &Path) -> Result<NessusReport, crate::error::Error> {
     let mut report = nessus_sqlite::parse_file(path)?;
@@ -287,12 +523,30 @@ pub fn filter_report(
         && filters.host_mac.is_none()
         && filters.host_id.is_none()
         && filters.plugin_id.is_none()
+        && filters.older_than.is_none()
+        && filters.tag.is_none()
     {
         return;
     }
 
-    // Host filtering
-    let mut host_index_map: Vec<Option<i32>> = Vec::new();
+    let tagged: Option<HashSet<i32>> = filters.tag.as_ref().map(|tag| {
+        report
+            .host_properties
+            .iter()
+            .filter(|hp| hp.name.as_deref() == Some(crate::models::host_property::ASSET_TAG))
+            .filter(|hp| hp.value.as_deref().is_some_and(|v| v.eq_ignore_ascii_case(tag)))
+            .filter_map(|hp| hp.host_id)
+            .collect()
+    });
+
+    // Findings without an observation date fall back to their host's scan end.
+    let host_end: HashMap<i32, NaiveDateTime> =
+        report.hosts.iter().filter_map(|h| Some((h.id, h.end?))).collect();
+
+    // Host filtering. Kept hosts are renumbered by position; the map goes from
+    // the old host id, which need not be the old position once `sort_hosts`
+    // has run, to the new one.
+    let mut host_index_map: HashMap<i32, i32> = HashMap::new();
     let mut new_hosts = Vec::new();
     for host in report.hosts.drain(..) {
         let mut keep = true;
@@ -317,37 +571,49 @@ pub fn filter_report(
                 keep = false;
             }
         }
+        if tagged.as_ref().is_some_and(|ids| !ids.contains(&host.id)) {
+            keep = false;
+        }
         if keep {
-            host_index_map.push(Some(new_hosts.len() as i32));
+            host_index_map.insert(host.id, new_hosts.len() as i32);
             let mut h = host;
             h.id = new_hosts.len() as i32;
             new_hosts.push(h);
-        } else {
-            host_index_map.push(None);
         }
     }
     report.hosts = new_hosts;
 
-    // Item filtering
-    let mut item_index_map: Vec<Option<i32>> = Vec::new();
-    let mut new_items = Vec::new();
-    for mut item in report.items.drain(..) {
-        let pid = item.plugin_id.unwrap_or(0);
-        let mut keep =
-            (whitelist.is_empty() || whitelist.contains(&pid)) && !blacklist.contains(&pid);
-        if let Some(pid_f) = filters.plugin_id {
-            if item.plugin_id != Some(pid_f) {
-                keep = false;
+    // Item filtering. Items are checked on their own, so the checks run on
+    // all cores and only the re-indexing below is sequential.
+    let keep: Vec<bool> = report
+        .items
+        .par_iter()
+        .map(|item| {
+            let pid = item.plugin_id.unwrap_or(0);
+            if !(whitelist.is_empty() || whitelist.contains(&pid)) || blacklist.contains(&pid) {
+                return false;
             }
-        }
-        if let Some(hid) = item.host_id {
-            if let Some(Some(new_hid)) = host_index_map.get(hid as usize) {
-                item.host_id = Some(*new_hid);
-            } else {
-                keep = false;
+            if filters.plugin_id.is_some_and(|pid_f| item.plugin_id != Some(pid_f)) {
+                return false;
             }
-        }
+            if let Some(cutoff) = filters.older_than {
+                let seen = item.last_observed.or_else(|| {
+                    item.host_id.and_then(|hid| host_end.get(&hid).copied())
+                });
+                if seen.is_some_and(|seen| seen >= cutoff) {
+                    return false;
+                }
+            }
+            item.host_id.is_none_or(|hid| host_index_map.contains_key(&hid))
+        })
+        .collect();
+    let mut item_index_map: Vec<Option<i32>> = Vec::new();
+    let mut new_items = Vec::new();
+    for (mut item, keep) in report.items.drain(..).zip(keep) {
         if keep {
+            if let Some(hid) = item.host_id {
+                item.host_id = host_index_map.get(&hid).copied();
+            }
             item_index_map.push(Some(new_items.len() as i32));
             new_items.push(item);
         } else {
@@ -367,7 +633,7 @@ pub fn filter_report(
 
     report.service_descriptions.retain_mut(|sd| {
         if let Some(old_h) = sd.host_id {
-            if let Some(Some(new_h)) = host_index_map.get(old_h as usize) {
+            if let Some(new_h) = host_index_map.get(&old_h) {
                 sd.host_id = Some(*new_h);
             } else {
                 return false;
@@ -385,7 +651,7 @@ pub fn filter_report(
 
     report.host_properties.retain_mut(|hp| {
         if let Some(old) = hp.host_id {
-            if let Some(Some(new_idx)) = host_index_map.get(old as usize) {
+            if let Some(new_idx) = host_index_map.get(&old) {
                 hp.host_id = Some(*new_idx);
                 true
             } else {
@@ -398,7 +664,7 @@ pub fn filter_report(
 
     report.patches.retain_mut(|p| {
         if let Some(old) = p.host_id {
-            if let Some(Some(new_idx)) = host_index_map.get(old as usize) {
+            if let Some(new_idx) = host_index_map.get(&old) {
                 p.host_id = Some(*new_idx);
                 true
             } else {
@@ -452,7 +718,12 @@ pub fn apply_severity_overrides(report: &mut NessusReport, overrides: &HashMap<i
     for item in &mut report.items {
         if let Some(pid) = item.plugin_id {
             if let Some(&sev) = overrides.get(&pid) {
-                item.severity = Some(sev);
+                // Suppressed findings stay hidden with the new severity.
+                if item.suppressed == Some(true) {
+                    item.real_severity = Some(sev);
+                } else {
+                    item.severity = Some(sev);
+                }
             }
         }
     }
@@ -460,6 +731,20 @@ pub fn apply_severity_overrides(report: &mut NessusReport, overrides: &HashMap<i
 
 /// Validate and parse a Nessus-style XML file into ORM models.
 fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::error::Error> {
+    let mut report = NessusReport::default();
+    parse_nessus_into(path, scanner_name, &mut report, true)?;
+    Ok(report)
+}
+
+/// Parse a Nessus-style XML file, handing each completed `ReportHost` to
+/// `sink`. The base64 data of attachments is kept only with
+/// `keep_attachment_data`; the decoded files are written either way.
+fn parse_nessus_into(
+    path: &Path,
+    scanner_name: &str,
+    sink: &mut dyn ReportSink,
+    keep_attachment_data: bool,
+) -> Result<(), crate::error::Error> {
     info!("Parsing file: {}", path.display());
 
     let mut reader = Reader::from_file(path)?;
@@ -467,7 +752,9 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
 
     let mut buf = Vec::new();
 
+    // Holds the report header and plugins; hosts are moved to `sink` as they end.
     let mut report = NessusReport::default();
+    report.scanner.scanner_type = scanner_name.to_string();
     let mut current_host: Option<Host> = None;
     let mut current_host_index: Option<i32> = None;
     let mut current_tag: Option<String> = None;
@@ -479,6 +766,7 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
     let mut current_plugin_output: Option<String> = None;
     let mut current_item_index: Option<i32> = None;
     let mut current_item_tag: Option<String> = None;
+    let mut hosts_done = 0usize;
     let base_dir: PathBuf = path.parent().unwrap_or(Path::new(".")).to_path_buf();
 
     struct PendingAttachment {
@@ -516,15 +804,23 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
                             ));
                         }
                     }
+                    report.scanner.scanner_version = Some(report.version.clone());
                 }
                 b"Policy" => {
                     let (
-                        policy,
+                        mut policy,
                         mut policy_plugins,
                         mut family_selections,
                         mut plugin_preferences,
                         mut server_preferences,
                     ) = parse_policy(&mut reader, &mut buf)?;
+                    // Link the policy settings to the policy's index.
+                    let policy_idx = report.policies.len() as i32;
+                    policy.id = policy_idx;
+                    policy_plugins.iter_mut().for_each(|p| p.policy_id = Some(policy_idx));
+                    family_selections.iter_mut().for_each(|f| f.policy_id = Some(policy_idx));
+                    plugin_preferences.iter_mut().for_each(|p| p.policy_id = Some(policy_idx));
+                    server_preferences.iter_mut().for_each(|s| s.policy_id = Some(policy_idx));
                     report.policies.push(policy);
                     report.policy_plugins.append(&mut policy_plugins);
                     report.family_selections.append(&mut family_selections);
@@ -719,6 +1015,16 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
                 | b"cvss_vector"
                 | b"cvss_temporal_score"
                 | b"cvss_temporal_vector"
+                | b"cvss3_base_score"
+                | b"cvss3_vector"
+                | b"cvss3_temporal_score"
+                | b"cvss3_temporal_vector"
+                | b"cvss4_base_score"
+                | b"cvss4_vector"
+                | b"vpr_score"
+                | b"epss_score"
+                | b"exploit_code_maturity"
+                | b"threat_intensity_last_28"
                 | b"exploitability_ease"
                 | b"synopsis"
                 | b"exploit_framework_core"
@@ -1260,6 +1566,21 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
                                         }
                                     }
                                 }
+                                "cvss3_base_score"
+                                | "cvss3_vector"
+                                | "cvss3_temporal_score"
+                                | "cvss3_temporal_vector"
+                                | "cvss4_base_score"
+                                | "cvss4_vector"
+                                | "vpr_score"
+                                | "epss_score"
+                                | "exploit_code_maturity"
+                                | "threat_intensity_last_28" => {
+                                    let plugin = item.plugin_id.and_then(|pid| {
+                                        report.plugins.iter_mut().find(|p| p.plugin_id == Some(pid))
+                                    });
+                                    set_scoring_field(item, plugin, field, &text);
+                                }
                                 _ => {}
                             }
                         }
@@ -1359,7 +1680,10 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
                             report.service_descriptions.push(sd);
                         }
                         // Helpful when running with --log-level debug
-                        debug!("Completed host {} (total: {})", host_index, report.hosts.len());
+                        hosts_done += 1;
+                        debug!("Completed host {}", hosts_done);
+                        let chunk = take_host_chunk(&mut report);
+                        sink.host(&report, chunk)?;
                     }
                 }
                 b"attachment" => {
@@ -1378,7 +1702,7 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
                         attachment.path = Some(file_path.to_string_lossy().to_string());
                         attachment.size = Some(bytes.len() as i32);
                         attachment.ahash = Some(hash);
-                        attachment.value = Some(att.data);
+                        attachment.value = keep_attachment_data.then_some(att.data);
                         let id = report.attachments.len() as i32;
                         report.attachments.push(attachment);
                         if let Some(item) = report.items.last_mut() {
@@ -1455,7 +1779,22 @@ fn parse_nessus(path: &Path, scanner_name: &str) -> Result<NessusReport, crate::
     }
 
     report.set_scanner(scanner_name, Some(report.version.clone()));
-    Ok(report)
+    sink.finish(report)
+}
+
+/// Move the hosts parsed so far, and everything indexed by them, out of
+/// `report`. Plugins, policies and the header stay behind.
+fn take_host_chunk(report: &mut NessusReport) -> NessusReport {
+    NessusReport {
+        hosts: std::mem::take(&mut report.hosts),
+        items: std::mem::take(&mut report.items),
+        attachments: std::mem::take(&mut report.attachments),
+        host_properties: std::mem::take(&mut report.host_properties),
+        service_descriptions: std::mem::take(&mut report.service_descriptions),
+        references: std::mem::take(&mut report.references),
+        patches: std::mem::take(&mut report.patches),
+        ..NessusReport::default()
+    }
 }
 
 fn parse_policy<R: std::io::BufRead>(
@@ -1784,6 +2123,46 @@ fn empty_host() -> Host {
     }
 }
 
+/// Store a CVSS v3/v4, VPR or EPSS element on an item and, unless already
+/// set, on its plugin.
+fn set_scoring_field(item: &mut Item, plugin: Option<&mut Plugin>, field: &str, text: &str) {
+    macro_rules! set {
+        ($field:ident, $value:expr) => {{
+            let value = $value;
+            if let Some(plugin) = plugin
+                && plugin.$field.is_none()
+            {
+                plugin.$field = value.clone();
+            }
+            item.$field = value;
+        }};
+    }
+    let score = text.trim().parse::<f32>().ok();
+    let value = Some(text.to_string());
+    match field {
+        "cvss3_base_score" => set!(cvss3_base_score, score),
+        "cvss3_vector" => set!(cvss3_vector, value),
+        "cvss3_temporal_score" => set!(cvss3_temporal_score, score),
+        "cvss3_temporal_vector" => set!(cvss3_temporal_vector, value),
+        "cvss4_base_score" => set!(cvss4_base_score, score),
+        "cvss4_vector" => set!(cvss4_vector, value),
+        "vpr_score" => set!(vpr_score, score),
+        "epss_score" => set!(epss_score, score),
+        "exploit_code_maturity" => set!(exploit_code_maturity, value),
+        "threat_intensity_last_28" => set!(threat_intensity_last_28, value),
+        _ => {}
+    }
+}
+
+/// Derive a stable plugin ID for findings that have no numeric ID of their own
+/// (OpenVAS OIDs, Nmap scripts). The key is hashed into the range above 2^30,
+/// clear of real Nessus and Greenbone script IDs.
+fn synthetic_plugin_id(key: &str) -> i32 {
+    let digest = Sha256::digest(key.as_bytes());
+    let n = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    ((n & 0x3FFF_FFFF) | 0x4000_0000) as i32
+}
+
 fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
     if let Ok(dt) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
         return Some(dt);
@@ -1847,6 +2226,20 @@ fn empty_item() -> Item {
         engagement_id: None,
         rollup_finding: Some(false),
         scanner_id: None,
+        confidence: None,
+        first_discovered: None,
+        last_observed: None,
+        cvss3_base_score: None,
+        cvss3_vector: None,
+        cvss3_temporal_score: None,
+        cvss3_temporal_vector: None,
+        cvss4_base_score: None,
+        cvss4_vector: None,
+        vpr_score: None,
+        epss_score: None,
+        exploit_code_maturity: None,
+        threat_intensity_last_28: None,
+        suppressed: None,
     }
 }
 
@@ -1899,6 +2292,17 @@ fn empty_plugin() -> Plugin {
         engagement_id: None,
         policy_id: None,
         scanner_id: None,
+        oid: None,
+        cvss3_base_score: None,
+        cvss3_vector: None,
+        cvss3_temporal_score: None,
+        cvss3_temporal_vector: None,
+        cvss4_base_score: None,
+        cvss4_vector: None,
+        vpr_score: None,
+        epss_score: None,
+        exploit_code_maturity: None,
+        threat_intensity_last_28: None,
     }
 }
 
@@ -0,0 +1,371 @@
+//! Generic JSON findings format for custom tools.
+//!
+//! In-house checks can emit this document and have their results flow through
+//! post-processing, the database and every template like a scanner file. The
+//! layout is described in `docs/json_findings.markdown`:
+//!
+//! ```json
+//! {
+//!   "schema": "risu-findings/1",
+//!   "tool": { "name": "cloud-audit", "version": "2.3" },
+//!   "report": { "title": "AWS configuration audit" },
+//!   "hosts": [{ "id": "web", "ip": "10.0.0.5", "fqdn": "web.example.com" }],
+//!   "plugins": [{ "id": "s3-public-read", "name": "S3 bucket is public",
+//!                 "family": "Cloud", "references": [{ "source": "CWE", "value": "284" }] }],
+//!   "findings": [{ "host": "web", "plugin_id": "s3-public-read", "severity": "high",
+//!                  "port": 443, "protocol": "tcp", "output": "bucket: logs" }]
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::net::IpAddr;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::models::{Host, Item, Plugin, Reference, Report};
+
+use super::NessusReport;
+
+/// Value of the top-level `schema` marker understood by this parser.
+pub const SCHEMA: &str = "risu-findings/1";
+
+#[derive(Deserialize)]
+struct Document {
+    schema: Option<String>,
+    #[serde(default)]
+    tool: ToolEntry,
+    #[serde(default)]
+    report: ReportEntry,
+    #[serde(default)]
+    hosts: Vec<HostEntry>,
+    #[serde(default)]
+    plugins: Vec<PluginEntry>,
+    #[serde(default)]
+    findings: Vec<FindingEntry>,
+}
+
+#[derive(Deserialize, Default)]
+struct ToolEntry {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct ReportEntry {
+    title: Option<String>,
+    author: Option<String>,
+    company: Option<String>,
+    classification: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HostEntry {
+    /// Key that findings use to refer to this host. Findings may also use
+    /// the IP, FQDN or name.
+    id: Option<String>,
+    name: Option<String>,
+    ip: Option<String>,
+    fqdn: Option<String>,
+    netbios: Option<String>,
+    mac: Option<String>,
+    os: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PluginEntry {
+    id: PluginKey,
+    name: Option<String>,
+    family: Option<String>,
+    synopsis: Option<String>,
+    description: Option<String>,
+    solution: Option<String>,
+    risk_factor: Option<String>,
+    cvss_base_score: Option<f32>,
+    cvss_vector: Option<String>,
+    cvss3_base_score: Option<f32>,
+    cvss3_vector: Option<String>,
+    cvss4_base_score: Option<f32>,
+    cvss4_vector: Option<String>,
+    vpr_score: Option<f32>,
+    epss_score: Option<f32>,
+    publication_date: Option<String>,
+    #[serde(default)]
+    references: Vec<ReferenceEntry>,
+}
+
+#[derive(Deserialize)]
+struct FindingEntry {
+    host: String,
+    plugin_id: PluginKey,
+    plugin_name: Option<String>,
+    severity: Option<Severity>,
+    port: Option<i32>,
+    protocol: Option<String>,
+    service: Option<String>,
+    output: Option<String>,
+    first_discovered: Option<String>,
+    last_observed: Option<String>,
+    #[serde(default)]
+    references: Vec<ReferenceEntry>,
+}
+
+#[derive(Deserialize)]
+struct ReferenceEntry {
+    source: String,
+    value: String,
+}
+
+/// Plugin IDs may be numeric or a tool-specific string key, which is mapped
+/// onto a stable synthetic ID.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PluginKey {
+    Number(i32),
+    Text(String),
+}
+
+impl PluginKey {
+    fn id(&self) -> i32 {
+        match self {
+            PluginKey::Number(n) => *n,
+            PluginKey::Text(s) => s
+                .parse()
+                .unwrap_or_else(|_| super::synthetic_plugin_id(&format!("risu-json:{s}"))),
+        }
+    }
+}
+
+/// Severity as 0-4 or a name (`info`, `low`, `medium`, `high`, `critical`).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Severity {
+    Number(i32),
+    Text(String),
+}
+
+impl Severity {
+    fn level(&self) -> Result<i32, Error> {
+        match self {
+            Severity::Number(n @ 0..=4) => Ok(*n),
+            Severity::Text(s) => severity_from_name(s)
+                .ok_or_else(|| Error::InvalidDocument(format!("unknown severity '{s}'"))),
+            Severity::Number(n) => {
+                Err(Error::InvalidDocument(format!("severity {n} is outside 0-4")))
+            }
+        }
+    }
+}
+
+fn severity_from_name(name: &str) -> Option<i32> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "info" | "informational" | "none" => Some(0),
+        "low" => Some(1),
+        "medium" | "moderate" => Some(2),
+        "high" => Some(3),
+        "critical" => Some(4),
+        other => other.parse().ok().filter(|n| (0..=4).contains(n)),
+    }
+}
+
+/// Return `true` if the file starts with a JSON object, so it is not XML.
+pub fn looks_like_json(path: &Path) -> Result<bool, Error> {
+    let mut head = [0u8; 512];
+    let n = File::open(path)?.read(&mut head)?;
+    let text = String::from_utf8_lossy(&head[..n]);
+    Ok(text.trim_start_matches('\u{feff}').trim_start().starts_with('{'))
+}
+
+/// Parse a risu JSON findings document into a [`NessusReport`].
+pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
+    let doc: Document = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    match doc.schema.as_deref() {
+        Some(SCHEMA) => {}
+        Some(other) => {
+            return Err(Error::InvalidDocument(format!(
+                "{}: unsupported findings schema '{other}' (expected '{SCHEMA}')",
+                path.display()
+            )));
+        }
+        None => {
+            return Err(Error::InvalidDocument(format!(
+                "{}: missing \"schema\": \"{SCHEMA}\" marker",
+                path.display()
+            )));
+        }
+    }
+    build_report(doc)
+}
+
+fn build_report(doc: Document) -> Result<NessusReport, Error> {
+    let mut report = NessusReport {
+        version: SCHEMA.to_string(),
+        report: Report {
+            title: doc.report.title,
+            author: doc.report.author,
+            company: doc.report.company,
+            classification: doc.report.classification,
+            ..Report::default()
+        },
+        ..NessusReport::default()
+    };
+
+    // Findings may refer to a host by its key, IP, FQDN or name.
+    let mut host_keys: HashMap<String, usize> = HashMap::new();
+    // Hosts without a declared scan window span the observations of their
+    // findings.
+    let mut has_window: Vec<bool> = Vec::new();
+    for entry in doc.hosts {
+        let idx = report.hosts.len();
+        for key in [&entry.id, &entry.ip, &entry.fqdn, &entry.name].into_iter().flatten() {
+            host_keys.entry(key.clone()).or_insert(idx);
+        }
+        let host = Host {
+            id: idx as i32,
+            name: entry.name.or_else(|| entry.fqdn.clone()).or_else(|| entry.ip.clone()),
+            ip: entry.ip,
+            fqdn: entry.fqdn,
+            netbios: entry.netbios,
+            mac: entry.mac,
+            os: entry.os,
+            start: entry.start.as_deref().and_then(parse_json_datetime),
+            end: entry.end.as_deref().and_then(parse_json_datetime),
+            ..Host::default()
+        };
+        has_window.push(host.start.is_some() || host.end.is_some());
+        report.hosts.push(host);
+    }
+
+    let mut plugin_refs: HashMap<i32, Vec<ReferenceEntry>> = HashMap::new();
+    let mut plugin_index: HashMap<i32, usize> = HashMap::new();
+    for entry in doc.plugins {
+        let plugin_id = entry.id.id();
+        plugin_index.insert(plugin_id, report.plugins.len());
+        plugin_refs.insert(plugin_id, entry.references);
+        report.plugins.push(Plugin {
+            plugin_id: Some(plugin_id),
+            plugin_name: entry.name,
+            family_name: entry.family,
+            synopsis: entry.synopsis,
+            description: entry.description,
+            solution: entry.solution,
+            risk_factor: entry.risk_factor,
+            cvss_base_score: entry.cvss_base_score,
+            cvss_vector: entry.cvss_vector,
+            cvss3_base_score: entry.cvss3_base_score,
+            cvss3_vector: entry.cvss3_vector,
+            cvss4_base_score: entry.cvss4_base_score,
+            cvss4_vector: entry.cvss4_vector,
+            vpr_score: entry.vpr_score,
+            epss_score: entry.epss_score,
+            plugin_publication_date: entry
+                .publication_date
+                .as_deref()
+                .and_then(parse_json_datetime),
+            ..Plugin::default()
+        });
+    }
+
+    for finding in doc.findings {
+        let host_idx = *host_keys.entry(finding.host.clone()).or_insert_with(|| {
+            let ip = finding.host.parse::<IpAddr>().is_ok().then(|| finding.host.clone());
+            has_window.push(false);
+            report.hosts.push(Host {
+                id: report.hosts.len() as i32,
+                name: Some(finding.host.clone()),
+                ip,
+                ..Host::default()
+            });
+            report.hosts.len() - 1
+        });
+
+        let plugin_id = finding.plugin_id.id();
+        let plugin_idx = *plugin_index.entry(plugin_id).or_insert_with(|| {
+            report.plugins.push(Plugin {
+                plugin_id: Some(plugin_id),
+                plugin_name: finding.plugin_name.clone(),
+                ..Plugin::default()
+            });
+            report.plugins.len() - 1
+        });
+        let plugin = &report.plugins[plugin_idx];
+
+        let severity = match &finding.severity {
+            Some(s) => s.level()?,
+            None => plugin
+                .risk_factor
+                .as_deref()
+                .and_then(severity_from_name)
+                .unwrap_or(0),
+        };
+        let first_discovered = finding.first_discovered.as_deref().and_then(parse_json_datetime);
+        let last_observed = finding.last_observed.as_deref().and_then(parse_json_datetime);
+
+        let item_id = report.items.len() as i32;
+        report.items.push(Item {
+            id: item_id,
+            host_id: Some(host_idx as i32),
+            plugin_id: Some(plugin_id),
+            plugin_name: finding.plugin_name.or_else(|| plugin.plugin_name.clone()),
+            plugin_output: finding.output,
+            port: finding.port,
+            protocol: finding.protocol.map(|p| p.to_ascii_lowercase()),
+            svc_name: finding.service,
+            severity: Some(severity),
+            risk_factor: plugin.risk_factor.clone(),
+            synopsis: plugin.synopsis.clone(),
+            description: plugin.description.clone(),
+            solution: plugin.solution.clone(),
+            cvss_base_score: plugin.cvss_base_score,
+            cvss_vector: plugin.cvss_vector.clone(),
+            cvss3_base_score: plugin.cvss3_base_score,
+            cvss3_vector: plugin.cvss3_vector.clone(),
+            cvss4_base_score: plugin.cvss4_base_score,
+            cvss4_vector: plugin.cvss4_vector.clone(),
+            vpr_score: plugin.vpr_score,
+            epss_score: plugin.epss_score,
+            first_discovered,
+            last_observed,
+            ..Item::default()
+        });
+
+        if let Some(seen) = last_observed.or(first_discovered)
+            && !has_window[host_idx]
+        {
+            let host = &mut report.hosts[host_idx];
+            host.start = Some(host.start.map_or(seen, |s| s.min(seen)));
+            host.end = Some(host.end.map_or(seen, |e| e.max(seen)));
+        }
+
+        let shared = plugin_refs.get(&plugin_id).into_iter().flatten();
+        for r in shared.chain(finding.references.iter()) {
+            report.references.push(Reference {
+                plugin_id: Some(plugin_id),
+                item_id: Some(item_id),
+                source: Some(r.source.clone()),
+                value: Some(r.value.clone()),
+                ..Reference::default()
+            });
+        }
+    }
+
+    let tool = doc.tool.name.unwrap_or_else(|| "Custom".to_string());
+    report.set_scanner(&tool, doc.tool.version);
+    Ok(report)
+}
+
+/// Dates are RFC 3339 timestamps or the `YYYY-MM-DD[ HH:MM:SS]` forms
+/// accepted elsewhere.
+fn parse_json_datetime(text: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|dt| dt.naive_utc())
+        .ok()
+        .or_else(|| super::parse_datetime(text))
+}
+
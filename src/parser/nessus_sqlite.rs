@@ -1,36 +1,86 @@
 use std::path::Path;
 
 use diesel::prelude::*;
+use diesel::sql_types::Text;
 use diesel::sqlite::SqliteConnection;
+use diesel_migrations::MigrationHarness;
 
+use crate::db::DbConnection;
 use crate::error::Error;
-use crate::models::{Attachment, Host, Item, Plugin};
+use crate::migrate::MIGRATIONS;
 
 use super::NessusReport;
 
+#[derive(QueryableByName)]
+struct Name {
+    #[diesel(sql_type = Text)]
+    name: String,
+}
+
 /// Parse a Nessus SQLite export into a [`NessusReport`].
+///
+/// The export is read through [`crate::loader::load_all`], so references,
+/// host properties, services, patches and policies are included and DB IDs
+/// are remapped to vector indices exactly as for a report loaded from the
+/// Risu database.
+///
+/// The rows are first copied into a fully migrated in-memory database, so
+/// exports written before later migrations load with the missing tables and
+/// columns left empty. The export file itself is never modified.
 pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
     let db_path = path
         .to_str()
         .ok_or_else(|| Error::InvalidDocument("invalid path".to_string()))?;
-    let mut conn = SqliteConnection::establish(db_path)?;
+    let mut conn = SqliteConnection::establish(":memory:")?;
+    conn.run_pending_migrations(MIGRATIONS).map_err(Error::Migration)?;
+    diesel::sql_query("ATTACH DATABASE ? AS export")
+        .bind::<Text, _>(db_path)
+        .execute(&mut conn)?;
+    copy_export(&mut conn)?;
+    diesel::sql_query("DETACH DATABASE export").execute(&mut conn)?;
+    let mut conn = DbConnection::from(conn);
 
-    use crate::schema::nessus_attachments::dsl as attachments_dsl;
-    use crate::schema::nessus_hosts::dsl as hosts_dsl;
-    use crate::schema::nessus_items::dsl as items_dsl;
-    use crate::schema::nessus_plugins::dsl as plugins_dsl;
+    let mut report = crate::loader::load_all(&mut conn)?;
+    report.version = "sqlite".to_string();
+    Ok(report)
+}
 
-    let hosts = hosts_dsl::nessus_hosts.load::<Host>(&mut conn)?;
-    let items = items_dsl::nessus_items.load::<Item>(&mut conn)?;
-    let plugins = plugins_dsl::nessus_plugins.load::<Plugin>(&mut conn)?;
-    let attachments = attachments_dsl::nessus_attachments.load::<Attachment>(&mut conn)?;
+fn quote(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
 
-    let mut report = NessusReport::default();
-    report.version = "sqlite".to_string();
-    report.hosts = hosts;
-    report.items = items;
-    report.plugins = plugins;
-    report.attachments = attachments;
+fn columns(conn: &mut SqliteConnection, schema: &str, table: &str) -> Result<Vec<String>, Error> {
+    let cols = diesel::sql_query("SELECT name FROM pragma_table_info(?, ?)")
+        .bind::<Text, _>(table)
+        .bind::<Text, _>(schema)
+        .load::<Name>(conn)?;
+    Ok(cols.into_iter().map(|c| c.name).collect())
+}
 
-    Ok(report)
+/// Copy every table of the attached `export` database into the matching
+/// table of `main`, using only the columns both sides have.
+fn copy_export(conn: &mut SqliteConnection) -> Result<(), Error> {
+    let tables = diesel::sql_query(
+        "SELECT name FROM export.sqlite_master WHERE type = 'table' \
+         AND name NOT LIKE 'sqlite_%' AND name != '__diesel_schema_migrations'",
+    )
+    .load::<Name>(conn)?;
+    for table in tables {
+        let ours = columns(conn, "main", &table.name)?;
+        let shared: Vec<String> = columns(conn, "export", &table.name)?
+            .into_iter()
+            .filter(|c| ours.contains(c))
+            .map(|c| quote(&c))
+            .collect();
+        if shared.is_empty() {
+            continue;
+        }
+        let cols = shared.join(", ");
+        let name = quote(&table.name);
+        diesel::sql_query(format!(
+            "INSERT INTO main.{name} ({cols}) SELECT {cols} FROM export.{name}"
+        ))
+        .execute(conn)?;
+    }
+    Ok(())
 }
@@ -1,55 +1,160 @@
+use std::collections::{BTreeSet, HashSet};
 use std::path::Path;
 
+use chrono::DateTime;
+use lazy_static::lazy_static;
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use regex::Regex;
 
 use crate::error::Error;
-use crate::models::{Host, Item, Plugin};
+use crate::models::{Host, HostProperty, Item, Plugin, Reference, ServiceDescription};
 
 use super::NessusReport;
 
-/// Parse an Nmap XML report into a [`NessusReport`]. The mapping is lossy but
-/// provides basic host and open port information so templates and
-/// post-processing can operate on the data.
+/// Plugin ID shared by all open-port items.
+const OPEN_PORT_PLUGIN_ID: i32 = 0;
+
+#[derive(Default)]
+struct PendingPort {
+    port: Option<i32>,
+    protocol: Option<String>,
+    state: Option<String>,
+    service: Option<String>,
+    banner: Vec<String>,
+    cpes: Vec<String>,
+    scripts: Vec<(String, String)>,
+}
+
+lazy_static! {
+    static ref CVE_RE: Regex = Regex::new(r"CVE-\d{4}-\d{4,}").unwrap();
+}
+
+/// Parse an Nmap XML report into a [`NessusReport`].
+///
+/// Every open port becomes an "Open Port" item with a matching
+/// [`ServiceDescription`] built from the service product, version and extra
+/// info; service CPEs are kept as `CPE` references. Each NSE script becomes
+/// its own informational plugin (keyed on the script ID) with the script
+/// output as plugin output. Hostnames, the best OS match and the MAC address
+/// are copied onto the host. Hosts reported as down are skipped.
 pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
     let mut reader = Reader::from_file(path)?;
     reader.trim_text(true);
+    reader.expand_empty_elements(true);
     let mut buf = Vec::new();
 
-    let mut report = NessusReport::default();
-    report.version = "nmap".to_string();
-
-    struct PendingPort {
-        port: Option<i32>,
-        protocol: Option<String>,
-        service: Option<String>,
-        state: Option<String>,
-    }
+    let mut report = NessusReport {
+        version: "nmap".to_string(),
+        ..NessusReport::default()
+    };
+    let mut nmap_version: Option<String> = None;
 
     let mut current_host: Option<Host> = None;
+    let mut host_up = true;
+    let mut fqdn_is_user = false;
     let mut current_port: Option<PendingPort> = None;
-    let mut plugin_added = false;
+    let mut in_service_cpe = false;
+    let mut seen_plugins = HashSet::new();
 
     loop {
         match reader.read_event_into(&mut buf)? {
             Event::Start(e) => match e.name().as_ref() {
-                b"host" => current_host = Some(empty_host()),
+                b"nmaprun" => {
+                    for a in e.attributes().flatten() {
+                        if a.key.as_ref() == b"version" {
+                            nmap_version = Some(a.unescape_value()?.to_string());
+                        }
+                    }
+                }
+                b"host" => {
+                    let mut host = empty_host();
+                    host.id = report.hosts.len() as i32;
+                    for a in e.attributes().flatten() {
+                        let ts = a.unescape_value()?.parse::<i64>().ok();
+                        let dt = ts
+                            .and_then(|t| DateTime::from_timestamp(t, 0))
+                            .map(|d| d.naive_utc());
+                        match a.key.as_ref() {
+                            b"starttime" => host.start = dt,
+                            b"endtime" => host.end = dt,
+                            _ => {}
+                        }
+                    }
+                    current_host = Some(host);
+                    host_up = true;
+                    fqdn_is_user = false;
+                }
+                b"status" => {
+                    for a in e.attributes().flatten() {
+                        if a.key.as_ref() == b"state" {
+                            host_up = a.unescape_value()?.as_ref() != "down";
+                        }
+                    }
+                }
                 b"address" => {
                     if let Some(ref mut host) = current_host {
+                        let mut addr = None;
+                        let mut addrtype = None;
+                        let mut vendor = None;
+                        for a in e.attributes().flatten() {
+                            let v = a.unescape_value()?.to_string();
+                            match a.key.as_ref() {
+                                b"addr" => addr = Some(v),
+                                b"addrtype" => addrtype = Some(v),
+                                b"vendor" => vendor = Some(v),
+                                _ => {}
+                            }
+                        }
+                        if addrtype.as_deref() == Some("mac") {
+                            host.mac = addr;
+                            if let Some(vendor) = vendor {
+                                report.host_properties.push(HostProperty {
+                                    host_id: Some(host.id),
+                                    name: Some("mac-vendor".to_string()),
+                                    value: Some(vendor),
+                                    ..HostProperty::default()
+                                });
+                            }
+                        } else if host.ip.is_none() {
+                            host.name = addr.clone();
+                            host.ip = addr;
+                        }
+                    }
+                }
+                b"hostname" => {
+                    if let Some(ref mut host) = current_host {
+                        let mut name = None;
+                        let mut kind = None;
                         for a in e.attributes().flatten() {
-                            if a.key.as_ref() == b"addr" {
-                                host.ip = Some(a.unescape_value()?.to_string());
+                            match a.key.as_ref() {
+                                b"name" => name = Some(a.unescape_value()?.to_string()),
+                                b"type" => kind = Some(a.unescape_value()?.to_string()),
+                                _ => {}
+                            }
+                        }
+                        // Names given on the command line win over PTR records.
+                        let is_user = kind.as_deref() == Some("user");
+                        if name.is_some() && (host.fqdn.is_none() || (is_user && !fqdn_is_user)) {
+                            host.fqdn = name;
+                            fqdn_is_user = is_user;
+                        }
+                    }
+                }
+                b"osmatch" => {
+                    // Nmap lists OS matches by decreasing accuracy.
+                    if let Some(ref mut host) = current_host
+                        && host.os.is_none()
+                    {
+                        for a in e.attributes().flatten() {
+                            if a.key.as_ref() == b"name" {
+                                host.os = Some(a.unescape_value()?.to_string());
                             }
                         }
                     }
                 }
                 b"port" => {
-                    let mut p = PendingPort {
-                        port: None,
-                        protocol: None,
-                        service: None,
-                        state: None,
-                    };
+                    let mut p = PendingPort::default();
                     for a in e.attributes().flatten() {
                         match a.key.as_ref() {
                             b"portid" => {
@@ -73,37 +178,73 @@ pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
                 b"service" => {
                     if let Some(ref mut p) = current_port {
                         for a in e.attributes().flatten() {
-                            if a.key.as_ref() == b"name" {
-                                p.service = Some(a.unescape_value()?.to_string());
+                            let v = a.unescape_value()?.to_string();
+                            match a.key.as_ref() {
+                                b"name" => p.service = Some(v),
+                                b"product" | b"version" => p.banner.push(v),
+                                b"extrainfo" => p.banner.push(format!("({v})")),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                b"cpe" => in_service_cpe = current_port.is_some(),
+                b"script" => {
+                    let mut id = None;
+                    let mut output = String::new();
+                    for a in e.attributes().flatten() {
+                        match a.key.as_ref() {
+                            b"id" => id = Some(a.unescape_value()?.to_string()),
+                            b"output" => output = a.unescape_value()?.to_string(),
+                            _ => {}
+                        }
+                    }
+                    if let Some(id) = id {
+                        match current_port {
+                            Some(ref mut p) => p.scripts.push((id, output)),
+                            None if host_up => {
+                                if let Some(ref host) = current_host {
+                                    push_script(
+                                        &mut report,
+                                        &mut seen_plugins,
+                                        host.id,
+                                        None,
+                                        &id,
+                                        output,
+                                    );
+                                }
                             }
+                            None => {}
                         }
                     }
                 }
                 _ => {}
             },
+            Event::Text(e) => {
+                if in_service_cpe && let Some(ref mut p) = current_port {
+                    p.cpes.push(e.unescape()?.to_string());
+                }
+            }
             Event::End(e) => match e.name().as_ref() {
+                b"cpe" => in_service_cpe = false,
                 b"port" => {
-                    if let Some(p) = current_port.take() {
-                        if p.state.as_deref() == Some("open") {
-                            if !plugin_added {
-                                let mut plugin = Plugin::default();
-                                plugin.plugin_id = Some(0);
-                                plugin.plugin_name = Some("Open Port".to_string());
-                                report.plugins.push(plugin);
-                                plugin_added = true;
-                            }
-                            let mut item = Item::default();
-                            item.plugin_id = Some(0);
-                            item.port = p.port;
-                            item.protocol = p.protocol;
-                            item.svc_name = p.service;
-                            report.items.push(item);
-                        }
+                    let host_id = current_host.as_ref().map(|h| h.id);
+                    if let (Some(p), Some(host_id)) = (current_port.take(), host_id)
+                        && host_up
+                        && p.state.as_deref() == Some("open")
+                    {
+                        push_open_port(&mut report, &mut seen_plugins, host_id, p);
                     }
                 }
                 b"host" => {
                     if let Some(host) = current_host.take() {
-                        report.hosts.push(host);
+                        if host_up {
+                            report.hosts.push(host);
+                        } else {
+                            report
+                                .host_properties
+                                .retain(|hp| hp.host_id != Some(host.id));
+                        }
                     }
                 }
                 _ => {}
@@ -114,10 +255,125 @@ pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
         buf.clear();
     }
 
-    report.set_scanner("Nmap", None);
+    report.set_scanner("Nmap", nmap_version);
     Ok(report)
 }
 
+/// Add the open-port item, service description and CPE references for a
+/// port, followed by items for the port's NSE scripts.
+fn push_open_port(
+    report: &mut NessusReport,
+    seen_plugins: &mut HashSet<i32>,
+    host_id: i32,
+    p: PendingPort,
+) {
+    if seen_plugins.insert(OPEN_PORT_PLUGIN_ID) {
+        report.plugins.push(Plugin {
+            plugin_id: Some(OPEN_PORT_PLUGIN_ID),
+            plugin_name: Some("Open Port".to_string()),
+            family_name: Some("Port scanners".to_string()),
+            risk_factor: Some("None".to_string()),
+            ..Plugin::default()
+        });
+    }
+    let banner = (!p.banner.is_empty()).then(|| p.banner.join(" "));
+    let item_id = report.items.len() as i32;
+    report.items.push(Item {
+        id: item_id,
+        host_id: Some(host_id),
+        plugin_id: Some(OPEN_PORT_PLUGIN_ID),
+        plugin_name: Some("Open Port".to_string()),
+        port: p.port,
+        protocol: p.protocol.clone(),
+        svc_name: p.service.clone(),
+        plugin_output: banner.clone(),
+        severity: Some(0),
+        risk_factor: Some("None".to_string()),
+        ..Item::default()
+    });
+    report.service_descriptions.push(ServiceDescription {
+        host_id: Some(host_id),
+        item_id: Some(item_id),
+        port: p.port,
+        svc_name: p.service.clone(),
+        protocol: p.protocol.clone(),
+        description: banner,
+        ..ServiceDescription::default()
+    });
+    for cpe in p.cpes {
+        report.references.push(Reference {
+            plugin_id: Some(OPEN_PORT_PLUGIN_ID),
+            item_id: Some(item_id),
+            source: Some("CPE".to_string()),
+            value: Some(cpe),
+            ..Reference::default()
+        });
+    }
+    for (id, output) in p.scripts {
+        let item = push_script(
+            report,
+            seen_plugins,
+            host_id,
+            Some((p.port, p.protocol.clone())),
+            &id,
+            output,
+        );
+        report.items[item].svc_name = p.service.clone();
+    }
+}
+
+/// Add an informational item for an NSE script result, creating the
+/// script's synthetic plugin on first use. Returns the new item's index.
+fn push_script(
+    report: &mut NessusReport,
+    seen_plugins: &mut HashSet<i32>,
+    host_id: i32,
+    port: Option<(Option<i32>, Option<String>)>,
+    script_id: &str,
+    output: String,
+) -> usize {
+    let plugin_id = super::synthetic_plugin_id(&format!("nmap-script:{script_id}"));
+    let name = format!("Nmap NSE: {script_id}");
+    if seen_plugins.insert(plugin_id) {
+        report.plugins.push(Plugin {
+            plugin_id: Some(plugin_id),
+            plugin_name: Some(name.clone()),
+            family_name: Some("Nmap NSE".to_string()),
+            fname: Some(format!("{script_id}.nse")),
+            risk_factor: Some("None".to_string()),
+            ..Plugin::default()
+        });
+    }
+    let (port, protocol) = port.unwrap_or((Some(0), None));
+    let item_id = report.items.len() as i32;
+    let cves: BTreeSet<String> = CVE_RE
+        .find_iter(&output)
+        .map(|m| m.as_str().to_string())
+        .collect();
+    report.items.push(Item {
+        id: item_id,
+        host_id: Some(host_id),
+        plugin_id: Some(plugin_id),
+        plugin_name: Some(name),
+        port,
+        protocol,
+        plugin_output: Some(output),
+        severity: Some(0),
+        risk_factor: Some("None".to_string()),
+        ..Item::default()
+    });
+    for cve in cves {
+        report.references.push(Reference {
+            plugin_id: Some(plugin_id),
+            item_id: Some(item_id),
+            source: Some("CVE".to_string()),
+            value: Some(cve),
+            ..Reference::default()
+        });
+    }
+    report.items.len() - 1
+}
+
 fn empty_host() -> Host {
     Host {
         id: 0,
@@ -1,11 +1,429 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use chrono::NaiveDateTime;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
 use crate::error::Error;
+use crate::models::{Host, HostProperty, Item, Plugin, Reference};
 
 use super::NessusReport;
 
-/// Parse an OpenVAS report. OpenVAS exports are generally compatible with the
-/// Nessus XML schema, so we simply reuse the existing Nessus parser.
+/// OID prefix of Greenbone's legacy NVTs, whose last arc is the script ID.
+const GREENBONE_NVT_PREFIX: &str = "1.3.6.1.4.1.25623.1.0.";
+
+#[derive(Default)]
+struct PendingNvt {
+    oid: String,
+    name: Option<String>,
+    family: Option<String>,
+    cvss_base: Option<f32>,
+    cvss_vector: Option<String>,
+    tags: HashMap<String, String>,
+    solution: Option<String>,
+    refs: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct PendingResult {
+    name: Option<String>,
+    host: Option<String>,
+    hostname: Option<String>,
+    port: Option<String>,
+    threat: Option<String>,
+    severity: Option<f32>,
+    qod: Option<i32>,
+    description: Option<String>,
+    nvt: PendingNvt,
+}
+
+/// Parse an OpenVAS/GVM XML report into a [`NessusReport`].
+///
+/// Each `<result>` becomes an item. NVT OIDs are mapped to stable plugin IDs
+/// with [`plugin_id_for_oid`] and the OID itself is kept on the plugin. The
+/// quality of detection (QoD) is stored as the item's `confidence`. Results
+/// with a negative severity (false positives and errors) are skipped.
 pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
-    super::parse_nessus(path, "OpenVAS")
+    let mut reader = Reader::from_file(path)?;
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut report = NessusReport {
+        version: "openvas".to_string(),
+        ..NessusReport::default()
+    };
+
+    // Element names and accumulated text of the open elements.
+    let mut stack: Vec<(String, String)> = Vec::new();
+    let mut current_result: Option<PendingResult> = None;
+    let mut current_host: Option<usize> = None;
+    let mut detail: (Option<String>, Option<String>) = (None, None);
+    let mut host_index: HashMap<String, usize> = HashMap::new();
+    let mut seen_plugins = HashSet::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                let parent = stack.last().map(|(n, _)| n.as_str());
+                match (name.as_str(), parent) {
+                    ("result", Some("results")) => {
+                        current_result = Some(PendingResult::default());
+                    }
+                    ("nvt", Some("result")) => {
+                        if let Some(ref mut r) = current_result {
+                            r.nvt.oid = attr(&e, b"oid")?.unwrap_or_default();
+                        }
+                    }
+                    ("host", Some("report")) => current_host = None,
+                    ("detail", Some("host")) => detail = (None, None),
+                    _ => {}
+                }
+                stack.push((name, String::new()));
+            }
+            Event::Empty(e) => {
+                if e.name().as_ref() == b"ref"
+                    && let Some(ref mut r) = current_result
+                {
+                    let kind = attr(&e, b"type")?.unwrap_or_default();
+                    if let Some(id) = attr(&e, b"id")? {
+                        r.nvt.refs.push((kind.to_uppercase(), id));
+                    }
+                }
+            }
+            Event::Text(e) => {
+                if let Some((_, text)) = stack.last_mut() {
+                    text.push_str(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some((_, text)) = stack.last_mut() {
+                    text.push_str(&String::from_utf8_lossy(&e.into_inner()));
+                }
+            }
+            Event::End(_) => {
+                let (name, text) = stack.pop().unwrap_or_default();
+                let value = text.trim().to_string();
+                let parent = stack.last().map(|(n, _)| n.as_str());
+                let grandparent = stack
+                    .len()
+                    .checked_sub(2)
+                    .and_then(|i| stack.get(i))
+                    .map(|(n, _)| n.as_str());
+                match (name.as_str(), parent) {
+                    ("name", Some("task")) => report.report.title = Some(value),
+                    ("result", Some("results")) => {
+                        if let Some(r) = current_result.take() {
+                            push_result(&mut report, &mut host_index, &mut seen_plugins, r);
+                        }
+                    }
+                    ("ip", Some("host")) if grandparent == Some("report") => {
+                        let idx = host_for(&mut report, &mut host_index, &value);
+                        current_host = Some(idx);
+                    }
+                    ("start", Some("host")) | ("end", Some("host")) => {
+                        if let Some(host) = current_host.and_then(|i| report.hosts.get_mut(i)) {
+                            let dt = parse_gvm_datetime(&value);
+                            if name == "start" {
+                                host.start = dt;
+                            } else {
+                                host.end = dt;
+                            }
+                        }
+                    }
+                    ("name", Some("detail")) => detail.0 = Some(value),
+                    ("value", Some("detail")) => detail.1 = Some(value),
+                    ("detail", Some("host")) => {
+                        if let (Some(idx), (Some(key), Some(val))) = (current_host, detail.clone())
+                        {
+                            let host = &mut report.hosts[idx];
+                            match key.as_str() {
+                                "hostname" => host.fqdn = Some(val.clone()),
+                                "best_os_txt" => host.os = Some(val.clone()),
+                                "MAC" | "mac" => host.mac = Some(val.clone()),
+                                _ => {}
+                            }
+                            report.host_properties.push(HostProperty {
+                                host_id: Some(idx as i32),
+                                name: Some(key),
+                                value: Some(val),
+                                ..HostProperty::default()
+                            });
+                        }
+                    }
+                    (field, Some("result")) => {
+                        if let Some(ref mut r) = current_result {
+                            match field {
+                                "name" => r.name = Some(value),
+                                "host" => r.host = Some(value),
+                                "port" => r.port = Some(value),
+                                "threat" => r.threat = Some(value),
+                                "severity" => r.severity = value.parse().ok(),
+                                "description" => r.description = Some(value),
+                                _ => {}
+                            }
+                        }
+                    }
+                    ("hostname", Some("host")) if grandparent == Some("result") => {
+                        if let Some(ref mut r) = current_result {
+                            r.hostname = Some(value);
+                        }
+                    }
+                    ("value", Some("qod")) => {
+                        if let Some(ref mut r) = current_result {
+                            r.qod = value.parse().ok();
+                        }
+                    }
+                    ("score", Some("severity")) | ("value", Some("severity")) => {
+                        if let Some(ref mut r) = current_result {
+                            if name == "score" {
+                                r.nvt.cvss_base = r.nvt.cvss_base.or(value.parse().ok());
+                            } else if r.nvt.cvss_vector.is_none() && !value.is_empty() {
+                                r.nvt.cvss_vector = Some(value);
+                            }
+                        }
+                    }
+                    (field, Some("nvt")) => {
+                        if let Some(ref mut r) = current_result {
+                            let nvt = &mut r.nvt;
+                            match field {
+                                "name" => nvt.name = Some(value),
+                                "family" => nvt.family = Some(value),
+                                "cvss_base" => nvt.cvss_base = value.parse().ok(),
+                                "solution" => nvt.solution = Some(value),
+                                "tags" => nvt.tags = parse_tags(&value),
+                                // OpenVAS 8/9 list references inline.
+                                "cve" | "bid" | "xref" => {
+                                    for v in value.split(',').map(str::trim) {
+                                        if v.is_empty() || v.starts_with("NO") {
+                                            continue;
+                                        }
+                                        let (kind, v) = match (field, v.split_once(':')) {
+                                            ("xref", Some((k, rest))) => {
+                                                (k.trim().to_uppercase(), rest.trim())
+                                            }
+                                            _ => (field.to_uppercase(), v),
+                                        };
+                                        nvt.refs.push((kind, v.to_string()));
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    report.set_scanner("OpenVAS", None);
+    Ok(report)
+}
+
+/// Map an NVT OID to a stable, positive plugin ID.
+///
+/// Legacy Greenbone NVTs (`1.3.6.1.4.1.25623.1.0.N`) use `N`, which matches
+/// the script ID shown in the Greenbone feed. Any other OID is hashed into
+/// the range above 2^30 so it cannot collide with those IDs.
+pub fn plugin_id_for_oid(oid: &str) -> i32 {
+    if let Some(id) = oid
+        .strip_prefix(GREENBONE_NVT_PREFIX)
+        .and_then(|rest| rest.parse::<i32>().ok())
+        .filter(|id| *id < 1 << 30)
+    {
+        return id;
+    }
+    super::synthetic_plugin_id(oid)
+}
+
+fn push_result(
+    report: &mut NessusReport,
+    host_index: &mut HashMap<String, usize>,
+    seen_plugins: &mut HashSet<i32>,
+    r: PendingResult,
+) {
+    if r.severity.is_some_and(|s| s < 0.0) {
+        return;
+    }
+    let Some(ip) = r.host.as_deref().filter(|h| !h.is_empty()) else {
+        return;
+    };
+    let host_idx = host_for(report, host_index, ip);
+    if let Some(hostname) = r.hostname.filter(|h| !h.is_empty()) {
+        report.hosts[host_idx].fqdn.get_or_insert(hostname);
+    }
+
+    let nvt = r.nvt;
+    let plugin_id = plugin_id_for_oid(&nvt.oid);
+    let severity = match r.severity {
+        Some(score) => severity_from_score(score),
+        None => severity_from_threat(r.threat.as_deref().unwrap_or("")),
+    };
+    let name = nvt.name.clone().or(r.name).unwrap_or_else(|| nvt.oid.clone());
+    let summary = nvt.tags.get("summary").cloned();
+    let insight = nvt.tags.get("insight").cloned().or_else(|| summary.clone());
+    let solution = nvt.solution.clone().or_else(|| nvt.tags.get("solution").cloned());
+    let cvss_vector = nvt
+        .tags
+        .get("cvss_base_vector")
+        .cloned()
+        .or(nvt.cvss_vector.clone());
+    let (port, svc_name, protocol) = split_port(r.port.as_deref().unwrap_or(""));
+
+    if seen_plugins.insert(plugin_id) {
+        report.plugins.push(Plugin {
+            plugin_id: Some(plugin_id),
+            plugin_name: Some(name.clone()),
+            family_name: nvt.family.clone(),
+            description: insight.clone(),
+            synopsis: summary.clone(),
+            solution: solution.clone(),
+            cvss_base_score: nvt.cvss_base,
+            cvss_vector: cvss_vector.clone(),
+            risk_factor: Some(risk_factor(severity).to_string()),
+            oid: Some(nvt.oid.clone()),
+            ..Plugin::default()
+        });
+    }
+
+    let item_id = report.items.len() as i32;
+    report.items.push(Item {
+        id: item_id,
+        host_id: Some(host_idx as i32),
+        plugin_id: Some(plugin_id),
+        plugin_output: r.description,
+        port,
+        svc_name,
+        protocol,
+        severity: Some(severity),
+        plugin_name: Some(name),
+        description: insight,
+        synopsis: summary,
+        solution,
+        risk_factor: Some(risk_factor(severity).to_string()),
+        cvss_base_score: nvt.cvss_base,
+        cvss_vector,
+        confidence: r.qod,
+        ..Item::default()
+    });
+
+    for (source, value) in nvt.refs {
+        report.references.push(Reference {
+            plugin_id: Some(plugin_id),
+            item_id: Some(item_id),
+            source: Some(source),
+            value: Some(value),
+            ..Reference::default()
+        });
+    }
+}
+
+fn host_for(report: &mut NessusReport, host_index: &mut HashMap<String, usize>, ip: &str) -> usize {
+    *host_index.entry(ip.to_string()).or_insert_with(|| {
+        report.hosts.push(Host {
+            id: report.hosts.len() as i32,
+            ip: Some(ip.to_string()),
+            name: Some(ip.to_string()),
+            ..Host::default()
+        });
+        report.hosts.len() - 1
+    })
+}
+
+/// Split a GVM port such as `443/tcp` or `general/icmp` into port number,
+/// service name and protocol.
+fn split_port(port: &str) -> (Option<i32>, Option<String>, Option<String>) {
+    let (left, proto) = match port.split_once('/') {
+        Some((l, p)) => (l, Some(p.to_lowercase())),
+        None => (port, None),
+    };
+    match left.parse::<i32>() {
+        Ok(n) => (Some(n), None, proto),
+        Err(_) if left.is_empty() => (None, None, proto),
+        Err(_) => (Some(0), Some(left.to_string()), proto),
+    }
+}
+
+/// Parse the `key=value|key=value` tag string attached to an NVT.
+fn parse_tags(tags: &str) -> HashMap<String, String> {
+    tags.split('|')
+        .filter_map(|t| t.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect()
+}
+
+/// Map a CVSS-style severity score onto the Nessus 0-4 scale.
+fn severity_from_score(score: f32) -> i32 {
+    match score {
+        s if s >= 9.0 => 4,
+        s if s >= 7.0 => 3,
+        s if s >= 4.0 => 2,
+        s if s > 0.0 => 1,
+        _ => 0,
+    }
+}
+
+fn severity_from_threat(threat: &str) -> i32 {
+    match threat.to_ascii_lowercase().as_str() {
+        "critical" => 4,
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+fn risk_factor(severity: i32) -> &'static str {
+    match severity {
+        4 => "Critical",
+        3 => "High",
+        2 => "Medium",
+        1 => "Low",
+        _ => "None",
+    }
+}
+
+fn parse_gvm_datetime(text: &str) -> Option<NaiveDateTime> {
+    chrono::DateTime::parse_from_rfc3339(text)
+        .map(|dt| dt.naive_utc())
+        .ok()
+        .or_else(|| super::parse_datetime(text))
+}
+
+fn attr(e: &BytesStart, key: &[u8]) -> Result<Option<String>, Error> {
+    for a in e.attributes().flatten() {
+        if a.key.as_ref() == key {
+            return Ok(Some(a.unescape_value()?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_oids_keep_script_id() {
+        assert_eq!(plugin_id_for_oid("1.3.6.1.4.1.25623.1.0.105611"), 105611);
+        let other = plugin_id_for_oid("1.3.6.1.4.1.25623.1.1.2.2023.1234");
+        assert!(other >= 1 << 30);
+        assert_eq!(other, plugin_id_for_oid("1.3.6.1.4.1.25623.1.1.2.2023.1234"));
+    }
+
+    #[test]
+    fn splits_gvm_ports() {
+        assert_eq!(split_port("443/tcp"), (Some(443), None, Some("tcp".into())));
+        assert_eq!(
+            split_port("general/icmp"),
+            (Some(0), Some("general".into()), Some("icmp".into()))
+        );
+        assert_eq!(split_port(""), (None, None, None));
+    }
 }
@@ -1,11 +1,397 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use chrono::NaiveDateTime;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
 use crate::error::Error;
+use crate::models::{Host, Item, Plugin, Reference};
 
 use super::NessusReport;
 
-/// Parse a Qualys export. Qualys can produce Nessus compatible XML, so we
-/// delegate to the shared Nessus parser.
+/// Qualys finding classes. Information gathered is reported with severity 0.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Vuln,
+    Potential,
+    Info,
+}
+
+impl Kind {
+    fn from_type(text: &str) -> Self {
+        match text.to_ascii_lowercase().as_str() {
+            "potential" | "practice" => Kind::Potential,
+            "info" | "ig" => Kind::Info,
+            _ => Kind::Vuln,
+        }
+    }
+}
+
+/// A detection of a QID on a host.
+struct Finding {
+    host: i32,
+    qid: Option<i32>,
+    kind: Option<Kind>,
+    severity: Option<i32>,
+    port: Option<i32>,
+    protocol: Option<String>,
+    output: Option<String>,
+}
+
+/// Knowledge base data for a QID, from a scan `VULN` or a glossary entry.
+#[derive(Default)]
+struct Details {
+    qid: Option<i32>,
+    kind: Option<Kind>,
+    title: Option<String>,
+    severity: Option<i32>,
+    category: Option<String>,
+    diagnosis: Option<String>,
+    consequence: Option<String>,
+    solution: Option<String>,
+    cvss_base: Option<f32>,
+    cvss_temporal: Option<String>,
+    cves: Vec<String>,
+    bids: Vec<String>,
+}
+
+/// Parse a Qualys XML export into a [`NessusReport`].
+///
+/// Scan reports (`SCAN`), asset data reports (`ASSET_DATA_REPORT`) and the
+/// VM host detection API output (`HOST_LIST_VM_DETECTION_OUTPUT`) are
+/// supported. Each QID becomes a plugin with `plugin_id` set to the QID and
+/// Qualys severities 1-5 are mapped onto the Nessus 0-4 scale.
 pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
-    super::parse_nessus(path, "Qualys")
+    let mut reader = Reader::from_file(path)?;
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut report = NessusReport {
+        version: "qualys".to_string(),
+        ..NessusReport::default()
+    };
+
+    let mut stack: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut header_key: Option<String> = None;
+    let mut scan_date: Option<NaiveDateTime> = None;
+    let mut category: Option<(Option<String>, Option<i32>, Option<String>)> = None;
+
+    let mut current_host: Option<Host> = None;
+    let mut current_finding: Option<Finding> = None;
+    let mut current_details: Option<Details> = None;
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut details: HashMap<i32, Details> = HashMap::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                let parent = stack.last().map(String::as_str);
+                match (name.as_str(), parent) {
+                    ("IP", Some("SCAN")) => {
+                        let ip = attr(&e, b"value")?;
+                        current_host = Some(Host {
+                            id: report.hosts.len() as i32,
+                            name: ip.clone(),
+                            ip,
+                            fqdn: attr(&e, b"name")?
+                                .filter(|n| n != "No registered hostname"),
+                            ..Host::default()
+                        });
+                    }
+                    ("HOST", Some("HOST_LIST")) => {
+                        current_host = Some(Host {
+                            id: report.hosts.len() as i32,
+                            ..Host::default()
+                        });
+                    }
+                    ("KEY", Some("HEADER")) => header_key = attr(&e, b"value")?,
+                    ("CAT", _) => {
+                        let port = attr(&e, b"port")?.and_then(|p| p.parse().ok());
+                        category = Some((attr(&e, b"value")?, port, attr(&e, b"protocol")?));
+                    }
+                    ("VULN", Some("CAT"))
+                    | ("PRACTICE", Some("CAT"))
+                    | ("INFO", Some("CAT"))
+                    | ("SERVICE", Some("CAT")) => {
+                        let kind = match name.as_str() {
+                            "VULN" => Kind::Vuln,
+                            "PRACTICE" => Kind::Potential,
+                            _ => Kind::Info,
+                        };
+                        let qid = attr(&e, b"number")?.and_then(|n| n.parse().ok());
+                        let severity = attr(&e, b"severity")?.and_then(|s| s.parse().ok());
+                        let (family, port, protocol) = category.clone().unwrap_or_default();
+                        current_finding = Some(Finding {
+                            host: current_host.as_ref().map_or(0, |h| h.id),
+                            qid,
+                            kind: Some(kind),
+                            severity,
+                            port,
+                            protocol,
+                            output: None,
+                        });
+                        current_details = Some(Details {
+                            qid,
+                            kind: Some(kind),
+                            severity,
+                            category: family,
+                            ..Details::default()
+                        });
+                    }
+                    ("VULN_INFO", _) | ("DETECTION", _) => {
+                        current_finding = Some(Finding {
+                            host: current_host.as_ref().map_or(0, |h| h.id),
+                            qid: None,
+                            kind: None,
+                            severity: None,
+                            port: None,
+                            protocol: None,
+                            output: None,
+                        });
+                    }
+                    ("VULN_DETAILS", _) => current_details = Some(Details::default()),
+                    _ => {}
+                }
+                stack.push(name);
+                text.clear();
+            }
+            Event::Text(e) => text.push_str(&e.unescape()?),
+            Event::CData(e) => text.push_str(&String::from_utf8_lossy(&e.into_inner())),
+            Event::End(e) => {
+                stack.pop();
+                let parent = stack.last().map(String::as_str);
+                let value = text.trim().to_string();
+                match (e.name().as_ref(), parent) {
+                    (b"IP", Some("SCAN")) | (b"HOST", Some("HOST_LIST")) => {
+                        if let Some(host) = current_host.take() {
+                            report.hosts.push(host);
+                        }
+                    }
+                    (b"KEY", Some("HEADER")) => {
+                        match header_key.take().as_deref() {
+                            Some("TITLE") => report.report.title = Some(value.clone()),
+                            Some("COMPANY") => report.report.company = Some(value.clone()),
+                            Some("DATE") => scan_date = parse_qualys_datetime(&value),
+                            _ => {}
+                        }
+                    }
+                    (b"CAT", _) => category = None,
+                    (b"VULN", Some("CAT"))
+                    | (b"PRACTICE", Some("CAT"))
+                    | (b"INFO", Some("CAT"))
+                    | (b"SERVICE", Some("CAT")) => {
+                        if let Some(f) = current_finding.take() {
+                            findings.push(f);
+                        }
+                        if let Some(d) = current_details.take()
+                            && let Some(qid) = d.qid
+                        {
+                            details.entry(qid).or_insert(d);
+                        }
+                    }
+                    (b"VULN_INFO", _) | (b"DETECTION", _) => {
+                        if let Some(f) = current_finding.take() {
+                            findings.push(f);
+                        }
+                    }
+                    (b"VULN_DETAILS", _) => {
+                        if let Some(d) = current_details.take()
+                            && let Some(qid) = d.qid
+                        {
+                            details.insert(qid, d);
+                        }
+                    }
+                    (b"ID", Some("CVE_ID")) => {
+                        if let Some(ref mut d) = current_details {
+                            d.cves.push(value.clone());
+                        }
+                    }
+                    (b"ID", Some("BUGTRAQ_ID")) => {
+                        if let Some(ref mut d) = current_details {
+                            d.bids.push(value.clone());
+                        }
+                    }
+                    (name, _) if current_finding.is_some() && current_details.is_none() => {
+                        let f = current_finding.as_mut().unwrap();
+                        match name {
+                            b"QID" => f.qid = value.parse().ok(),
+                            b"TYPE" => f.kind = Some(Kind::from_type(&value)),
+                            b"SEVERITY" => f.severity = value.parse().ok(),
+                            b"PORT" => f.port = value.parse().ok(),
+                            b"PROTOCOL" => f.protocol = Some(value.clone()),
+                            b"RESULTS" | b"RESULT" => f.output = Some(value.clone()),
+                            b"FIRST_FOUND_DATETIME" | b"FIRST_FOUND" => {
+                                if let (Some(host), Some(dt)) =
+                                    (current_host.as_mut(), parse_qualys_datetime(&value))
+                                {
+                                    host.start = Some(host.start.map_or(dt, |s| s.min(dt)));
+                                }
+                            }
+                            b"LAST_FOUND_DATETIME" | b"LAST_FOUND" => {
+                                if let (Some(host), Some(dt)) =
+                                    (current_host.as_mut(), parse_qualys_datetime(&value))
+                                {
+                                    host.end = Some(host.end.map_or(dt, |s| s.max(dt)));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    (name, _) if current_details.is_some() => {
+                        let d = current_details.as_mut().unwrap();
+                        match name {
+                            b"QID" => d.qid = value.parse().ok(),
+                            b"TITLE" => d.title = Some(value.clone()),
+                            b"SEVERITY" => d.severity = value.parse().ok(),
+                            b"CATEGORY" => d.category = Some(value.clone()),
+                            b"TYPE" => d.kind = Some(Kind::from_type(&value)),
+                            b"DIAGNOSIS" | b"THREAT" => d.diagnosis = Some(value.clone()),
+                            b"CONSEQUENCE" | b"IMPACT" => d.consequence = Some(value.clone()),
+                            b"SOLUTION" => d.solution = Some(value.clone()),
+                            b"CVSS_BASE" => d.cvss_base = value.parse().ok(),
+                            b"CVSS_TEMPORAL" => d.cvss_temporal = Some(value.clone()),
+                            b"RESULT" => {
+                                if let Some(ref mut f) = current_finding {
+                                    f.output = Some(value.clone());
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    (name, _) => {
+                        if let Some(ref mut host) = current_host {
+                            match name {
+                                b"IP" => {
+                                    host.ip = Some(value.clone());
+                                    host.name = Some(value.clone());
+                                }
+                                b"DNS" => host.fqdn = Some(value.clone()),
+                                b"NETBIOS" | b"NETBIOS_HOSTNAME" => {
+                                    host.netbios = Some(value.clone())
+                                }
+                                b"OS" | b"OPERATING_SYSTEM" => host.os = Some(value.clone()),
+                                b"LAST_SCAN_DATETIME" => {
+                                    host.end = parse_qualys_datetime(&value).or(host.end)
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if let Some(date) = scan_date {
+        for host in &mut report.hosts {
+            host.start.get_or_insert(date);
+            host.end.get_or_insert(date);
+        }
+    }
+
+    let mut seen_plugins = HashSet::new();
+    for f in findings {
+        let Some(qid) = f.qid else { continue };
+        let d = details.get(&qid);
+        let kind = f.kind.or(d.and_then(|d| d.kind)).unwrap_or(Kind::Vuln);
+        let severity = match kind {
+            Kind::Info => 0,
+            _ => (f.severity.or(d.and_then(|d| d.severity)).unwrap_or(1) - 1).clamp(0, 4),
+        };
+        let name = d
+            .and_then(|d| d.title.clone())
+            .unwrap_or_else(|| format!("QID {qid}"));
+
+        if seen_plugins.insert(qid) {
+            let mut plugin = Plugin {
+                plugin_id: Some(qid),
+                plugin_name: Some(name.clone()),
+                risk_factor: Some(risk_factor(severity).to_string()),
+                potential_vulnerability: Some(kind == Kind::Potential),
+                ..Plugin::default()
+            };
+            if let Some(d) = d {
+                plugin.family_name = d.category.clone();
+                plugin.description = d.diagnosis.clone();
+                plugin.synopsis = d.consequence.clone();
+                plugin.solution = d.solution.clone();
+                plugin.cvss_base_score = d.cvss_base;
+                plugin.cvss_temporal_score = d.cvss_temporal.clone();
+            }
+            report.plugins.push(plugin);
+        }
+
+        let mut item = Item {
+            id: report.items.len() as i32,
+            host_id: Some(f.host),
+            plugin_id: Some(qid),
+            plugin_name: Some(name),
+            port: f.port,
+            protocol: f.protocol,
+            severity: Some(severity),
+            risk_factor: Some(risk_factor(severity).to_string()),
+            plugin_output: f.output,
+            ..Item::default()
+        };
+        if let Some(d) = d {
+            item.description = d.diagnosis.clone();
+            item.synopsis = d.consequence.clone();
+            item.solution = d.solution.clone();
+            item.cvss_base_score = d.cvss_base;
+            item.cvss_temporal_score = d.cvss_temporal.clone();
+            let refs = d
+                .cves
+                .iter()
+                .map(|v| ("CVE", v))
+                .chain(d.bids.iter().map(|v| ("BID", v)));
+            for (source, value) in refs {
+                report.references.push(Reference {
+                    item_id: Some(item.id),
+                    plugin_id: Some(qid),
+                    source: Some(source.to_string()),
+                    value: Some(value.clone()),
+                    ..Reference::default()
+                });
+            }
+        }
+        report.items.push(item);
+    }
+
+    report.set_scanner("Qualys", None);
+    Ok(report)
+}
+
+fn attr(e: &BytesStart, key: &[u8]) -> Result<Option<String>, Error> {
+    for a in e.attributes().flatten() {
+        if a.key.as_ref() == key {
+            return Ok(Some(a.unescape_value()?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn risk_factor(severity: i32) -> &'static str {
+    match severity {
+        4 => "Critical",
+        3 => "High",
+        2 => "Medium",
+        1 => "Low",
+        _ => "None",
+    }
 }
+
+/// Qualys uses ISO 8601 timestamps (`2024-01-31T10:00:00Z`) in API output and
+/// the Nessus-style formats elsewhere.
+fn parse_qualys_datetime(text: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%SZ")
+        .ok()
+        .or_else(|| super::parse_datetime(text))
+}
+
@@ -1,11 +1,259 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use chrono::NaiveDateTime;
+use csv::{ReaderBuilder, StringRecord};
+
 use crate::error::Error;
+use crate::models::{Host, Item, Plugin, Reference};
 
 use super::NessusReport;
 
-/// Parse a Tenable SecurityCenter export. SecurityCenter uses the Nessus XML
-/// format so the standard Nessus parser can handle these reports.
+/// Columns that identify a Tenable.sc vulnerability CSV export.
+const REQUIRED_COLUMNS: &[&str] = &["Plugin", "Plugin Name", "IP Address"];
+
+/// Parse a Tenable.sc (SecurityCenter) export. The vulnerability CSV export
+/// is recognised by its header row; anything else is treated as the Nessus
+/// XML format that Tenable.sc also produces.
 pub fn parse_file(path: &Path) -> Result<NessusReport, Error> {
-    super::parse_nessus(path, "SecurityCenter")
+    if is_security_center_csv(path)? {
+        parse_csv(path)
+    } else {
+        super::parse_nessus(path, "SecurityCenter")
+    }
+}
+
+/// Return `true` if the file starts with a Tenable.sc vulnerability CSV header.
+pub fn is_security_center_csv(path: &Path) -> Result<bool, Error> {
+    let mut rdr = ReaderBuilder::new().flexible(true).from_path(path)?;
+    let headers = match rdr.headers() {
+        Ok(h) => h,
+        // Binary or XML content is simply not a CSV export.
+        Err(_) => return Ok(false),
+    };
+    Ok(REQUIRED_COLUMNS
+        .iter()
+        .all(|c| headers.iter().any(|h| h.trim() == *c)))
+}
+
+/// Parse a Tenable.sc vulnerability CSV export into a [`NessusReport`].
+///
+/// Columns are located by name, so exports with extra or reordered columns
+/// are accepted. `First Discovered` and `Last Observed` are kept on each item
+/// and the host scan window spans the item observations.
+pub fn parse_csv(path: &Path) -> Result<NessusReport, Error> {
+    let mut rdr = ReaderBuilder::new().flexible(true).from_path(path)?;
+    let columns: HashMap<String, usize> = rdr
+        .headers()?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (h.trim().to_string(), i))
+        .collect();
+
+    let mut report = NessusReport {
+        version: "securitycenter-csv".to_string(),
+        ..NessusReport::default()
+    };
+    let mut host_map: HashMap<String, usize> = HashMap::new();
+    let mut seen_plugins = HashSet::new();
+
+    for result in rdr.records() {
+        let record = result?;
+        let row = Row {
+            record: &record,
+            columns: &columns,
+        };
+        let Some(plugin_id) = row.get("Plugin").and_then(|p| p.parse::<i32>().ok()) else {
+            continue;
+        };
+        let Some(ip) = row.get("IP Address") else {
+            continue;
+        };
+
+        let host_idx = *host_map.entry(ip.to_string()).or_insert_with(|| {
+            report.hosts.push(Host {
+                id: report.hosts.len() as i32,
+                ip: Some(ip.to_string()),
+                name: Some(ip.to_string()),
+                ..Host::default()
+            });
+            report.hosts.len() - 1
+        });
+        let first_discovered = row.get("First Discovered").and_then(parse_sc_datetime);
+        let last_observed = row.get("Last Observed").and_then(parse_sc_datetime);
+        {
+            let host = &mut report.hosts[host_idx];
+            if host.fqdn.is_none() {
+                host.fqdn = row.string("DNS Name");
+            }
+            if host.netbios.is_none() {
+                host.netbios = row.string("NetBIOS Name");
+            }
+            if host.mac.is_none() {
+                host.mac = row.string("MAC Address");
+            }
+            // The host was seen from its earliest discovery to its latest
+            // observation.
+            if let Some(first) = first_discovered.or(last_observed) {
+                host.start = Some(host.start.map_or(first, |s| s.min(first)));
+            }
+            if let Some(seen) = last_observed {
+                host.end = Some(host.end.map_or(seen, |e| e.max(seen)));
+            }
+        }
+
+        let severity = row.get("Severity").map(severity_from_text);
+        let risk_factor = row.string("Risk Factor").or_else(|| row.string("Severity"));
+        let cvss_base_score = row.get("CVSS V2 Base Score").and_then(|s| s.parse().ok());
+
+        if seen_plugins.insert(plugin_id) {
+            report.plugins.push(Plugin {
+                plugin_id: Some(plugin_id),
+                plugin_name: row.string("Plugin Name"),
+                family_name: row.string("Family"),
+                synopsis: row.string("Synopsis"),
+                description: row.string("Description"),
+                solution: row.string("Solution"),
+                risk_factor: risk_factor.clone(),
+                cvss_base_score,
+                cvss_vector: row.string("CVSS V2 Vector"),
+                stig_severity: row.string("STIG Severity"),
+                exploitability_ease: row.string("Exploit Ease"),
+                exploit_available: row.string("Exploit?"),
+                plugin_type: row.string("Check Type"),
+                plugin_publication_date: row
+                    .get("Plugin Publication Date")
+                    .and_then(parse_sc_datetime),
+                plugin_modification_date: row
+                    .get("Plugin Modification Date")
+                    .and_then(parse_sc_datetime),
+                vuln_publication_date: row
+                    .get("Vuln Publication Date")
+                    .and_then(parse_sc_datetime),
+                ..Plugin::default()
+            });
+        }
+
+        let item_id = report.items.len() as i32;
+        report.items.push(Item {
+            id: item_id,
+            host_id: Some(host_idx as i32),
+            plugin_id: Some(plugin_id),
+            plugin_name: row.string("Plugin Name"),
+            plugin_output: row.string("Plugin Output").or_else(|| row.string("Plugin Text")),
+            port: row.get("Port").and_then(|p| p.parse().ok()),
+            protocol: row.get("Protocol").map(str::to_lowercase),
+            severity,
+            risk_factor,
+            synopsis: row.string("Synopsis"),
+            description: row.string("Description"),
+            solution: row.string("Solution"),
+            cvss_base_score,
+            first_discovered,
+            last_observed,
+            ..Item::default()
+        });
+
+        for (source, value) in row.references() {
+            report.references.push(Reference {
+                plugin_id: Some(plugin_id),
+                item_id: Some(item_id),
+                source: Some(source),
+                value: Some(value),
+                ..Reference::default()
+            });
+        }
+    }
+
+    report.set_scanner("SecurityCenter", None);
+    Ok(report)
+}
+
+/// A CSV record with column lookup by header name.
+struct Row<'a> {
+    record: &'a StringRecord,
+    columns: &'a HashMap<String, usize>,
+}
+
+impl<'a> Row<'a> {
+    /// Trimmed, non-empty value of `column`.
+    fn get(&self, column: &str) -> Option<&'a str> {
+        self.columns
+            .get(column)
+            .and_then(|i| self.record.get(*i))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+
+    fn string(&self, column: &str) -> Option<String> {
+        self.get(column).map(str::to_string)
+    }
+
+    /// CVE, BID and cross references (`IAVA #2020-A-0001`) of the row.
+    fn references(&self) -> Vec<(String, String)> {
+        let mut refs = Vec::new();
+        for (column, source) in [("CVE", "CVE"), ("BID", "BID")] {
+            if let Some(values) = self.get(column) {
+                refs.extend(
+                    values
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(|v| (source.to_string(), v.to_string())),
+                );
+            }
+        }
+        if let Some(xrefs) = self.get("Cross References") {
+            for xref in xrefs.split(',').map(str::trim) {
+                if let Some((source, value)) = xref.split_once('#') {
+                    refs.push((source.trim().to_uppercase(), value.trim().to_string()));
+                }
+            }
+        }
+        if let Some(see_also) = self.get("See Also") {
+            refs.extend(
+                see_also
+                    .split_whitespace()
+                    .map(|v| ("SEE_ALSO".to_string(), v.to_string())),
+            );
+        }
+        refs
+    }
+}
+
+/// Map a Tenable.sc severity (`Critical` .. `Info`, or a number) onto 0-4.
+fn severity_from_text(text: &str) -> i32 {
+    match text.to_ascii_lowercase().as_str() {
+        "critical" => 4,
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        other => other.parse().unwrap_or(0),
+    }
+}
+
+/// Tenable.sc writes dates as `Jan 5, 2024 10:22:33 UTC`; the trailing time
+/// zone abbreviation is ignored.
+fn parse_sc_datetime(text: &str) -> Option<NaiveDateTime> {
+    let without_zone = match text.rsplit_once(' ') {
+        Some((rest, zone)) if zone.chars().all(|c| c.is_ascii_alphabetic()) => rest,
+        _ => text,
+    };
+    NaiveDateTime::parse_from_str(without_zone, "%b %d, %Y %H:%M:%S")
+        .ok()
+        .or_else(|| super::parse_datetime(text))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sc_dates() {
+        let dt = parse_sc_datetime("Jan 5, 2024 10:22:33 UTC").unwrap();
+        assert_eq!(dt.to_string(), "2024-01-05 10:22:33");
+        assert!(parse_sc_datetime("2024-01-05").is_some());
+        assert!(parse_sc_datetime("N/A").is_none());
+    }
 }
@@ -86,6 +86,10 @@ impl From<SimpleNexpose> for super::NessusReport {
             server_preferences: Vec::new(),
             scanner: Scanner::default(),
             filters: super::Filters::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
         };
         r.set_scanner("Nexpose", None);
         r
@@ -1,17 +1,35 @@
+use std::collections::{HashMap, HashSet};
+
 use diesel::prelude::*;
-use diesel::sql_types::BigInt;
 
+use crate::db::{self, DbConnection};
 use crate::error::Error;
-use crate::models::{
-    Attachment, FamilySelection, Host, HostProperty, Item, Patch, Plugin, PluginPreference,
-    Policy, PolicyPlugin, Reference, Report, Scanner, ServerPreference, ServiceDescription,
-};
+use crate::models::Plugin;
+use crate::parser::NessusReport;
 use crate::schema;
 
-#[derive(QueryableByName)]
-struct LastId {
-    #[diesel(sql_type = BigInt)]
-    id: i64,
+/// Rows per multi-row `INSERT`, keeping the bound variables of the widest
+/// table well under SQLite's per-statement limit.
+const INSERT_CHUNK: usize = 100;
+
+/// Insert `rows` into `table` with one multi-row statement per chunk.
+///
+/// `MultiConnection` cannot build batch inserts itself (SQLite has no
+/// `DEFAULT` keyword), so each chunk runs on the concrete connection.
+macro_rules! batch_insert {
+    ($conn:expr, $table:expr, $rows:expr) => {{
+        let mut inserted = 0;
+        for chunk in $rows.chunks(INSERT_CHUNK) {
+            inserted += match &mut *$conn {
+                DbConnection::Sqlite(c) => diesel::insert_into($table).values(chunk).execute(c),
+                #[cfg(feature = "postgres")]
+                DbConnection::Postgres(c) => diesel::insert_into($table).values(chunk).execute(c),
+                #[cfg(feature = "mysql")]
+                DbConnection::Mysql(c) => diesel::insert_into($table).values(chunk).execute(c),
+            }?;
+        }
+        Ok::<usize, diesel::result::Error>(inserted)
+    }};
 }
 
 #[derive(Insertable)]
@@ -100,6 +118,17 @@ struct NewPlugin<'a> {
     engagement_id: Option<i32>,
     policy_id: Option<i32>,
     scanner_id: Option<i32>,
+    oid: Option<&'a str>,
+    cvss3_base_score: Option<f32>,
+    cvss3_vector: Option<&'a str>,
+    cvss3_temporal_score: Option<f32>,
+    cvss3_temporal_vector: Option<&'a str>,
+    cvss4_base_score: Option<f32>,
+    cvss4_vector: Option<&'a str>,
+    vpr_score: Option<f32>,
+    epss_score: Option<f32>,
+    exploit_code_maturity: Option<&'a str>,
+    threat_intensity_last_28: Option<&'a str>,
 }
 
 #[derive(Insertable)]
@@ -161,6 +190,20 @@ struct NewItem<'a> {
     engagement_id: Option<i32>,
     rollup_finding: Option<bool>,
     scanner_id: Option<i32>,
+    confidence: Option<i32>,
+    first_discovered: Option<chrono::NaiveDateTime>,
+    last_observed: Option<chrono::NaiveDateTime>,
+    cvss3_base_score: Option<f32>,
+    cvss3_vector: Option<&'a str>,
+    cvss3_temporal_score: Option<f32>,
+    cvss3_temporal_vector: Option<&'a str>,
+    cvss4_base_score: Option<f32>,
+    cvss4_vector: Option<&'a str>,
+    vpr_score: Option<f32>,
+    epss_score: Option<f32>,
+    exploit_code_maturity: Option<&'a str>,
+    threat_intensity_last_28: Option<&'a str>,
+    suppressed: Option<bool>,
 }
 
 #[derive(Insertable)]
@@ -255,475 +298,646 @@ struct NewServerPreference<'a> {
     value: Option<&'a str>,
 }
 
-pub fn to_sqlite(conn: &mut SqliteConnection, report: &crate::parser::NessusReport) -> Result<(), Error> {
+/// Database IDs shared by every batch written for one report.
+struct StoredReport {
+    report_id: i32,
+    scanner_id: i32,
+    /// External plugin ID -> `nessus_plugins.id`.
+    plugin_ids: HashMap<i32, i32>,
+}
+
+/// Persist a parsed report and all of its related rows inside a single
+/// transaction. Returns the database ID of the new `nessus_reports` row.
+pub fn to_database(conn: &mut DbConnection, report: &NessusReport) -> Result<i32, Error> {
     conn.transaction(|conn| {
-        use schema::*;
-        use schema::scanners::dsl as scn;
-        use schema::nessus_reports::dsl as rep;
-        use schema::nessus_hosts::dsl as hosts;
-        use schema::nessus_plugins::dsl as plugins;
-        use schema::nessus_attachments::dsl as attach;
-        use schema::nessus_items::dsl as items;
-        use schema::nessus_host_properties::dsl as hprops;
-        use schema::nessus_service_descriptions::dsl as sdesc;
-        use schema::nessus_references::dsl as refs;
-        use schema::nessus_patches::dsl as patches;
-        use schema::nessus_policies::dsl as pols;
-        use schema::nessus_policy_plugins::dsl as polplugs;
-        use schema::nessus_family_selections::dsl as famsel;
-        use schema::nessus_plugin_preferences::dsl as plgprefs;
-        use schema::nessus_server_preferences::dsl as srvprefs;
-
-        // 1) Scanner upsert (by type + version)
-        let scanner_type = report.scanner.scanner_type.as_str();
-        let scanner_version = report.scanner.scanner_version.as_deref();
-        let scanner_id: i32 = if let Some(ver) = scanner_version {
-            scn::scanners
-                .filter(scn::scanner_type.eq(scanner_type))
-                .filter(scn::scanner_version.eq(ver))
-                .select(scn::id)
-                .first::<i32>(conn)
-                .optional()?
-                .unwrap_or_else(|| {
-                    diesel::insert_into(scn::scanners)
-                        .values(NewScanner { scanner_type, scanner_version })
-                        .execute(conn)
-                        .expect("insert scanner");
-                    diesel::sql_query("SELECT last_insert_rowid() AS id")
-                        .get_result::<LastId>(conn)
-                        .expect("last id").id as i32
-                })
-        } else {
-            scn::scanners
-                .filter(scn::scanner_type.eq(scanner_type))
-                .filter(scn::scanner_version.is_null())
-                .select(scn::id)
-                .first::<i32>(conn)
-                .optional()?
-                .unwrap_or_else(|| {
-                    diesel::insert_into(scn::scanners)
-                        .values(NewScanner { scanner_type, scanner_version })
-                        .execute(conn)
-                        .expect("insert scanner");
-                    diesel::sql_query("SELECT last_insert_rowid() AS id")
-                        .get_result::<LastId>(conn)
-                        .expect("last id").id as i32
-                })
-        };
+        let mut stored = insert_report(conn, report)?;
+        let plugins: Vec<&Plugin> = report.plugins.iter().collect();
+        insert_plugins(conn, &mut stored, &plugins)?;
+        insert_hosts(conn, &stored, report)?;
+        insert_policies(conn, stored.report_id, report)?;
+        Ok(stored.report_id)
+    })
+}
 
-        // 2) Report row
-        diesel::insert_into(rep::nessus_reports)
-            .values(NewReport {
-                title: report.report.title.as_deref(),
-                author: report.report.author.as_deref(),
-                company: report.report.company.as_deref(),
-                classification: report.report.classification.as_deref(),
-                user_id: None,
-                engagement_id: None,
-            })
-            .execute(conn)?;
-        let report_id = diesel::sql_query("SELECT last_insert_rowid() AS id")
-            .get_result::<LastId>(conn)?
-            .id as i32;
+/// Find or create the scanner row and create the `nessus_reports` row.
+fn insert_report(conn: &mut DbConnection, report: &NessusReport) -> Result<StoredReport, Error> {
+    use schema::nessus_reports::dsl as rep;
+    use schema::scanners::dsl as scn;
 
-        // 3) Hosts (batch insert, then fetch ids in order)
-        let new_hosts: Vec<NewHost> = report
-            .hosts
-            .iter()
-            .map(|h| NewHost {
-                nessus_report_id: Some(report_id),
-                name: h.name.as_deref(),
-                os: h.os.as_deref(),
-                mac: h.mac.as_deref(),
-                start: h.start,
-                end: h.end,
-                ip: h.ip.as_deref(),
-                fqdn: h.fqdn.as_deref(),
-                netbios: h.netbios.as_deref(),
-                notes: h.notes.as_deref(),
-                risk_score: h.risk_score,
-                user_id: None,
-                engagement_id: None,
-                scanner_id: Some(scanner_id),
-            })
-            .collect();
-        if !new_hosts.is_empty() {
-            diesel::insert_into(hosts::nessus_hosts)
-                .values(&new_hosts)
+    // 1) Scanner upsert (by type + version)
+    let scanner_type = report.scanner.scanner_type.as_str();
+    let scanner_version = report.scanner.scanner_version.as_deref();
+    let existing: Option<i32> = if let Some(ver) = scanner_version {
+        scn::scanners
+            .filter(scn::scanner_type.eq(scanner_type))
+            .filter(scn::scanner_version.eq(ver))
+            .select(scn::id)
+            .first::<i32>(conn)
+            .optional()?
+    } else {
+        scn::scanners
+            .filter(scn::scanner_type.eq(scanner_type))
+            .filter(scn::scanner_version.is_null())
+            .select(scn::id)
+            .first::<i32>(conn)
+            .optional()?
+    };
+    let scanner_id = match existing {
+        Some(id) => id,
+        None => {
+            diesel::insert_into(scn::scanners)
+                .values(NewScanner { scanner_type, scanner_version })
                 .execute(conn)?;
+            db::last_insert_id(conn)?
         }
-        let host_ids: Vec<i32> = hosts::nessus_hosts
-            .filter(hosts::nessus_report_id.eq(report_id))
-            .order(hosts::id.asc())
-            .select(hosts::id)
-            .load::<i32>(conn)?;
+    };
 
-        // 4) Plugins: build map external plugin_id -> db id
-        use std::collections::HashMap;
-        let mut plugin_id_map: HashMap<i32, i32> = HashMap::new();
-        let ext_ids: Vec<i32> = report
-            .plugins
+    // 2) Report row
+    diesel::insert_into(rep::nessus_reports)
+        .values(NewReport {
+            title: report.report.title.as_deref(),
+            author: report.report.author.as_deref(),
+            company: report.report.company.as_deref(),
+            classification: report.report.classification.as_deref(),
+            user_id: None,
+            engagement_id: None,
+        })
+        .execute(conn)?;
+    let report_id = db::last_insert_id(conn)?;
+
+    Ok(StoredReport {
+        report_id,
+        scanner_id,
+        plugin_ids: HashMap::new(),
+    })
+}
+
+/// Store the plugins in `new_plugins` that `stored` has no row for yet,
+/// reusing rows already stored for the same scanner.
+fn insert_plugins(
+    conn: &mut DbConnection,
+    stored: &mut StoredReport,
+    new_plugins: &[&Plugin],
+) -> Result<(), Error> {
+    use schema::nessus_plugins::dsl as plugins;
+
+    let ext_ids: Vec<i32> = new_plugins
+        .iter()
+        .filter_map(|p| p.plugin_id)
+        .collect();
+    if !ext_ids.is_empty() {
+        // existing
+        for (pid, id) in plugins::nessus_plugins
+            .filter(plugins::scanner_id.eq(stored.scanner_id))
+            .filter(plugins::plugin_id.eq_any(&ext_ids))
+            .select((plugins::plugin_id, plugins::id))
+            .load::<(Option<i32>, i32)>(conn)?
+            .into_iter()
+            .filter_map(|(pid, id)| pid.map(|p| (p, id)))
+        {
+            stored.plugin_ids.insert(pid, id);
+        }
+        // insert missing
+        let missing: Vec<&Plugin> = new_plugins
+            .iter()
+            .copied()
+            .filter(|p| p.plugin_id.and_then(|pid| stored.plugin_ids.get(&pid).cloned()).is_none())
+            .collect();
+        let to_insert: Vec<NewPlugin> = missing
             .iter()
-            .filter_map(|p| p.plugin_id)
+            .map(|p| NewPlugin {
+                plugin_id: p.plugin_id,
+                plugin_name: p.plugin_name.as_deref(),
+                family_name: p.family_name.as_deref(),
+                description: p.description.as_deref(),
+                plugin_version: p.plugin_version.as_deref(),
+                plugin_publication_date: p.plugin_publication_date,
+                plugin_modification_date: p.plugin_modification_date,
+                vuln_publication_date: p.vuln_publication_date,
+                cvss_vector: p.cvss_vector.as_deref(),
+                cvss_base_score: p.cvss_base_score,
+                cvss_temporal_score: p.cvss_temporal_score.as_deref(),
+                cvss_temporal_vector: p.cvss_temporal_vector.as_deref(),
+                exploitability_ease: p.exploitability_ease.as_deref(),
+                exploit_framework_core: p.exploit_framework_core.as_deref(),
+                exploit_framework_metasploit: p.exploit_framework_metasploit.as_deref(),
+                metasploit_name: p.metasploit_name.as_deref(),
+                exploit_framework_canvas: p.exploit_framework_canvas.as_deref(),
+                canvas_package: p.canvas_package.as_deref(),
+                exploit_available: p.exploit_available.as_deref(),
+                risk_factor: p.risk_factor.as_deref(),
+                solution: p.solution.as_deref(),
+                synopsis: p.synopsis.as_deref(),
+                plugin_type: p.plugin_type.as_deref(),
+                exploit_framework_exploithub: p.exploit_framework_exploithub.as_deref(),
+                exploithub_sku: p.exploithub_sku.as_deref(),
+                stig_severity: p.stig_severity.as_deref(),
+                fname: p.fname.as_deref(),
+                always_run: p.always_run.as_deref(),
+                script_version: p.script_version.as_deref(),
+                d2_elliot_name: p.d2_elliot_name.as_deref(),
+                exploit_framework_d2_elliot: p.exploit_framework_d2_elliot.as_deref(),
+                exploited_by_malware: p.exploited_by_malware.as_deref(),
+                rollup: p.rollup,
+                risk_score: p.risk_score,
+                compliance: p.compliance.as_deref(),
+                root_cause: p.root_cause.as_deref(),
+                agent: p.agent.as_deref(),
+                potential_vulnerability: p.potential_vulnerability,
+                in_the_news: p.in_the_news,
+                exploited_by_nessus: p.exploited_by_nessus,
+                unsupported_by_vendor: p.unsupported_by_vendor,
+                default_account: p.default_account,
+                user_id: None,
+                engagement_id: None,
+                policy_id: None,
+                scanner_id: Some(stored.scanner_id),
+                oid: p.oid.as_deref(),
+                cvss3_base_score: p.cvss3_base_score,
+                cvss3_vector: p.cvss3_vector.as_deref(),
+                cvss3_temporal_score: p.cvss3_temporal_score,
+                cvss3_temporal_vector: p.cvss3_temporal_vector.as_deref(),
+                cvss4_base_score: p.cvss4_base_score,
+                cvss4_vector: p.cvss4_vector.as_deref(),
+                vpr_score: p.vpr_score,
+                epss_score: p.epss_score,
+                exploit_code_maturity: p.exploit_code_maturity.as_deref(),
+                threat_intensity_last_28: p.threat_intensity_last_28.as_deref(),
+            })
             .collect();
-        if !ext_ids.is_empty() {
-            // existing
+        if !to_insert.is_empty() {
+            batch_insert!(conn, plugins::nessus_plugins, to_insert)?;
+            // refresh map
             for (pid, id) in plugins::nessus_plugins
-                .filter(plugins::scanner_id.eq(scanner_id))
+                .filter(plugins::scanner_id.eq(stored.scanner_id))
                 .filter(plugins::plugin_id.eq_any(&ext_ids))
                 .select((plugins::plugin_id, plugins::id))
                 .load::<(Option<i32>, i32)>(conn)?
                 .into_iter()
                 .filter_map(|(pid, id)| pid.map(|p| (p, id)))
             {
-                plugin_id_map.insert(pid, id);
+                stored.plugin_ids.insert(pid, id);
             }
-            // insert missing
-            let missing: Vec<&Plugin> = report
-                .plugins
-                .iter()
-                .filter(|p| p.plugin_id.and_then(|pid| plugin_id_map.get(&pid).cloned()).is_none())
-                .collect();
-            let to_insert: Vec<NewPlugin> = missing
-                .iter()
-                .map(|p| NewPlugin {
-                    plugin_id: p.plugin_id,
-                    plugin_name: p.plugin_name.as_deref(),
-                    family_name: p.family_name.as_deref(),
-                    description: p.description.as_deref(),
-                    plugin_version: p.plugin_version.as_deref(),
-                    plugin_publication_date: p.plugin_publication_date,
-                    plugin_modification_date: p.plugin_modification_date,
-                    vuln_publication_date: p.vuln_publication_date,
-                    cvss_vector: p.cvss_vector.as_deref(),
-                    cvss_base_score: p.cvss_base_score,
-                    cvss_temporal_score: p.cvss_temporal_score.as_deref(),
-                    cvss_temporal_vector: p.cvss_temporal_vector.as_deref(),
-                    exploitability_ease: p.exploitability_ease.as_deref(),
-                    exploit_framework_core: p.exploit_framework_core.as_deref(),
-                    exploit_framework_metasploit: p.exploit_framework_metasploit.as_deref(),
-                    metasploit_name: p.metasploit_name.as_deref(),
-                    exploit_framework_canvas: p.exploit_framework_canvas.as_deref(),
-                    canvas_package: p.canvas_package.as_deref(),
-                    exploit_available: p.exploit_available.as_deref(),
-                    risk_factor: p.risk_factor.as_deref(),
-                    solution: p.solution.as_deref(),
-                    synopsis: p.synopsis.as_deref(),
-                    plugin_type: p.plugin_type.as_deref(),
-                    exploit_framework_exploithub: p.exploit_framework_exploithub.as_deref(),
-                    exploithub_sku: p.exploithub_sku.as_deref(),
-                    stig_severity: p.stig_severity.as_deref(),
-                    fname: p.fname.as_deref(),
-                    always_run: p.always_run.as_deref(),
-                    script_version: p.script_version.as_deref(),
-                    d2_elliot_name: p.d2_elliot_name.as_deref(),
-                    exploit_framework_d2_elliot: p.exploit_framework_d2_elliot.as_deref(),
-                    exploited_by_malware: p.exploited_by_malware.as_deref(),
-                    rollup: p.rollup,
-                    risk_score: p.risk_score,
-                    compliance: p.compliance.as_deref(),
-                    root_cause: p.root_cause.as_deref(),
-                    agent: p.agent.as_deref(),
-                    potential_vulnerability: p.potential_vulnerability,
-                    in_the_news: p.in_the_news,
-                    exploited_by_nessus: p.exploited_by_nessus,
-                    unsupported_by_vendor: p.unsupported_by_vendor,
-                    default_account: p.default_account,
-                    user_id: None,
-                    engagement_id: None,
-                    policy_id: None,
-                    scanner_id: Some(scanner_id),
-                })
-                .collect();
-            if !to_insert.is_empty() {
-                diesel::insert_into(plugins::nessus_plugins)
-                    .values(&to_insert)
+        }
+    }
+    Ok(())
+}
+
+/// Store the hosts of `report` with their items, attachments, properties,
+/// services, references and patches. Plugins must already be stored.
+fn insert_hosts(
+    conn: &mut DbConnection,
+    stored: &StoredReport,
+    report: &NessusReport,
+) -> Result<(), Error> {
+    use schema::nessus_attachments::dsl as attach;
+    use schema::nessus_host_properties::dsl as hprops;
+    use schema::nessus_hosts::dsl as hosts;
+    use schema::nessus_items::dsl as items;
+    use schema::nessus_patches::dsl as patches;
+    use schema::nessus_references::dsl as refs;
+    use schema::nessus_service_descriptions::dsl as sdesc;
+
+    // 3) Hosts, keeping the new row ids in order
+    let new_hosts: Vec<NewHost> = report
+        .hosts
+        .iter()
+        .map(|h| NewHost {
+            nessus_report_id: Some(stored.report_id),
+            name: h.name.as_deref(),
+            os: h.os.as_deref(),
+            mac: h.mac.as_deref(),
+            start: h.start,
+            end: h.end,
+            ip: h.ip.as_deref(),
+            fqdn: h.fqdn.as_deref(),
+            netbios: h.netbios.as_deref(),
+            notes: h.notes.as_deref(),
+            risk_score: h.risk_score,
+            user_id: None,
+            engagement_id: None,
+            scanner_id: Some(stored.scanner_id),
+        })
+        .collect();
+    batch_insert!(conn, hosts::nessus_hosts, new_hosts)?;
+    // The streaming sink stores a report's hosts in several calls, so take
+    // the newest rows of this report rather than all of them.
+    let mut host_ids: Vec<i32> = hosts::nessus_hosts
+        .filter(hosts::nessus_report_id.eq(stored.report_id))
+        .select(hosts::id)
+        .order(hosts::id.desc())
+        .limit(new_hosts.len() as i64)
+        .load(conn)?;
+    host_ids.reverse();
+
+    // 5) Attachments: upsert by ahash
+    let mut attachment_id_map: Vec<Option<i32>> = vec![None; report.attachments.len()];
+    for (idx, a) in report.attachments.iter().enumerate() {
+        if let Some(hash) = a.ahash.as_deref() {
+            if let Some(id) = attach::nessus_attachments
+                .filter(attach::ahash.eq(hash))
+                .select(attach::id)
+                .first::<i32>(conn)
+                .optional()? {
+                attachment_id_map[idx] = Some(id);
+            } else {
+                diesel::insert_into(attach::nessus_attachments)
+                    .values(NewAttachment {
+                        name: a.name.as_deref(),
+                        content_type: a.content_type.as_deref(),
+                        path: a.path.as_deref(),
+                        size: a.size,
+                        ahash: a.ahash.as_deref(),
+                        value: a.value.as_deref(),
+                    })
                     .execute(conn)?;
-                // refresh map
-                for (pid, id) in plugins::nessus_plugins
-                    .filter(plugins::scanner_id.eq(scanner_id))
-                    .filter(plugins::plugin_id.eq_any(&ext_ids))
-                    .select((plugins::plugin_id, plugins::id))
-                    .load::<(Option<i32>, i32)>(conn)?
-                    .into_iter()
-                    .filter_map(|(pid, id)| pid.map(|p| (p, id)))
-                {
-                    plugin_id_map.insert(pid, id);
-                }
+                let id = db::last_insert_id(conn)?;
+                attachment_id_map[idx] = Some(id);
             }
         }
+    }
 
-        // 5) Attachments: upsert by ahash
-        let mut attachment_id_map: Vec<Option<i32>> = vec![None; report.attachments.len()];
-        for (idx, a) in report.attachments.iter().enumerate() {
-            if let Some(hash) = a.ahash.as_deref() {
-                if let Some(id) = attach::nessus_attachments
-                    .filter(attach::ahash.eq(hash))
-                    .select(attach::id)
-                    .first::<i32>(conn)
-                    .optional()? {
-                    attachment_id_map[idx] = Some(id);
-                } else {
-                    diesel::insert_into(attach::nessus_attachments)
-                        .values(NewAttachment {
-                            name: a.name.as_deref(),
-                            content_type: a.content_type.as_deref(),
-                            path: a.path.as_deref(),
-                            size: a.size,
-                            ahash: a.ahash.as_deref(),
-                            value: a.value.as_deref(),
-                        })
-                        .execute(conn)?;
-                    let id = diesel::sql_query("SELECT last_insert_rowid() AS id")
-                        .get_result::<LastId>(conn)?
-                        .id as i32;
-                    attachment_id_map[idx] = Some(id);
-                }
-            }
+    // 6) Items one-by-one to get id map
+    let mut item_id_map: Vec<Option<i32>> = vec![None; report.items.len()];
+    for (idx, it) in report.items.iter().enumerate() {
+        let host_db_id = it.host_id.and_then(|hid| host_ids.get(hid as usize).copied());
+        let plugin_db_id = it
+            .plugin_id
+            .and_then(|pid| stored.plugin_ids.get(&pid).copied());
+        let attachment_db_id = it
+            .attachment_id
+            .and_then(|aid| attachment_id_map.get(aid as usize).copied().flatten());
+        diesel::insert_into(items::nessus_items)
+            .values(NewItem {
+                host_id: host_db_id,
+                plugin_id: plugin_db_id,
+                attachment_id: attachment_db_id,
+                plugin_output: it.plugin_output.as_deref(),
+                port: it.port,
+                svc_name: it.svc_name.as_deref(),
+                protocol: it.protocol.as_deref(),
+                severity: it.severity,
+                plugin_name: it.plugin_name.as_deref(),
+                description: it.description.as_deref(),
+                solution: it.solution.as_deref(),
+                risk_factor: it.risk_factor.as_deref(),
+                cvss_base_score: it.cvss_base_score,
+                plugin_version: it.plugin_version.as_deref(),
+                plugin_publication_date: it.plugin_publication_date,
+                plugin_modification_date: it.plugin_modification_date,
+                vuln_publication_date: it.vuln_publication_date,
+                cvss_vector: it.cvss_vector.as_deref(),
+                cvss_temporal_score: it.cvss_temporal_score.as_deref(),
+                cvss_temporal_vector: it.cvss_temporal_vector.as_deref(),
+                exploitability_ease: it.exploitability_ease.as_deref(),
+                synopsis: it.synopsis.as_deref(),
+                exploit_framework_core: it.exploit_framework_core.as_deref(),
+                exploit_framework_metasploit: it.exploit_framework_metasploit.as_deref(),
+                exploit_framework_canvas: it.exploit_framework_canvas.as_deref(),
+                exploit_framework_exploithub: it.exploit_framework_exploithub.as_deref(),
+                exploit_framework_d2_elliot: it.exploit_framework_d2_elliot.as_deref(),
+                verified: it.verified,
+                cm_compliance_info: it.cm_compliance_info.as_deref(),
+                cm_compliance_actual_value: it.cm_compliance_actual_value.as_deref(),
+                cm_compliance_check_id: it.cm_compliance_check_id.as_deref(),
+                cm_compliance_policy_value: it.cm_compliance_policy_value.as_deref(),
+                cm_compliance_audit_file: it.cm_compliance_audit_file.as_deref(),
+                cm_compliance_check_name: it.cm_compliance_check_name.as_deref(),
+                cm_compliance_result: it.cm_compliance_result.as_deref(),
+                cm_compliance_output: it.cm_compliance_output.as_deref(),
+                cm_compliance_reference: it.cm_compliance_reference.as_deref(),
+                cm_compliance_see_also: it.cm_compliance_see_also.as_deref(),
+                cm_compliance_solution: it.cm_compliance_solution.as_deref(),
+                real_severity: it.real_severity,
+                risk_score: it.risk_score,
+                user_id: None,
+                engagement_id: None,
+                rollup_finding: it.rollup_finding,
+                scanner_id: Some(stored.scanner_id),
+                confidence: it.confidence,
+                first_discovered: it.first_discovered,
+                last_observed: it.last_observed,
+                cvss3_base_score: it.cvss3_base_score,
+                cvss3_vector: it.cvss3_vector.as_deref(),
+                cvss3_temporal_score: it.cvss3_temporal_score,
+                cvss3_temporal_vector: it.cvss3_temporal_vector.as_deref(),
+                cvss4_base_score: it.cvss4_base_score,
+                cvss4_vector: it.cvss4_vector.as_deref(),
+                vpr_score: it.vpr_score,
+                epss_score: it.epss_score,
+                exploit_code_maturity: it.exploit_code_maturity.as_deref(),
+                threat_intensity_last_28: it.threat_intensity_last_28.as_deref(),
+                suppressed: it.suppressed,
+            })
+            .execute(conn)?;
+        let id = db::last_insert_id(conn)?;
+        item_id_map[idx] = Some(id);
+    }
+
+    // 7) Host properties
+    if !report.host_properties.is_empty() {
+        let new_props: Vec<NewHostProperty> = report
+            .host_properties
+            .iter()
+            .map(|p| NewHostProperty {
+                host_id: p.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
+                name: p.name.as_deref(),
+                value: p.value.as_deref(),
+                user_id: None,
+                engagement_id: None,
+            })
+            .collect();
+        if !new_props.is_empty() {
+            batch_insert!(conn, hprops::nessus_host_properties, new_props)?;
         }
+    }
 
-        // 6) Items one-by-one to get id map
-        let mut item_id_map: Vec<Option<i32>> = vec![None; report.items.len()];
-        for (idx, it) in report.items.iter().enumerate() {
-            let host_db_id = it.host_id.and_then(|hid| host_ids.get(hid as usize).copied());
-            let plugin_db_id = it
-                .plugin_id
-                .and_then(|pid| plugin_id_map.get(&pid).copied());
-            let attachment_db_id = it
-                .attachment_id
-                .and_then(|aid| attachment_id_map.get(aid as usize).copied().flatten());
-            diesel::insert_into(items::nessus_items)
-                .values(NewItem {
-                    host_id: host_db_id,
-                    plugin_id: plugin_db_id,
-                    attachment_id: attachment_db_id,
-                    plugin_output: it.plugin_output.as_deref(),
-                    port: it.port,
-                    svc_name: it.svc_name.as_deref(),
-                    protocol: it.protocol.as_deref(),
-                    severity: it.severity,
-                    plugin_name: it.plugin_name.as_deref(),
-                    description: it.description.as_deref(),
-                    solution: it.solution.as_deref(),
-                    risk_factor: it.risk_factor.as_deref(),
-                    cvss_base_score: it.cvss_base_score,
-                    plugin_version: it.plugin_version.as_deref(),
-                    plugin_publication_date: it.plugin_publication_date,
-                    plugin_modification_date: it.plugin_modification_date,
-                    vuln_publication_date: it.vuln_publication_date,
-                    cvss_vector: it.cvss_vector.as_deref(),
-                    cvss_temporal_score: it.cvss_temporal_score.as_deref(),
-                    cvss_temporal_vector: it.cvss_temporal_vector.as_deref(),
-                    exploitability_ease: it.exploitability_ease.as_deref(),
-                    synopsis: it.synopsis.as_deref(),
-                    exploit_framework_core: it.exploit_framework_core.as_deref(),
-                    exploit_framework_metasploit: it.exploit_framework_metasploit.as_deref(),
-                    exploit_framework_canvas: it.exploit_framework_canvas.as_deref(),
-                    exploit_framework_exploithub: it.exploit_framework_exploithub.as_deref(),
-                    exploit_framework_d2_elliot: it.exploit_framework_d2_elliot.as_deref(),
-                    verified: it.verified,
-                    cm_compliance_info: it.cm_compliance_info.as_deref(),
-                    cm_compliance_actual_value: it.cm_compliance_actual_value.as_deref(),
-                    cm_compliance_check_id: it.cm_compliance_check_id.as_deref(),
-                    cm_compliance_policy_value: it.cm_compliance_policy_value.as_deref(),
-                    cm_compliance_audit_file: it.cm_compliance_audit_file.as_deref(),
-                    cm_compliance_check_name: it.cm_compliance_check_name.as_deref(),
-                    cm_compliance_result: it.cm_compliance_result.as_deref(),
-                    cm_compliance_output: it.cm_compliance_output.as_deref(),
-                    cm_compliance_reference: it.cm_compliance_reference.as_deref(),
-                    cm_compliance_see_also: it.cm_compliance_see_also.as_deref(),
-                    cm_compliance_solution: it.cm_compliance_solution.as_deref(),
-                    real_severity: it.real_severity,
-                    risk_score: it.risk_score,
-                    user_id: None,
-                    engagement_id: None,
-                    rollup_finding: it.rollup_finding,
-                    scanner_id: Some(scanner_id),
-                })
-                .execute(conn)?;
-            let id = diesel::sql_query("SELECT last_insert_rowid() AS id")
-                .get_result::<LastId>(conn)?
-                .id as i32;
-            item_id_map[idx] = Some(id);
+    // 8) Service descriptions
+    if !report.service_descriptions.is_empty() {
+        let new_sd: Vec<NewServiceDescription> = report
+            .service_descriptions
+            .iter()
+            .map(|sd| NewServiceDescription {
+                host_id: sd.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
+                item_id: sd.item_id.and_then(|iid| item_id_map.get(iid as usize).copied().flatten()),
+                port: sd.port,
+                svc_name: sd.svc_name.as_deref(),
+                protocol: sd.protocol.as_deref(),
+                description: sd.description.as_deref(),
+                user_id: None,
+                engagement_id: None,
+            })
+            .collect();
+        if !new_sd.is_empty() {
+            batch_insert!(conn, sdesc::nessus_service_descriptions, new_sd)?;
         }
+    }
 
-        // 7) Host properties
-        if !report.host_properties.is_empty() {
-            let new_props: Vec<NewHostProperty> = report
-                .host_properties
-                .iter()
-                .map(|p| NewHostProperty {
-                    host_id: p.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
-                    name: p.name.as_deref(),
-                    value: p.value.as_deref(),
-                    user_id: None,
-                    engagement_id: None,
-                })
-                .collect();
-            if !new_props.is_empty() {
-                diesel::insert_into(hprops::nessus_host_properties)
-                    .values(&new_props)
-                    .execute(conn)?;
-            }
+    // 9) References
+    if !report.references.is_empty() {
+        let new_refs: Vec<NewReference> = report
+            .references
+            .iter()
+            .map(|r| NewReference {
+                plugin_id: r.plugin_id.and_then(|pid| stored.plugin_ids.get(&pid).copied()),
+                item_id: r.item_id.and_then(|iid| item_id_map.get(iid as usize).copied().flatten()),
+                source: r.source.as_deref(),
+                value: r.value.as_deref(),
+                user_id: None,
+                engagement_id: None,
+            })
+            .collect();
+        if !new_refs.is_empty() {
+            batch_insert!(conn, refs::nessus_references, new_refs)?;
         }
+    }
 
-        // 8) Service descriptions
-        if !report.service_descriptions.is_empty() {
-            let new_sd: Vec<NewServiceDescription> = report
-                .service_descriptions
-                .iter()
-                .map(|sd| NewServiceDescription {
-                    host_id: sd.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
-                    item_id: sd.item_id.and_then(|iid| item_id_map.get(iid as usize).copied().flatten()),
-                    port: sd.port,
-                    svc_name: sd.svc_name.as_deref(),
-                    protocol: sd.protocol.as_deref(),
-                    description: sd.description.as_deref(),
-                    user_id: None,
-                    engagement_id: None,
-                })
-                .collect();
-            if !new_sd.is_empty() {
-                diesel::insert_into(sdesc::nessus_service_descriptions)
-                    .values(&new_sd)
-                    .execute(conn)?;
-            }
+    // 10) Patches
+    if !report.patches.is_empty() {
+        let new_patches: Vec<NewPatch> = report
+            .patches
+            .iter()
+            .map(|p| NewPatch {
+                host_id: p.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
+                name: p.name.as_deref(),
+                value: p.value.as_deref(),
+                user_id: None,
+                engagement_id: None,
+            })
+            .collect();
+        if !new_patches.is_empty() {
+            batch_insert!(conn, patches::nessus_patches, new_patches)?;
         }
+    }
 
-        // 9) References
-        if !report.references.is_empty() {
-            let new_refs: Vec<NewReference> = report
-                .references
-                .iter()
-                .map(|r| NewReference {
-                    plugin_id: r.plugin_id.and_then(|pid| plugin_id_map.get(&pid).copied()),
-                    item_id: r.item_id.and_then(|iid| item_id_map.get(iid as usize).copied().flatten()),
-                    source: r.source.as_deref(),
-                    value: r.value.as_deref(),
-                    user_id: None,
-                    engagement_id: None,
-                })
-                .collect();
-            if !new_refs.is_empty() {
-                diesel::insert_into(refs::nessus_references)
-                    .values(&new_refs)
-                    .execute(conn)?;
-            }
+    Ok(())
+}
+
+/// Store the scan policies of `report` and their settings.
+fn insert_policies(
+    conn: &mut DbConnection,
+    report_id: i32,
+    report: &NessusReport,
+) -> Result<(), Error> {
+    use schema::nessus_family_selections::dsl as famsel;
+    use schema::nessus_plugin_preferences::dsl as plgprefs;
+    use schema::nessus_policies::dsl as pols;
+    use schema::nessus_policy_plugins::dsl as polplugs;
+    use schema::nessus_server_preferences::dsl as srvprefs;
+
+    // 11) Policies and related
+    if !report.policies.is_empty() {
+        let new_pols: Vec<NewPolicy> = report
+            .policies
+            .iter()
+            .map(|p| NewPolicy {
+                nessus_report_id: Some(report_id),
+                name: p.name.as_deref(),
+                comments: p.comments.as_deref(),
+                owner: p.owner.as_deref(),
+                visibility: p.visibility.as_deref(),
+            })
+            .collect();
+        if !new_pols.is_empty() {
+            batch_insert!(conn, pols::nessus_policies, new_pols)?;
         }
+        // Fetch policy ids in order
+        let policy_ids: Vec<i32> = pols::nessus_policies
+            .filter(pols::nessus_report_id.eq(report_id))
+            .order(pols::id.asc())
+            .select(pols::id)
+            .load::<i32>(conn)?;
+        // Settings carry their policy's index; map it to the inserted row
+        let policy_fk = |idx: Option<i32>| {
+            idx.and_then(|i| policy_ids.get(i as usize))
+                .or_else(|| policy_ids.first())
+                .copied()
+        };
 
-        // 10) Patches
-        if !report.patches.is_empty() {
-            let new_patches: Vec<NewPatch> = report
-                .patches
-                .iter()
-                .map(|p| NewPatch {
-                    host_id: p.host_id.and_then(|hid| host_ids.get(hid as usize).copied()),
-                    name: p.name.as_deref(),
-                    value: p.value.as_deref(),
-                    user_id: None,
-                    engagement_id: None,
-                })
-                .collect();
-            if !new_patches.is_empty() {
-                diesel::insert_into(patches::nessus_patches)
-                    .values(&new_patches)
-                    .execute(conn)?;
-            }
+        // Policy plugins
+        let new_pp: Vec<NewPolicyPlugin> = report
+            .policy_plugins
+            .iter()
+            .map(|pp| NewPolicyPlugin {
+                policy_id: policy_fk(pp.policy_id),
+                plugin_id: pp.plugin_id,
+                plugin_name: pp.plugin_name.as_deref(),
+                family_name: pp.family_name.as_deref(),
+                status: pp.status.as_deref(),
+            })
+            .collect();
+        if !new_pp.is_empty() {
+            batch_insert!(conn, polplugs::nessus_policy_plugins, new_pp)?;
+        }
+        // Family selections
+        let new_fs: Vec<NewFamilySelection> = report
+            .family_selections
+            .iter()
+            .map(|fs| NewFamilySelection {
+                policy_id: policy_fk(fs.policy_id),
+                family_name: fs.family_name.as_deref(),
+                status: fs.status.as_deref(),
+            })
+            .collect();
+        if !new_fs.is_empty() {
+            batch_insert!(conn, famsel::nessus_family_selections, new_fs)?;
+        }
+        // Plugin preferences
+        let new_pf: Vec<NewPluginPreference> = report
+            .plugin_preferences
+            .iter()
+            .map(|pf| NewPluginPreference {
+                policy_id: policy_fk(pf.policy_id),
+                plugin_id: pf.plugin_id,
+                fullname: pf.fullname.as_deref(),
+                preference_name: pf.preference_name.as_deref(),
+                preference_type: pf.preference_type.as_deref(),
+                selected_value: pf.selected_value.as_deref(),
+                preference_values: pf.preference_values.as_deref(),
+            })
+            .collect();
+        if !new_pf.is_empty() {
+            batch_insert!(conn, plgprefs::nessus_plugin_preferences, new_pf)?;
+        }
+        // Server preferences
+        let new_sp: Vec<NewServerPreference> = report
+            .server_preferences
+            .iter()
+            .map(|sp| NewServerPreference {
+                policy_id: policy_fk(sp.policy_id),
+                name: sp.name.as_deref(),
+                value: sp.value.as_deref(),
+            })
+            .collect();
+        if !new_sp.is_empty() {
+            batch_insert!(conn, srvprefs::nessus_server_preferences, new_sp)?;
         }
+    }
+    Ok(())
+}
 
-        // 11) Policies and related
-        if !report.policies.is_empty() {
-            let new_pols: Vec<NewPolicy> = report
-                .policies
-                .iter()
-                .map(|p| NewPolicy {
-                    nessus_report_id: Some(report_id),
-                    name: p.name.as_deref(),
-                    comments: p.comments.as_deref(),
-                    owner: p.owner.as_deref(),
-                    visibility: p.visibility.as_deref(),
-                })
-                .collect();
-            if !new_pols.is_empty() {
-                diesel::insert_into(pols::nessus_policies)
-                    .values(&new_pols)
-                    .execute(conn)?;
-            }
-            // Fetch policy ids in order
-            let policy_ids: Vec<i32> = pols::nessus_policies
-                .filter(pols::nessus_report_id.eq(report_id))
-                .order(pols::id.asc())
-                .select(pols::id)
-                .load::<i32>(conn)?;
-
-            // Policy plugins
-            let new_pp: Vec<NewPolicyPlugin> = report
-                .policy_plugins
-                .iter()
-                .map(|pp| NewPolicyPlugin {
-                    policy_id: None,
-                    plugin_id: pp.plugin_id,
-                    plugin_name: pp.plugin_name.as_deref(),
-                    family_name: pp.family_name.as_deref(),
-                    status: pp.status.as_deref(),
-                })
-                .collect();
-            if !new_pp.is_empty() {
-                diesel::insert_into(polplugs::nessus_policy_plugins)
-                    .values(&new_pp)
-                    .execute(conn)?;
-            }
-            // Family selections
-            let new_fs: Vec<NewFamilySelection> = report
-                .family_selections
-                .iter()
-                .map(|fs| NewFamilySelection {
-                    policy_id: None,
-                    family_name: fs.family_name.as_deref(),
-                    status: fs.status.as_deref(),
-                })
-                .collect();
-            if !new_fs.is_empty() {
-                diesel::insert_into(famsel::nessus_family_selections)
-                    .values(&new_fs)
-                    .execute(conn)?;
-            }
-            // Plugin preferences
-            let new_pf: Vec<NewPluginPreference> = report
-                .plugin_preferences
-                .iter()
-                .map(|pf| NewPluginPreference {
-                    policy_id: None,
-                    plugin_id: pf.plugin_id,
-                    fullname: pf.fullname.as_deref(),
-                    preference_name: pf.preference_name.as_deref(),
-                    preference_type: pf.preference_type.as_deref(),
-                    selected_value: pf.selected_value.as_deref(),
-                    preference_values: pf.preference_values.as_deref(),
-                })
-                .collect();
-            if !new_pf.is_empty() {
-                diesel::insert_into(plgprefs::nessus_plugin_preferences)
-                    .values(&new_pf)
-                    .execute(conn)?;
-            }
-            // Server preferences
-            let new_sp: Vec<NewServerPreference> = report
-                .server_preferences
-                .iter()
-                .map(|sp| NewServerPreference {
-                    policy_id: None,
-                    name: sp.name.as_deref(),
-                    value: sp.value.as_deref(),
-                })
-                .collect();
-            if !new_sp.is_empty() {
-                diesel::insert_into(srvprefs::nessus_server_preferences)
-                    .values(&new_sp)
-                    .execute(conn)?;
-            }
+/// Hosts buffered by [`DatabaseSink`] before a batch is written.
+pub const DEFAULT_BATCH_HOSTS: usize = 100;
+
+/// Writes a streamed report to the database in batches of hosts, each batch
+/// in its own transaction, so only one batch is held in memory at a time.
+/// Run the whole stream inside an outer transaction to store the report only
+/// if the file is read completely.
+pub struct DatabaseSink<'a> {
+    conn: &'a mut DbConnection,
+    batch_hosts: usize,
+    severity_overrides: HashMap<i32, i32>,
+    whitelist: HashSet<i32>,
+    blacklist: HashSet<i32>,
+    stored: Option<StoredReport>,
+    /// Leading plugins of the streamed header that are already stored.
+    plugins_stored: usize,
+    pending: NessusReport,
+    hosts_written: usize,
+    items_written: usize,
+}
+
+impl<'a> DatabaseSink<'a> {
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self {
+            conn,
+            batch_hosts: DEFAULT_BATCH_HOSTS,
+            severity_overrides: HashMap::new(),
+            whitelist: HashSet::new(),
+            blacklist: HashSet::new(),
+            stored: None,
+            plugins_stored: 0,
+            pending: NessusReport::default(),
+            hosts_written: 0,
+            items_written: 0,
         }
+    }
+
+    /// Number of hosts written per transaction.
+    pub fn batch_hosts(mut self, hosts: usize) -> Self {
+        self.batch_hosts = hosts.max(1);
+        self
+    }
 
+    /// Plugin severity overrides applied to each host before it is stored.
+    pub fn severity_overrides(mut self, overrides: HashMap<i32, i32>) -> Self {
+        self.severity_overrides = overrides;
+        self
+    }
+
+    /// Plugin whitelist and blacklist applied to each host and to the stored
+    /// plugins, as `filter_report` does for an in-memory report.
+    pub fn plugin_filters(mut self, whitelist: HashSet<i32>, blacklist: HashSet<i32>) -> Self {
+        self.whitelist = whitelist;
+        self.blacklist = blacklist;
+        self
+    }
+
+    /// ID of the `nessus_reports` row, once the first batch has been written.
+    pub fn report_id(&self) -> Option<i32> {
+        self.stored.as_ref().map(|s| s.report_id)
+    }
+
+    pub fn hosts_written(&self) -> usize {
+        self.hosts_written
+    }
+
+    pub fn items_written(&self) -> usize {
+        self.items_written
+    }
+
+    /// Write the pending hosts and any plugins of `header` not stored yet.
+    /// With `last`, the policies are written as well.
+    fn flush(&mut self, header: &NessusReport, last: bool) -> Result<(), Error> {
+        let pending = std::mem::take(&mut self.pending);
+        let new_plugins: Vec<&Plugin> = header
+            .plugins
+            .get(self.plugins_stored..)
+            .unwrap_or_default()
+            .iter()
+            .filter(|p| {
+                let pid = p.plugin_id.unwrap_or(0);
+                (self.whitelist.is_empty() || self.whitelist.contains(&pid))
+                    && !self.blacklist.contains(&pid)
+            })
+            .collect();
+        let stored = &mut self.stored;
+        self.conn.transaction::<_, Error, _>(|conn| {
+            let stored = match stored {
+                Some(s) => s,
+                None => stored.insert(insert_report(conn, header)?),
+            };
+            insert_plugins(conn, stored, &new_plugins)?;
+            insert_hosts(conn, stored, &pending)?;
+            if last {
+                insert_policies(conn, stored.report_id, header)?;
+            }
+            Ok(())
+        })?;
+        self.plugins_stored = header.plugins.len();
+        self.hosts_written += pending.hosts.len();
+        self.items_written += pending.items.len();
         Ok(())
-    })
+    }
+}
+
+impl crate::parser::ReportSink for DatabaseSink<'_> {
+    fn host(&mut self, header: &NessusReport, mut chunk: NessusReport) -> Result<(), Error> {
+        crate::parser::filter_report(
+            &mut chunk,
+            &self.whitelist,
+            &self.blacklist,
+            &crate::parser::Filters::default(),
+        );
+        crate::parser::apply_severity_overrides(&mut chunk, &self.severity_overrides);
+        crate::parser::append_report(&mut self.pending, chunk);
+        if self.pending.hosts.len() >= self.batch_hosts {
+            self.flush(header, false)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, header: NessusReport) -> Result<(), Error> {
+        self.flush(&header, true)
+    }
 }
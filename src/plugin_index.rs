@@ -4,6 +4,9 @@ use diesel_migrations::MigrationHarness;
 use regex::Regex;
 use walkdir::WalkDir;
 
+use std::collections::HashSet;
+use std::path::Path;
+
 use crate::migrate::MIGRATIONS;
 use crate::schema::nessus_plugin_metadata;
 use tracing::info;
@@ -17,9 +20,11 @@ struct NewPluginMetadata<'a> {
     bid: Option<&'a str>,
 }
 
+/// SQLite database the index is written to.
+pub const INDEX_DB: &str = "risu.db";
+
 pub fn run(dir: &std::path::Path) -> Result<(), crate::error::Error> {
-    let database_url = "risu.db";
-    let mut conn = SqliteConnection::establish(database_url)?;
+    let mut conn = SqliteConnection::establish(INDEX_DB)?;
     conn.run_pending_migrations(MIGRATIONS)
         .map_err(crate::error::Error::Migration)?;
 
@@ -95,3 +100,19 @@ pub fn run(dir: &std::path::Path) -> Result<(), crate::error::Error> {
     );
     Ok(())
 }
+
+/// Plugin IDs in the index at `path`, or `None` when there is no index or it
+/// is empty. The database is not created when missing.
+pub fn known_plugin_ids(path: &Path) -> Option<HashSet<i32>> {
+    if !path.exists() {
+        return None;
+    }
+    let url = path.to_str()?;
+    let mut conn = SqliteConnection::establish(url).ok()?;
+    let ids: Vec<Option<i32>> = nessus_plugin_metadata::table
+        .select(nessus_plugin_metadata::script_id)
+        .load(&mut conn)
+        .ok()?;
+    let ids: HashSet<i32> = ids.into_iter().flatten().collect();
+    (!ids.is_empty()).then_some(ids)
+}
@@ -0,0 +1,257 @@
+//! Business context for hosts from an asset-context TOML file.
+//!
+//! Each `[[asset]]` rule matches hosts by CIDR, hostname regex and/or OS
+//! regex; every criterion given must match. Matching hosts get one
+//! `asset-tag` host property per tag and, from the first matching rule that
+//! names one, an `asset-criticality` tier.
+
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
+use crate::models::host_property::{ASSET_CRITICALITY, ASSET_TAG};
+use crate::models::{Host, HostProperty};
+use crate::parser::NessusReport;
+use ipnet::IpNet;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetDef {
+    cidr: Option<OneOrMany>,
+    hostname: Option<String>,
+    os: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    tier: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetsFile {
+    #[serde(default)]
+    asset: Vec<AssetDef>,
+}
+
+/// A validated asset rule.
+#[derive(Debug)]
+pub struct AssetRule {
+    networks: Vec<IpNet>,
+    hostname: Option<Regex>,
+    os: Option<Regex>,
+    pub tags: Vec<String>,
+    pub tier: Option<String>,
+}
+
+impl AssetRule {
+    fn from_def(def: AssetDef) -> Result<Self, String> {
+        let networks = match def.cidr {
+            None => Vec::new(),
+            Some(OneOrMany::One(c)) => vec![c],
+            Some(OneOrMany::Many(c)) => c,
+        }
+        .iter()
+        .map(|c| c.trim().parse::<IpNet>().map_err(|e| format!("cidr '{c}': {e}")))
+        .collect::<Result<Vec<_>, _>>()?;
+        let regex = |field: &str, pattern: Option<String>| {
+            pattern
+                .map(|p| Regex::new(&format!("(?i){p}")).map_err(|e| format!("{field}: {e}")))
+                .transpose()
+        };
+        let rule = Self {
+            networks,
+            hostname: regex("hostname", def.hostname)?,
+            os: regex("os", def.os)?,
+            tags: def.tags,
+            tier: def.tier,
+        };
+        if rule.networks.is_empty() && rule.hostname.is_none() && rule.os.is_none() {
+            return Err("rule has no cidr, hostname or os to match".to_string());
+        }
+        Ok(rule)
+    }
+
+    /// Whether `host` meets every criterion of the rule.
+    pub fn matches(&self, host: &Host) -> bool {
+        let ip_ok = self.networks.is_empty()
+            || host
+                .ip
+                .as_deref()
+                .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+                .is_some_and(|ip| self.networks.iter().any(|n| n.contains(&ip)));
+        let name_ok = self.hostname.as_ref().is_none_or(|re| {
+            [&host.name, &host.fqdn, &host.netbios]
+                .into_iter()
+                .flatten()
+                .any(|n| re.is_match(n))
+        });
+        let os_ok = self
+            .os
+            .as_ref()
+            .is_none_or(|re| host.os.as_deref().is_some_and(|os| re.is_match(os)));
+        ip_ok && name_ok && os_ok
+    }
+}
+
+/// Locate the asset-context file: `RISU_ASSETS_FILE`, then `./assets.toml`,
+/// then `~/.risu/assets.toml`.
+pub(crate) fn find_assets_file() -> Option<PathBuf> {
+    crate::config::find_config_file("RISU_ASSETS_FILE", "assets.toml")
+}
+
+/// Read the rules of an asset-context file. Rules with an invalid CIDR or
+/// regex are reported and skipped.
+pub fn load_rules(path: &Path) -> Result<Vec<AssetRule>, crate::error::Error> {
+    let txt = fs::read_to_string(path)?;
+    let file: AssetsFile = toml::from_str(&txt).map_err(|e| {
+        crate::error::Error::Config(format!("asset file '{}': {e}", path.display()))
+    })?;
+    let mut rules = Vec::new();
+    for (n, def) in file.asset.into_iter().enumerate() {
+        match AssetRule::from_def(def) {
+            Ok(rule) => rules.push(rule),
+            Err(e) => eprintln!("Skipping asset rule {} in '{}': {e}", n + 1, path.display()),
+        }
+    }
+    Ok(rules)
+}
+
+/// Attach the tags and tier of the matching `rules` to each host. Properties
+/// the host already has are not added twice, and an existing tier is kept.
+pub fn tag_hosts(report: &mut NessusReport, rules: &[AssetRule]) {
+    let mut existing: HashSet<(i32, String, String)> = report
+        .host_properties
+        .iter()
+        .filter(|hp| matches!(hp.name.as_deref(), Some(ASSET_TAG | ASSET_CRITICALITY)))
+        .filter_map(|hp| Some((hp.host_id?, hp.name.clone()?, hp.value.clone()?)))
+        .collect();
+    let mut tiered: HashSet<i32> = existing
+        .iter()
+        .filter(|(_, name, _)| name == ASSET_CRITICALITY)
+        .map(|(id, _, _)| *id)
+        .collect();
+    let mut added = Vec::new();
+    for host in &report.hosts {
+        for rule in rules.iter().filter(|r| r.matches(host)) {
+            for tag in &rule.tags {
+                if existing.insert((host.id, ASSET_TAG.to_string(), tag.clone())) {
+                    added.push((host.id, ASSET_TAG, tag.clone()));
+                }
+            }
+            if let Some(tier) = &rule.tier
+                && tiered.insert(host.id)
+            {
+                added.push((host.id, ASSET_CRITICALITY, tier.clone()));
+            }
+        }
+    }
+    for (host_id, name, value) in added {
+        report.host_properties.push(HostProperty {
+            host_id: Some(host_id),
+            name: Some(name.to_string()),
+            value: Some(value),
+            ..HostProperty::default()
+        });
+    }
+}
+
+struct AssetContext;
+
+impl PostProcess for AssetContext {
+    fn info(&self) -> PostProcessInfo {
+        // After fix_ips so hosts known only by name can match a CIDR.
+        PostProcessInfo { name: "asset_context", order: 15 }
+    }
+
+    fn run(&self, report: &mut NessusReport, _index: &mut ReportIndex) {
+        let Some(path) = find_assets_file() else { return };
+        match load_rules(&path) {
+            Ok(rules) => {
+                info!("Loaded asset context from {} ({} rules)", path.display(), rules.len());
+                tag_hosts(report, &rules);
+            }
+            Err(e) => eprintln!("Failed to load asset context: {e}"),
+        }
+    }
+}
+
+inventory::submit! {
+    PluginEntry { plugin: &AssetContext }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml: &str) -> Vec<AssetRule> {
+        let file: AssetsFile = toml::from_str(toml).unwrap();
+        file.asset.into_iter().map(|d| AssetRule::from_def(d).unwrap()).collect()
+    }
+
+    #[test]
+    fn tags_hosts_by_cidr_hostname_and_os() {
+        let rules = rules(
+            r#"
+            [[asset]]
+            hostname = "^dc\\d+"
+            os = "windows server"
+            tags = ["domain-controller"]
+            tier = "critical"
+
+            [[asset]]
+            cidr = ["10.1.0.0/16", "10.9.0.0/16"]
+            tags = ["pci"]
+            tier = "high"
+            "#,
+        );
+        let host = |id: i32, ip: &str, name: &str, os: &str| Host {
+            id,
+            name: Some(name.to_string()),
+            os: Some(os.to_string()),
+            ip: Some(ip.to_string()),
+            ..Host::default()
+        };
+        let mut report = NessusReport {
+            hosts: vec![
+                host(0, "10.1.2.3", "DC01", "Microsoft Windows Server 2019"),
+                host(1, "10.9.0.7", "web01", "Linux"),
+                host(2, "192.168.1.1", "dc02", "Linux"),
+            ],
+            ..NessusReport::default()
+        };
+        tag_hosts(&mut report, &rules);
+        tag_hosts(&mut report, &rules);
+        let props: Vec<(i32, &str, &str)> = report
+            .host_properties
+            .iter()
+            .map(|p| {
+                (p.host_id.unwrap(), p.name.as_deref().unwrap(), p.value.as_deref().unwrap())
+            })
+            .collect();
+        assert_eq!(
+            props,
+            [
+                (0, ASSET_TAG, "domain-controller"),
+                (0, ASSET_CRITICALITY, "critical"),
+                (0, ASSET_TAG, "pci"),
+                (1, ASSET_TAG, "pci"),
+                (1, ASSET_CRITICALITY, "high"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_rules_without_criteria() {
+        let file: AssetsFile = toml::from_str("[[asset]]\ntags = [\"x\"]\n").unwrap();
+        let def = file.asset.into_iter().next().unwrap();
+        assert!(AssetRule::from_def(def).is_err());
+    }
+}
@@ -1,6 +1,10 @@
-use super::{PluginEntry, PostProcess, PostProcessInfo};
+use std::collections::HashMap;
+
+use super::rules::{self, Rules};
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
 use crate::parser::NessusReport;
 
+/// Sets the severity of findings from the severity rules; see [`rules`].
 struct DowngradePlugins;
 
 impl PostProcess for DowngradePlugins {
@@ -11,12 +15,31 @@ impl PostProcess for DowngradePlugins {
         }
     }
 
-    fn run(&self, report: &mut NessusReport) {
-        for (plugin_id, severity) in PLUGINS_TO_SEVERITY {
-            for item in &mut report.items {
-                if item.plugin_id == Some(*plugin_id) {
-                    item.severity = Some(*severity);
-                }
+    fn run(&self, report: &mut NessusReport, index: &mut ReportIndex) {
+        let rules = Rules::load();
+        let cpes = rules::plugin_cpes(report);
+        let mut by_plugin: HashMap<i32, Option<i32>> = HashMap::new();
+        let updates: Vec<(usize, i32)> = report
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                let pid = item.plugin_id?;
+                let severity = *by_plugin.entry(pid).or_insert_with(|| {
+                    let plugin = index.plugin_index(pid).map(|i| &report.plugins[i]);
+                    let cpes = cpes.get(&pid).map_or(&[][..], Vec::as_slice);
+                    rules.severity(pid, plugin, cpes)
+                });
+                severity.map(|sev| (idx, sev))
+            })
+            .collect();
+        for (idx, sev) in updates {
+            let item = &mut report.items[idx];
+            // Suppressed findings stay hidden with the new severity.
+            if item.suppressed == Some(true) {
+                item.real_severity = Some(sev);
+            } else {
+                item.severity = Some(sev);
             }
         }
     }
@@ -25,16 +48,3 @@ impl PostProcess for DowngradePlugins {
 inventory::submit! {
     PluginEntry { plugin: &DowngradePlugins }
 }
-
-const PLUGINS_TO_SEVERITY: &[(i32, i32)] = &[
-    (41028, 0), // SNMP Agent Default Community Name (public)
-    (10264, 0), // SNMP Agent Default Community Names
-    (10081, 0), // FTP Privileged Port Bounce Scan
-    (42411, 0), // Microsoft Windows SMB Shares Unprivileged Access
-    (66349, 0), // X Server Unauthenticated Access: Screenshot
-    (26925, 0), // VNC Server Unauthenticated Access
-    (66174, 0), // VNC Server Unauthenticated Access: Screenshot
-    (10205, 0), // rlogin Service Detection
-    (20007, 2), // SSL Version 2 and 3 Protocol Detection
-    (80101, 2), // IPMI v2.0 Password Hash Disclosure
-];
@@ -1,5 +1,5 @@
 use crate::parser::NessusReport;
-use super::{PostProcess, PostProcessInfo, PluginEntry};
+use super::{PostProcess, PostProcessInfo, PluginEntry, ReportIndex};
 
 struct FixIps;
 
@@ -8,7 +8,7 @@ impl PostProcess for FixIps {
         PostProcessInfo { name: "fix_ips", order: 10 }
     }
 
-    fn run(&self, report: &mut NessusReport) {
+    fn run(&self, report: &mut NessusReport, _index: &mut ReportIndex) {
         for host in &mut report.hosts {
             if host.ip.is_none() {
                 if let Some(name) = host.name.clone() {
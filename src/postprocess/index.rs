@@ -0,0 +1,124 @@
+//! Lookup tables shared by post-processing plugins.
+//!
+//! Items refer to plugins by their external `plugin_id` and to hosts by
+//! `Host::id`, so finding either by scanning the report costs a pass over
+//! every plugin or item. [`ReportIndex`] builds the maps once per run.
+
+use std::collections::HashMap;
+
+use crate::models::{Item, Plugin};
+use crate::parser::NessusReport;
+
+/// Positions of plugins and items in a [`NessusReport`], keyed by plugin ID
+/// and host ID.
+#[derive(Debug, Default)]
+pub struct ReportIndex {
+    plugins: HashMap<i32, usize>,
+    items_by_plugin: HashMap<i32, Vec<usize>>,
+    items_by_host: HashMap<Option<i32>, Vec<usize>>,
+}
+
+impl ReportIndex {
+    /// Index the plugins and items of `report`.
+    pub fn new(report: &NessusReport) -> Self {
+        let mut index = Self::default();
+        index.refresh(report);
+        index
+    }
+
+    /// Rebuild the maps after items or plugins were added, removed or
+    /// reordered without going through [`push_item`](Self::push_item) or
+    /// [`push_plugin`](Self::push_plugin).
+    pub fn refresh(&mut self, report: &NessusReport) {
+        self.plugins.clear();
+        self.items_by_plugin.clear();
+        self.items_by_host.clear();
+        for (idx, plugin) in report.plugins.iter().enumerate() {
+            if let Some(pid) = plugin.plugin_id {
+                // The first plugin wins, like `plugins.iter().find`.
+                self.plugins.entry(pid).or_insert(idx);
+            }
+        }
+        for (idx, item) in report.items.iter().enumerate() {
+            self.add_item(idx, item);
+        }
+    }
+
+    fn add_item(&mut self, idx: usize, item: &Item) {
+        if let Some(pid) = item.plugin_id {
+            self.items_by_plugin.entry(pid).or_default().push(idx);
+        }
+        self.items_by_host.entry(item.host_id).or_default().push(idx);
+    }
+
+    /// Position of the plugin with `plugin_id` in `report.plugins`.
+    pub fn plugin_index(&self, plugin_id: i32) -> Option<usize> {
+        self.plugins.get(&plugin_id).copied()
+    }
+
+    /// Positions in `report.items` of the findings of `plugin_id`.
+    pub fn items_for_plugin(&self, plugin_id: i32) -> &[usize] {
+        self.items_by_plugin.get(&plugin_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Positions in `report.items` of the findings on `host_id`. `None`
+    /// returns the findings without a host.
+    pub fn items_for_host(&self, host_id: Option<i32>) -> &[usize] {
+        self.items_by_host.get(&host_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Append `item` to the report and the index.
+    pub fn push_item(&mut self, report: &mut NessusReport, item: Item) {
+        self.add_item(report.items.len(), &item);
+        report.items.push(item);
+    }
+
+    /// Append `plugin` to the report and the index.
+    pub fn push_plugin(&mut self, report: &mut NessusReport, plugin: Plugin) {
+        if let Some(pid) = plugin.plugin_id {
+            self.plugins.entry(pid).or_insert(report.plugins.len());
+        }
+        report.plugins.push(plugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_plugins_and_items_by_host() {
+        let mut report = NessusReport::default();
+        for pid in [10, 20] {
+            report.plugins.push(Plugin {
+                plugin_id: Some(pid),
+                ..Plugin::default()
+            });
+        }
+        for (host, pid) in [(Some(0), 10), (Some(1), 10), (Some(1), 20), (None, 20)] {
+            report.items.push(Item {
+                host_id: host,
+                plugin_id: Some(pid),
+                ..Item::default()
+            });
+        }
+        let mut index = ReportIndex::new(&report);
+        assert_eq!(index.plugin_index(20), Some(1));
+        assert_eq!(index.items_for_plugin(10), &[0, 1]);
+        assert_eq!(index.items_for_host(Some(1)), &[1, 2]);
+        assert_eq!(index.items_for_host(None), &[3]);
+        assert!(index.items_for_plugin(30).is_empty());
+
+        index.push_plugin(&mut report, Plugin {
+            plugin_id: Some(30),
+            ..Plugin::default()
+        });
+        index.push_item(&mut report, Item {
+            host_id: Some(0),
+            plugin_id: Some(30),
+            ..Item::default()
+        });
+        assert_eq!(index.plugin_index(30), Some(2));
+        assert_eq!(index.items_for_host(Some(0)), &[0, 4]);
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Plugins implement [`PostProcess`] and register themselves using the
 //! [`inventory`] crate. They run sequentially on the [`NessusReport`] after
-//! parsing to adjust or enrich data.
+//! parsing to adjust or enrich data, sharing one [`ReportIndex`] for plugin
+//! and host lookups.
 
 use crate::parser::{filter_report, Filters, NessusReport};
 use std::collections::HashSet;
@@ -18,8 +19,9 @@ pub struct PostProcessInfo {
 pub trait PostProcess: Sync + Send {
     /// Return metadata about the plugin.
     fn info(&self) -> PostProcessInfo;
-    /// Execute the plugin on the report.
-    fn run(&self, report: &mut NessusReport);
+    /// Execute the plugin on the report. Plugins that add, remove or reorder
+    /// items or plugins keep `index` current.
+    fn run(&self, report: &mut NessusReport, index: &mut ReportIndex);
 }
 
 /// Wrapper type used for inventory registration.
@@ -47,9 +49,10 @@ impl Registry {
 
     /// Run all plugins in order.
     pub fn run(&self, report: &mut NessusReport) {
+        let mut index = ReportIndex::new(report);
         for plugin in &self.plugins {
             info!("Running post-process plugin: {}", plugin.info().name);
-            plugin.run(report);
+            plugin.run(report, &mut index);
         }
         info!(
             "Post-processed report v{} ({} hosts, {} items, {} plugins)",
@@ -78,10 +81,22 @@ pub fn process(
     blacklist: &HashSet<i32>,
     filters: &Filters,
 ) {
-    filter_report(report, whitelist, blacklist, filters);
+    // Tags are attached by the asset_context plugin, so they can only be
+    // filtered on afterwards.
+    let untagged = Filters {
+        tag: None,
+        ..filters.clone()
+    };
+    filter_report(report, whitelist, blacklist, &untagged);
     let registry = Registry::discover();
     registry.run(report);
-    filter_report(report, whitelist, blacklist, filters);
+    if filters.tag.is_some() {
+        let tagged = Filters {
+            tag: filters.tag.clone(),
+            ..Filters::default()
+        };
+        filter_report(report, &HashSet::new(), &HashSet::new(), &tagged);
+    }
 }
 
 /// Display the names of all registered plugins.
@@ -100,10 +115,16 @@ pub fn list() -> Vec<PostProcessInfo> {
     infos
 }
 
+pub mod asset_context;
 mod downgrade_plugins;
 mod fix_ips;
+mod index;
 mod normalize_plugin_names;
 mod risk_score;
-mod rollups_toml;
+pub mod rollups_toml;
+mod rules;
 mod root_cause;
-mod sort_hosts;
+pub(crate) mod sort_hosts;
+mod suppressions;
+
+pub use index::ReportIndex;
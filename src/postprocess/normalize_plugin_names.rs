@@ -1,4 +1,4 @@
-use super::{PluginEntry, PostProcess, PostProcessInfo};
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
 use crate::parser::NessusReport;
 
 struct NormalizePluginNames;
@@ -11,7 +11,7 @@ impl PostProcess for NormalizePluginNames {
         }
     }
 
-    fn run(&self, report: &mut NessusReport) {
+    fn run(&self, report: &mut NessusReport, _index: &mut ReportIndex) {
         for plugin in &mut report.plugins {
             if let Some(ref mut name) = plugin.plugin_name {
                 for s in STRINGS_TO_SANITIZE {
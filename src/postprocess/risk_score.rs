@@ -1,6 +1,8 @@
 use chrono::Local;
+use rayon::prelude::*;
 
-use super::{PluginEntry, PostProcess, PostProcessInfo};
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
+use crate::analysis::scoring;
 use crate::parser::NessusReport;
 
 struct RiskScore;
@@ -13,59 +15,43 @@ impl PostProcess for RiskScore {
         }
     }
 
-    fn run(&self, report: &mut NessusReport) {
+    fn run(&self, report: &mut NessusReport, index: &mut ReportIndex) {
         let today = Local::now().naive_local().date();
+        let risk = scoring::explain_report(report, index, today);
         // Calculate item risk scores
-        for item in &mut report.items {
-            if let Some(pid) = item.plugin_id {
-                if let Some(plugin) = report.plugins.iter().find(|p| p.plugin_id == Some(pid)) {
-                    let cvss = plugin.cvss_base_score.unwrap_or(1.0) as f64;
-                    let vuln_pub_days = plugin
-                        .vuln_publication_date
-                        .map(|d| (today - d.date()).num_days() as f64)
-                        .unwrap_or(1.0);
-                    let exploitable_factor = if plugin.exploit_available.as_deref() == Some("true")
-                    {
-                        0.6
-                    } else {
-                        1.0
-                    };
-                    let score = (cvss * vuln_pub_days * 0.8) * exploitable_factor;
-                    item.risk_score = Some(score.round() as i32);
-                }
+        for (item, e) in report.items.iter_mut().zip(&risk.items) {
+            if let Some(e) = e {
+                item.risk_score = Some(e.total().round() as i32);
+            } else if item.suppressed == Some(true) {
+                item.risk_score = None;
             }
         }
         // Calculate plugin risk scores
-        for plugin in &mut report.plugins {
+        let items = &report.items;
+        report.plugins.par_iter_mut().for_each(|plugin| {
             if let Some(pid) = plugin.plugin_id {
-                let count = report
-                    .items
+                // Suppressed findings have no score and are not counted.
+                let mut scores = index
+                    .items_for_plugin(pid)
                     .iter()
-                    .filter(|i| i.plugin_id == Some(pid))
-                    .count();
-                if count > 0 {
-                    if let Some(score) = report
-                        .items
-                        .iter()
-                        .find(|i| i.plugin_id == Some(pid))
-                        .and_then(|i| i.risk_score)
-                    {
-                        plugin.risk_score = Some(score * count as i32);
-                    }
+                    .filter_map(|&idx| items[idx].risk_score);
+                if let Some(score) = scores.next() {
+                    plugin.risk_score = Some(score * (1 + scores.count() as i32));
                 }
             }
-        }
+        });
         // Calculate host risk scores (aggregate per-host item scores)
         let host_count = report.hosts.len();
-        for host in &mut report.hosts {
-            let sum: i32 = report
-                .items
+        report.hosts.par_iter_mut().for_each(|host| {
+            let unhosted = if host_count <= 1 { index.items_for_host(None) } else { &[] };
+            let sum: i32 = index
+                .items_for_host(Some(host.id))
                 .iter()
-                .filter(|i| i.host_id == Some(host.id) || (i.host_id.is_none() && host_count <= 1))
-                .filter_map(|i| i.risk_score)
+                .chain(unhosted)
+                .filter_map(|&idx| items[idx].risk_score)
                 .sum();
             host.risk_score = Some(sum);
-        }
+        });
     }
 }
 
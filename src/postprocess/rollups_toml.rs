@@ -1,13 +1,15 @@
-use super::{PluginEntry, PostProcess, PostProcessInfo};
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
+use crate::error::Error;
 use crate::parser::NessusReport;
 use crate::models::{Item, Plugin};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tracing::info;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RollupDef {
     plugin_id: i32,
     plugin_name: String,
@@ -16,63 +18,154 @@ struct RollupDef {
     plugin_ids: Vec<i32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RollupsFile {
     #[serde(default)]
     rollup: Vec<RollupDef>,
 }
 
+/// Locate the rollups file: `RISU_ROLLUPS_FILE`, then `./rollups.toml`,
+/// then `~/.risu/rollups.toml`.
 pub(crate) fn find_rollups_file() -> Option<PathBuf> {
-    if let Ok(p) = std::env::var("RISU_ROLLUPS_FILE") {
-        let pb = PathBuf::from(p);
-        if pb.exists() {
-            return Some(pb);
+    crate::config::find_config_file("RISU_ROLLUPS_FILE", "rollups.toml")
+}
+
+fn read_defs(path: &Path) -> Result<Vec<RollupDef>, Error> {
+    let txt = fs::read_to_string(path).map_err(|e| {
+        Error::Config(format!("failed to read rollups file '{}': {e}", path.display()))
+    })?;
+    let cfg: RollupsFile = toml::from_str(&txt).map_err(|e| {
+        Error::Config(format!("rollups file '{}': {e}", path.display()))
+    })?;
+    Ok(cfg.rollup)
+}
+
+fn load_defs() -> Vec<RollupDef> {
+    let Some(path) = find_rollups_file() else { return Vec::new(); };
+    match read_defs(&path) {
+        Ok(defs) => {
+            info!("Loaded rollups from {} ({} rules)", path.display(), defs.len());
+            defs
+        }
+        Err(e) => {
+            eprintln!("Failed to load rollups: {e}");
+            Vec::new()
         }
     }
-    let cwd = PathBuf::from("rollups.toml");
-    if cwd.exists() {
-        return Some(cwd);
-    }
-    if let Some(home) = std::env::var_os("HOME") {
-        let user = PathBuf::from(home).join(".risu").join("rollups.toml");
-        if user.exists() {
-            return Some(user);
+}
+
+/// A problem in a rollups file found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintProblem {
+    /// Several rollups use the same rollup `plugin_id`.
+    DuplicateRollup { plugin_id: i32, rollups: Vec<String> },
+    /// A rollup lists the same plugin IDs more than once.
+    RepeatedIds { rollup: String, plugin_ids: Vec<i32> },
+    /// A plugin ID is claimed by several rollups; only the first rolls it up.
+    SharedId { plugin_id: i32, rollups: Vec<String> },
+    /// A rollup lists plugin IDs missing from the plugin index.
+    UnknownIds { rollup: String, plugin_ids: Vec<i32> },
+}
+
+fn join_ids(ids: &[i32]) -> String {
+    ids.iter().map(i32::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for LintProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateRollup { plugin_id, rollups } => {
+                write!(f, "rollup plugin_id {plugin_id} is used by: {}", rollups.join("; "))
+            }
+            Self::RepeatedIds { rollup, plugin_ids } => {
+                write!(f, "'{rollup}' lists plugin IDs more than once: {}", join_ids(plugin_ids))
+            }
+            Self::SharedId { plugin_id, rollups } => {
+                write!(f, "plugin {plugin_id} is claimed by: {}", rollups.join("; "))
+            }
+            Self::UnknownIds { rollup, plugin_ids } => {
+                write!(f, "'{rollup}' lists plugins not in the index: {}", join_ids(plugin_ids))
+            }
         }
     }
-    None
 }
 
-fn load_defs() -> Vec<RollupDef> {
-    let Some(path) = find_rollups_file() else { return Vec::new(); };
-    match fs::read_to_string(&path) {
-        Ok(txt) => match toml::from_str::<RollupsFile>(&txt) {
-            Ok(cfg) => {
-                info!("Loaded rollups from {} ({} rules)", path.display(), cfg.rollup.len());
-                cfg.rollup
+fn lint_defs(defs: &[RollupDef], known: Option<&HashSet<i32>>) -> Vec<LintProblem> {
+    let mut problems = Vec::new();
+    let mut by_rollup_id: BTreeMap<i32, Vec<String>> = BTreeMap::new();
+    let mut by_plugin_id: BTreeMap<i32, Vec<String>> = BTreeMap::new();
+    for d in defs {
+        by_rollup_id.entry(d.plugin_id).or_default().push(d.plugin_name.clone());
+        let mut seen = HashSet::new();
+        let mut repeated = Vec::new();
+        for &pid in &d.plugin_ids {
+            if !seen.insert(pid) {
+                if !repeated.contains(&pid) {
+                    repeated.push(pid);
+                }
+            } else {
+                by_plugin_id.entry(pid).or_default().push(d.plugin_name.clone());
             }
-            Err(e) => {
-                eprintln!(
-                    "Failed to parse rollups file '{}': {}",
-                    path.display(),
-                    e
-                );
-                Vec::new()
+        }
+        if !repeated.is_empty() {
+            repeated.sort_unstable();
+            problems.push(LintProblem::RepeatedIds {
+                rollup: d.plugin_name.clone(),
+                plugin_ids: repeated,
+            });
+        }
+        if let Some(known) = known {
+            let mut unknown: Vec<i32> = seen.into_iter().filter(|p| !known.contains(p)).collect();
+            if !unknown.is_empty() {
+                unknown.sort_unstable();
+                problems.push(LintProblem::UnknownIds {
+                    rollup: d.plugin_name.clone(),
+                    plugin_ids: unknown,
+                });
             }
-        },
-        Err(e) => {
-            eprintln!(
-                "Failed to read rollups file '{}': {}",
-                path.display(),
-                e
-            );
-            Vec::new()
         }
     }
+    for (plugin_id, rollups) in by_rollup_id {
+        if rollups.len() > 1 {
+            problems.push(LintProblem::DuplicateRollup { plugin_id, rollups });
+        }
+    }
+    for (plugin_id, rollups) in by_plugin_id {
+        if rollups.len() > 1 {
+            problems.push(LintProblem::SharedId { plugin_id, rollups });
+        }
+    }
+    problems
+}
+
+/// Check the rollups file at `path` for duplicate rollup IDs, plugin IDs
+/// listed twice in a rollup or claimed by several rollups, and, when `known`
+/// is given, plugin IDs missing from the plugin index.
+pub fn lint(path: &Path, known: Option<&HashSet<i32>>) -> Result<Vec<LintProblem>, Error> {
+    Ok(lint_defs(&read_defs(path)?, known))
+}
+
+fn normalize_defs(mut defs: Vec<RollupDef>) -> Vec<RollupDef> {
+    for d in &mut defs {
+        d.plugin_ids.sort_unstable();
+        d.plugin_ids.dedup();
+    }
+    defs
+}
+
+/// The rollups file at `path` as TOML with each rollup's plugin IDs sorted and
+/// deduplicated. Rollups keep their file order, which decides which rollup
+/// gets a shared plugin ID.
+pub fn normalize(path: &Path) -> Result<String, Error> {
+    let file = RollupsFile { rollup: normalize_defs(read_defs(path)?) };
+    toml::to_string(&file)
+        .map_err(|e| Error::Config(format!("rollups file '{}': {e}", path.display())))
 }
 
 // Core rollup executor: per-host rollup items and metadata enrichment.
 fn run_rollup(
     report: &mut NessusReport,
+    index: &mut ReportIndex,
     plugin_id: i32,
     plugin_name: &str,
     item_name: &str,
@@ -80,11 +173,7 @@ fn run_rollup(
     plugin_ids: &[i32],
 ) {
     // Avoid duplicates
-    if report
-        .plugins
-        .iter()
-        .any(|p| p.plugin_id == Some(plugin_id))
-    {
+    if index.plugin_index(plugin_id).is_some() {
         return;
     }
 
@@ -93,25 +182,35 @@ fn run_rollup(
     let mut overall_max_sev = 0;
     let mut per_host_max: HashMap<i32, i32> = HashMap::new();
 
-    for item in &mut report.items {
-        if let Some(pid) = item.plugin_id {
-            if plugin_ids.contains(&pid) {
-                found_any = true;
-                if let Some(sev) = item.severity {
-                    if sev > overall_max_sev {
-                        overall_max_sev = sev;
-                    }
-                    if let Some(hid) = item.host_id {
-                        let e = per_host_max.entry(hid).or_insert(0);
-                        if sev > *e {
-                            *e = sev;
-                        }
-                    }
+    let mut matched: Vec<usize> = plugin_ids
+        .iter()
+        .flat_map(|pid| index.items_for_plugin(*pid))
+        .copied()
+        .collect();
+    // Keep report order, and touch items listed twice in `plugin_ids` once.
+    matched.sort_unstable();
+    matched.dedup();
+    for idx in matched {
+        let item = &mut report.items[idx];
+        // Suppressed findings and findings an earlier rollup claimed already
+        // keep their severity in `real_severity`.
+        if item.severity == Some(-1) {
+            continue;
+        }
+        found_any = true;
+        if let Some(sev) = item.severity {
+            if sev > overall_max_sev {
+                overall_max_sev = sev;
+            }
+            if let Some(hid) = item.host_id {
+                let e = per_host_max.entry(hid).or_insert(0);
+                if sev > *e {
+                    *e = sev;
                 }
-                item.real_severity = item.severity;
-                item.severity = Some(-1);
             }
         }
+        item.real_severity = item.severity;
+        item.severity = Some(-1);
     }
     if !found_any {
         return;
@@ -128,11 +227,12 @@ fn run_rollup(
     rollup.solution = Some("If possible, update to the latest version of the software.".to_string());
 
     // Enrich from underlying plugins
-    let candidates: Vec<&Plugin> = report
-        .plugins
-        .iter()
-        .filter(|p| p.plugin_id.map_or(false, |id| plugin_ids.contains(&id)))
-        .collect();
+    let mut candidate_idx: Vec<usize> =
+        plugin_ids.iter().filter_map(|pid| index.plugin_index(*pid)).collect();
+    candidate_idx.sort_unstable();
+    candidate_idx.dedup();
+    let candidates: Vec<&Plugin> =
+        candidate_idx.iter().map(|&idx| &report.plugins[idx]).collect();
 
     let newest_opt: Option<&Plugin> = candidates
         .iter()
@@ -245,7 +345,7 @@ fn run_rollup(
         rollup.default_account = Some(true);
     }
 
-    report.plugins.push(rollup);
+    index.push_plugin(report, rollup);
 
     if !per_host_max.is_empty() {
         for (hid, sev) in per_host_max.into_iter() {
@@ -255,7 +355,7 @@ fn run_rollup(
             item.plugin_name = Some(item_name.to_string());
             item.severity = Some(sev);
             item.rollup_finding = Some(true);
-            report.items.push(item);
+            index.push_item(report, item);
         }
     } else {
         let mut item = Item::default();
@@ -263,7 +363,7 @@ fn run_rollup(
         item.plugin_name = Some(item_name.to_string());
         item.severity = Some(overall_max_sev);
         item.rollup_finding = Some(true);
-        report.items.push(item);
+        index.push_item(report, item);
     }
 }
 
@@ -276,7 +376,7 @@ impl PostProcess for TomlRollups {
         PostProcessInfo { name: "rollups_toml", order: 990 }
     }
 
-    fn run(&self, report: &mut NessusReport) {
+    fn run(&self, report: &mut NessusReport, index: &mut ReportIndex) {
         let defs = load_defs();
         if defs.is_empty() {
             return;
@@ -284,6 +384,7 @@ impl PostProcess for TomlRollups {
         for d in defs {
             run_rollup(
                 report,
+                index,
                 d.plugin_id,
                 &d.plugin_name,
                 &d.item_name,
@@ -297,3 +398,83 @@ impl PostProcess for TomlRollups {
 inventory::submit! {
     PluginEntry { plugin: &TomlRollups }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(plugin_id: i32, name: &str, plugin_ids: &[i32]) -> RollupDef {
+        RollupDef {
+            plugin_id,
+            plugin_name: name.to_string(),
+            item_name: format!("Update {name}"),
+            description: format!("{name} Patch Rollup"),
+            plugin_ids: plugin_ids.to_vec(),
+        }
+    }
+
+    #[test]
+    fn lint_reports_duplicates_and_unknown_ids() {
+        let defs = vec![
+            def(-1, "Zeta", &[30, 10, 30, 20]),
+            def(-2, "alpha", &[20, 40]),
+            def(-1, "Beta", &[50]),
+        ];
+        let known: HashSet<i32> = [10, 20, 30, 40].into_iter().collect();
+        let problems = lint_defs(&defs, Some(&known));
+        assert_eq!(
+            problems,
+            [
+                LintProblem::RepeatedIds { rollup: "Zeta".into(), plugin_ids: vec![30] },
+                LintProblem::UnknownIds { rollup: "Beta".into(), plugin_ids: vec![50] },
+                LintProblem::DuplicateRollup {
+                    plugin_id: -1,
+                    rollups: vec!["Zeta".into(), "Beta".into()],
+                },
+                LintProblem::SharedId {
+                    plugin_id: 20,
+                    rollups: vec!["Zeta".into(), "alpha".into()],
+                },
+            ]
+        );
+
+        let normalized = normalize_defs(defs);
+        let names: Vec<&str> = normalized.iter().map(|d| d.plugin_name.as_str()).collect();
+        assert_eq!(names, ["Zeta", "alpha", "Beta"]);
+        assert_eq!(normalized[0].plugin_ids, [10, 20, 30]);
+    }
+
+    #[test]
+    fn shared_id_is_rolled_up_by_the_first_rollup_only() {
+        let mut report = NessusReport::default();
+        report.plugins.push(Plugin { plugin_id: Some(20), ..Plugin::default() });
+        report.items.push(Item {
+            host_id: Some(1),
+            plugin_id: Some(20),
+            severity: Some(3),
+            ..Item::default()
+        });
+        let mut index = ReportIndex::new(&report);
+        for d in [def(-1, "Zeta", &[20]), def(-2, "alpha", &[20])] {
+            run_rollup(
+                &mut report,
+                &mut index,
+                d.plugin_id,
+                &d.plugin_name,
+                &d.item_name,
+                &d.description,
+                &d.plugin_ids,
+            );
+        }
+
+        assert_eq!(report.items[0].severity, Some(-1));
+        assert_eq!(report.items[0].real_severity, Some(3));
+        let rollups: Vec<Option<i32>> = report
+            .items
+            .iter()
+            .filter(|i| i.rollup_finding == Some(true))
+            .map(|i| i.plugin_id)
+            .collect();
+        assert_eq!(rollups, [Some(-1)]);
+    }
+}
@@ -0,0 +1,268 @@
+//! Root-cause and severity rules loaded from TOML.
+//!
+//! The defaults in `default_rules.toml` are compiled in. A user rules file,
+//! found like the rollups file, is tried first and can turn the defaults off
+//! with `defaults = false`. Rules match plugins by ID, family, name regex
+//! and/or CPE prefix; every criterion given must match.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::models::Plugin;
+use crate::parser::NessusReport;
+
+const DEFAULT_RULES: &str = include_str!("default_rules.toml");
+
+#[derive(Debug, Default, Deserialize)]
+struct MatchDef {
+    plugin_id: Option<i32>,
+    #[serde(default)]
+    plugin_ids: Vec<i32>,
+    family: Option<String>,
+    name: Option<String>,
+    cpe: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RootCauseDef {
+    #[serde(flatten)]
+    matcher: MatchDef,
+    cause: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeverityDef {
+    #[serde(flatten)]
+    matcher: MatchDef,
+    severity: i32,
+}
+
+fn yes() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default = "yes")]
+    defaults: bool,
+    #[serde(default)]
+    root_cause: Vec<RootCauseDef>,
+    #[serde(default)]
+    severity: Vec<SeverityDef>,
+}
+
+/// What a rule looks at in a plugin.
+#[derive(Debug)]
+pub struct PluginMatcher {
+    plugin_ids: Vec<i32>,
+    family: Option<String>,
+    name: Option<Regex>,
+    cpe: Option<String>,
+}
+
+impl PluginMatcher {
+    fn from_def(def: MatchDef) -> Result<Self, String> {
+        let mut plugin_ids = def.plugin_ids;
+        plugin_ids.extend(def.plugin_id);
+        let name = def
+            .name
+            .map(|n| Regex::new(&format!("(?i){n}")).map_err(|e| format!("name: {e}")))
+            .transpose()?;
+        let matcher = Self {
+            plugin_ids,
+            family: def.family,
+            name,
+            cpe: def.cpe.map(|c| c.to_ascii_lowercase()),
+        };
+        if matcher.plugin_ids.is_empty()
+            && matcher.family.is_none()
+            && matcher.name.is_none()
+            && matcher.cpe.is_none()
+        {
+            return Err("rule has no plugin_id, family, name or cpe to match".to_string());
+        }
+        Ok(matcher)
+    }
+
+    /// Whether the plugin `plugin_id`, described by `plugin` when the report
+    /// has it and listing `cpes`, meets every criterion.
+    pub fn matches(&self, plugin_id: i32, plugin: Option<&Plugin>, cpes: &[&str]) -> bool {
+        if !self.plugin_ids.is_empty() && !self.plugin_ids.contains(&plugin_id) {
+            return false;
+        }
+        let family_ok = self.family.as_ref().is_none_or(|f| {
+            plugin
+                .and_then(|p| p.family_name.as_deref())
+                .is_some_and(|pf| pf.trim().eq_ignore_ascii_case(f.trim()))
+        });
+        let name_ok = self.name.as_ref().is_none_or(|re| {
+            plugin
+                .and_then(|p| p.plugin_name.as_deref())
+                .is_some_and(|n| re.is_match(n))
+        });
+        let cpe_ok = self.cpe.as_ref().is_none_or(|prefix| {
+            cpes.iter()
+                .any(|c| c.trim().to_ascii_lowercase().starts_with(prefix.as_str()))
+        });
+        family_ok && name_ok && cpe_ok
+    }
+}
+
+/// Root-cause and severity rules in the order they are tried.
+#[derive(Debug, Default)]
+pub struct Rules {
+    pub root_cause: Vec<(PluginMatcher, String)>,
+    pub severity: Vec<(PluginMatcher, i32)>,
+}
+
+impl Rules {
+    fn parse(txt: &str, origin: &str) -> Result<(Self, bool), crate::error::Error> {
+        let file: RulesFile = toml::from_str(txt)
+            .map_err(|e| crate::error::Error::Config(format!("rules file '{origin}': {e}")))?;
+        let mut rules = Self::default();
+        for (n, def) in file.root_cause.into_iter().enumerate() {
+            match PluginMatcher::from_def(def.matcher) {
+                Ok(m) => rules.root_cause.push((m, def.cause)),
+                Err(e) => eprintln!("Skipping root_cause rule {} in '{origin}': {e}", n + 1),
+            }
+        }
+        for (n, def) in file.severity.into_iter().enumerate() {
+            match PluginMatcher::from_def(def.matcher) {
+                Ok(m) => rules.severity.push((m, def.severity)),
+                Err(e) => eprintln!("Skipping severity rule {} in '{origin}': {e}", n + 1),
+            }
+        }
+        Ok((rules, file.defaults))
+    }
+
+    /// The compiled-in default rules.
+    pub fn defaults() -> Self {
+        Self::parse(DEFAULT_RULES, "default_rules.toml")
+            .expect("default rules are valid")
+            .0
+    }
+
+    /// Rules of the file at `path`, followed by the defaults unless the file
+    /// sets `defaults = false`.
+    pub fn from_file(path: &Path) -> Result<Self, crate::error::Error> {
+        let txt = std::fs::read_to_string(path)?;
+        let (mut rules, defaults) = Self::parse(&txt, &path.display().to_string())?;
+        if defaults {
+            let d = Self::defaults();
+            rules.root_cause.extend(d.root_cause);
+            rules.severity.extend(d.severity);
+        }
+        Ok(rules)
+    }
+
+    /// The user rules file when there is one, otherwise the defaults. A file
+    /// that fails to load is reported and the defaults are used.
+    pub fn load() -> Self {
+        let Some(path) = find_rules_file() else {
+            return Self::defaults();
+        };
+        match Self::from_file(&path) {
+            Ok(rules) => {
+                info!("Loaded rules from {}", path.display());
+                rules
+            }
+            Err(e) => {
+                eprintln!("Failed to load rules, using the defaults: {e}");
+                Self::defaults()
+            }
+        }
+    }
+
+    /// Root cause of the first matching rule.
+    pub fn root_cause(
+        &self,
+        plugin_id: i32,
+        plugin: Option<&Plugin>,
+        cpes: &[&str],
+    ) -> Option<&str> {
+        self.root_cause
+            .iter()
+            .find(|(m, _)| m.matches(plugin_id, plugin, cpes))
+            .map(|(_, cause)| cause.as_str())
+    }
+
+    /// Severity of the first matching rule.
+    pub fn severity(
+        &self,
+        plugin_id: i32,
+        plugin: Option<&Plugin>,
+        cpes: &[&str],
+    ) -> Option<i32> {
+        self.severity
+            .iter()
+            .find(|(m, _)| m.matches(plugin_id, plugin, cpes))
+            .map(|(_, sev)| *sev)
+    }
+}
+
+/// Locate the rules file: `RISU_RULES_FILE`, then `./rules.toml`, then
+/// `~/.risu/rules.toml`.
+pub(crate) fn find_rules_file() -> Option<PathBuf> {
+    crate::config::find_config_file("RISU_RULES_FILE", "rules.toml")
+}
+
+/// CPE references of each plugin ID in `report`.
+pub fn plugin_cpes(report: &NessusReport) -> HashMap<i32, Vec<&str>> {
+    let mut cpes: HashMap<i32, Vec<&str>> = HashMap::new();
+    for r in &report.references {
+        if let (Some(pid), Some(value)) = (r.plugin_id, r.value.as_deref())
+            && r.source.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("cpe"))
+        {
+            let list = cpes.entry(pid).or_default();
+            if !list.contains(&value) {
+                list.push(value);
+            }
+        }
+    }
+    cpes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_rules_come_before_defaults() {
+        let (mut rules, defaults) = Rules::parse(
+            r#"
+            [[root_cause]]
+            family = "Web Servers"
+            cpe = "cpe:/a:apache:http_server"
+            cause = "Vendor Support"
+
+            [[severity]]
+            name = "^SSL Version 2"
+            severity = 1
+            "#,
+            "test",
+        )
+        .unwrap();
+        assert!(defaults);
+        let d = Rules::defaults();
+        rules.root_cause.extend(d.root_cause);
+        rules.severity.extend(d.severity);
+
+        let plugin = Plugin {
+            plugin_id: Some(20007),
+            plugin_name: Some("SSL Version 2 and 3 Protocol Detection".into()),
+            family_name: Some("web servers".into()),
+            ..Plugin::default()
+        };
+        let apache = ["cpe:/a:apache:http_server:2.4.1"];
+        assert_eq!(rules.root_cause(1, Some(&plugin), &apache), Some("Vendor Support"));
+        assert_eq!(rules.root_cause(1, Some(&plugin), &[]), None);
+        assert_eq!(rules.root_cause(22194, None, &[]), Some("Vendor Patch"));
+        assert_eq!(rules.severity(20007, Some(&plugin), &[]), Some(1));
+        assert_eq!(rules.severity(20007, None, &[]), Some(2));
+        assert_eq!(rules.severity(41028, None, &[]), Some(0));
+    }
+}
@@ -1,7 +1,17 @@
 use std::net::IpAddr;
 
 use crate::parser::NessusReport;
-use super::{PostProcess, PostProcessInfo, PluginEntry};
+use super::{PostProcess, PostProcessInfo, PluginEntry, ReportIndex};
+
+/// Sort `report.hosts` by IP address. Host ids are left as they are, so
+/// hosts must be looked up by id rather than position afterwards.
+pub fn sort(report: &mut NessusReport) {
+    report.hosts.sort_by(|a, b| {
+        let ia = a.ip.as_ref().and_then(|s| s.parse::<IpAddr>().ok());
+        let ib = b.ip.as_ref().and_then(|s| s.parse::<IpAddr>().ok());
+        ia.cmp(&ib)
+    });
+}
 
 struct SortHosts;
 
@@ -10,12 +20,8 @@ impl PostProcess for SortHosts {
         PostProcessInfo { name: "sort_hosts", order: 20 }
     }
 
-    fn run(&self, report: &mut NessusReport) {
-        report.hosts.sort_by(|a, b| {
-            let ia = a.ip.as_ref().and_then(|s| s.parse::<IpAddr>().ok());
-            let ib = b.ip.as_ref().and_then(|s| s.parse::<IpAddr>().ok());
-            ia.cmp(&ib)
-        });
+    fn run(&self, report: &mut NessusReport, _index: &mut ReportIndex) {
+        sort(report);
     }
 }
 
@@ -0,0 +1,26 @@
+use super::{PluginEntry, PostProcess, PostProcessInfo, ReportIndex};
+use crate::analysis::suppression;
+use crate::parser::NessusReport;
+use chrono::Local;
+
+/// Hides findings covered by the accepted-risk register; see
+/// [`suppression`].
+struct Suppressions;
+
+impl PostProcess for Suppressions {
+    fn info(&self) -> PostProcessInfo {
+        // After severities are final and before risk scoring.
+        PostProcessInfo {
+            name: "suppressions",
+            order: 47,
+        }
+    }
+
+    fn run(&self, report: &mut NessusReport, _index: &mut ReportIndex) {
+        suppression::apply_register(report, Local::now().naive_local().date());
+    }
+}
+
+inventory::submit! {
+    PluginEntry { plugin: &Suppressions }
+}
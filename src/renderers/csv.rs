@@ -1,10 +1,13 @@
 use std::{error::Error, io::Write};
 
-use super::Renderer;
+use super::{Renderer, TableOptions};
 
 /// Renderer that outputs simple CSV files using the `csv` crate.
+///
+/// Free-form text becomes a single-column record while tables are written as
+/// one record per row, so the output can be consumed by spreadsheet tools.
 pub struct CsvRenderer {
-    rows: Vec<String>,
+    rows: Vec<Vec<String>>,
 }
 
 impl CsvRenderer {
@@ -29,33 +32,60 @@ impl CsvRenderer {
 }
 
 impl Renderer for CsvRenderer {
-    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
-        self.rows.push(text.to_string());
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.rows.push(vec![text.to_string()]);
         Ok(())
     }
 
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         // CSV output has no pages; this is a no-op.
         Ok(())
     }
 
-    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
-        let mut wtr = csv::Writer::from_writer(writer);
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut wtr = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(writer);
         for row in &self.rows {
-            wtr.write_record(&[row])?;
+            wtr.write_record(row)?;
         }
         wtr.flush()?;
         Ok(())
     }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        _options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // Widths and alignment have no meaning in CSV.
+        self.rows.push(headers.iter().map(|h| h.to_string()).collect());
+        self.rows.extend(rows.iter().cloned());
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::CsvRenderer;
+    use crate::renderer::Renderer;
 
     #[test]
     fn headers_match_ruby() {
         let ruby_headers: &[&str] = &["IP Address", "FQDN", "Netbios Name", "MAC Address", "Finding", "Risk Factor", "CVSS Base Score", "Solution"];
         assert_eq!(CsvRenderer::headers(), ruby_headers);
     }
+
+    #[test]
+    fn table_writes_one_record_per_row() {
+        let mut r = CsvRenderer::new();
+        r.text("Title").unwrap();
+        r.table(&["Host", "Port"], &[vec!["10.0.0.1".into(), "22".into()]])
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(s, "Title\nHost,Port\n10.0.0.1,22\n");
+    }
 }
@@ -0,0 +1,213 @@
+use std::{error::Error, io::Write};
+
+use super::{Align, Renderer, TableOptions};
+
+/// Stylesheet embedded in every document so the output is a single file.
+const STYLESHEET: &str = "\
+body { font-family: Helvetica, Arial, sans-serif; font-size: 14px; color: #222; \
+max-width: 1100px; margin: 2em auto; padding: 0 1em; line-height: 1.45; }
+h1, h2, h3, h4 { color: #1f3a5f; }
+h1 { border-bottom: 2px solid #1f3a5f; padding-bottom: .2em; }
+nav.toc { background: #f4f6f9; border: 1px solid #d8dee6; padding: .5em 1.5em; }
+nav.toc ul { list-style: none; padding-left: 1em; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; }
+th, td { border: 1px solid #c9d1db; padding: 4px 8px; vertical-align: top; }
+th { background: #1f3a5f; color: #fff; }
+tr:nth-child(even) td { background: #f7f9fb; }
+td.center { text-align: center; }
+td.right { text-align: right; }
+p { white-space: pre-wrap; }
+img { max-width: 100%; }
+@media print { nav.toc { display: none; } .page-break { page-break-after: always; } }
+";
+
+/// Renderer that produces a self-contained HTML document with an embedded
+/// stylesheet, a table of contents and inline images.
+pub struct HtmlRenderer {
+    title: String,
+    body: String,
+    /// `(level, text, anchor)` for every heading, in document order.
+    headings: Vec<(usize, String, String)>,
+}
+
+impl HtmlRenderer {
+    /// Create a new HTML renderer with the given document title.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            body: String::new(),
+            headings: Vec::new(),
+        }
+    }
+
+    /// Escape characters with special meaning in HTML text and attributes.
+    fn escape(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&#39;"),
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Build a unique anchor for the heading `text`.
+    fn anchor(&self, text: &str) -> String {
+        let mut slug = String::new();
+        for c in text.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug = slug.trim_matches('-');
+        format!("sec-{}-{}", self.headings.len() + 1, slug)
+    }
+
+    fn table_of_contents(&self) -> String {
+        if self.headings.is_empty() {
+            return String::new();
+        }
+        let mut toc = String::from("<nav class=\"toc\">\n<h2>Contents</h2>\n<ul>\n");
+        for (level, text, anchor) in &self.headings {
+            toc.push_str(&format!(
+                "<li style=\"margin-left: {}em\"><a href=\"#{anchor}\">{}</a></li>\n",
+                level.saturating_sub(1),
+                Self::escape(text)
+            ));
+        }
+        toc.push_str("</ul>\n</nav>\n");
+        toc
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.body.push_str(&format!("<p>{}</p>\n", Self::escape(text)));
+        Ok(())
+    }
+
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.body.push_str("<div class=\"page-break\"></div>\n");
+        Ok(())
+    }
+
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let title = Self::escape(&self.title);
+        write!(
+            writer,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>\n{STYLESHEET}</style>\n</head>\n<body>\n{}{}\
+             </body>\n</html>\n",
+            self.table_of_contents(),
+            self.body
+        )?;
+        Ok(())
+    }
+
+    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let level = level.clamp(1, 6);
+        let anchor = self.anchor(text);
+        self.body.push_str(&format!(
+            "<h{level} id=\"{anchor}\">{}</h{level}>\n",
+            Self::escape(text)
+        ));
+        self.headings.push((level, text.to_string(), anchor));
+        Ok(())
+    }
+
+    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if data_uri.starts_with("data:image/") && data_uri.contains(";base64,") {
+            self.body
+                .push_str(&format!("<img src=\"{}\" alt=\"\">\n", Self::escape(data_uri)));
+            return Ok(());
+        }
+        self.text(data_uri)
+    }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let columns = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        self.body.push_str("<table>\n<colgroup>");
+        for f in options.fractions(columns) {
+            self.body
+                .push_str(&format!("<col style=\"width: {:.0}%\">", f * 100.0));
+        }
+        self.body.push_str("</colgroup>\n");
+        if !headers.is_empty() {
+            self.body.push_str("<thead><tr>");
+            for h in headers {
+                self.body.push_str(&format!("<th>{}</th>", Self::escape(h)));
+            }
+            self.body.push_str("</tr></thead>\n");
+        }
+        self.body.push_str("<tbody>\n");
+        for row in rows {
+            self.body.push_str("<tr>");
+            for (i, cell) in row.iter().enumerate() {
+                let class = match options.align_of(i) {
+                    Align::Left => "",
+                    Align::Center => " class=\"center\"",
+                    Align::Right => " class=\"right\"",
+                };
+                self.body
+                    .push_str(&format!("<td{class}>{}</td>", Self::escape(cell)));
+            }
+            self.body.push_str("</tr>\n");
+        }
+        self.body.push_str("</tbody>\n</table>\n");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlRenderer;
+    use crate::renderer::{Align, Renderer, TableOptions};
+
+    fn render(r: &mut HtmlRenderer) -> String {
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn headings_link_from_table_of_contents() {
+        let mut r = HtmlRenderer::new("Scan <Report>");
+        r.heading(1, "Top Findings").unwrap();
+        r.heading(2, "Top Findings").unwrap();
+        let s = render(&mut r);
+        assert!(s.contains("<title>Scan &lt;Report&gt;</title>"));
+        assert!(s.contains("<a href=\"#sec-1-top-findings\">Top Findings</a>"));
+        assert!(s.contains("<h1 id=\"sec-1-top-findings\">"));
+        assert!(s.contains("<h2 id=\"sec-2-top-findings\">"));
+        assert!(s.contains("<style>"));
+    }
+
+    #[test]
+    fn escapes_scanner_text_and_inlines_images() {
+        let mut r = HtmlRenderer::new("Report");
+        r.text("<script>alert('x')</script>").unwrap();
+        let opts = TableOptions::default().align(&[Align::Left, Align::Right]);
+        r.table_with(&["Plugin", "Count"], &[vec!["A & B".into(), "4".into()]], &opts)
+            .unwrap();
+        r.image_data_uri("data:image/png;base64,AAAA").unwrap();
+        let s = render(&mut r);
+        assert!(!s.contains("<script>"));
+        assert!(s.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;"));
+        assert!(s.contains("<td>A &amp; B</td><td class=\"right\">4</td>"));
+        assert!(s.contains("<img src=\"data:image/png;base64,AAAA\""));
+    }
+}
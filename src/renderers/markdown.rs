@@ -0,0 +1,196 @@
+use std::{
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use base64::engine::general_purpose;
+use base64::Engine;
+
+use super::{Align, Renderer, TableOptions};
+
+/// Renderer that emits GitHub/GitLab flavoured Markdown.
+///
+/// Images are embedded as data URIs by default. A renderer created with
+/// [`MarkdownRenderer::with_sidecar_images`] instead writes each image to a
+/// PNG (or other image) file next to the output and links to it.
+pub struct MarkdownRenderer {
+    content: String,
+    /// Output path whose directory and file stem name the sidecar images.
+    sidecar: Option<PathBuf>,
+    images: Vec<(String, Vec<u8>)>,
+    /// Number of images emitted so far, inline or sidecar.
+    figures: usize,
+}
+
+impl MarkdownRenderer {
+    pub fn new() -> Self {
+        Self {
+            content: String::new(),
+            sidecar: None,
+            images: Vec::new(),
+            figures: 0,
+        }
+    }
+
+    /// Write images as files alongside `output` instead of inline data URIs.
+    pub fn with_sidecar_images(output: &Path) -> Self {
+        Self {
+            sidecar: Some(output.to_path_buf()),
+            ..Self::new()
+        }
+    }
+
+    /// Escape a table cell: pipes would end the cell and line breaks the row.
+    fn escape_cell(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    }
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.content.push_str(text);
+        self.content.push_str("\n\n");
+        Ok(())
+    }
+
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.content.push_str("---\n\n");
+        Ok(())
+    }
+
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(output) = &self.sidecar {
+            let dir = output.parent().unwrap_or_else(|| Path::new(""));
+            for (name, bytes) in &self.images {
+                fs::write(dir.join(name), bytes)?;
+            }
+        }
+        writer.write_all(self.content.as_bytes())?;
+        Ok(())
+    }
+
+    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let marks = "#".repeat(level.clamp(1, 6));
+        self.content.push_str(&format!("{marks} {}\n\n", text.trim()));
+        Ok(())
+    }
+
+    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(output) = &self.sidecar else {
+            self.figures += 1;
+            let figure = self.figures;
+            self.content.push_str(&format!("![Figure {figure}]({data_uri})\n\n"));
+            return Ok(());
+        };
+        let decoded = data_uri
+            .strip_prefix("data:image/")
+            .and_then(|rest| rest.split_once(";base64,"))
+            .and_then(|(format, data)| {
+                general_purpose::STANDARD
+                    .decode(data)
+                    .ok()
+                    .map(|bytes| (format.to_string(), bytes))
+            });
+        let Some((format, bytes)) = decoded else {
+            return self.text(data_uri);
+        };
+        self.figures += 1;
+        let figure = self.figures;
+        let ext = if format == "svg+xml" { "svg" } else { format.as_str() };
+        let stem = output
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("report");
+        let name = format!("{stem}-figure-{figure}.{ext}");
+        self.content.push_str(&format!("![Figure {figure}]({name})\n\n"));
+        self.images.push((name, bytes));
+        Ok(())
+    }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let columns = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        if columns == 0 {
+            return Ok(());
+        }
+        // Markdown tables require a header row; pad with blanks if needed.
+        let header: Vec<String> = (0..columns)
+            .map(|i| headers.get(i).map(|h| Self::escape_cell(h)).unwrap_or_default())
+            .collect();
+        let rule: Vec<&str> = (0..columns)
+            .map(|i| match options.align_of(i) {
+                Align::Left => "---",
+                Align::Center => ":---:",
+                Align::Right => "---:",
+            })
+            .collect();
+        self.content.push_str(&format!("| {} |\n", header.join(" | ")));
+        self.content.push_str(&format!("| {} |\n", rule.join(" | ")));
+        for row in rows {
+            let cells: Vec<String> = (0..columns)
+                .map(|i| row.get(i).map(|c| Self::escape_cell(c)).unwrap_or_default())
+                .collect();
+            self.content.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        self.content.push('\n');
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MarkdownRenderer;
+    use crate::renderer::{Align, Renderer, TableOptions};
+
+    fn render(r: &mut MarkdownRenderer) -> String {
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn writes_headings_paragraphs_and_tables() {
+        let mut r = MarkdownRenderer::new();
+        r.heading(2, "Top Findings").unwrap();
+        r.text("Hosts were scanned.").unwrap();
+        let opts = TableOptions::default().align(&[Align::Left, Align::Right]);
+        r.table_with(&["Plugin", "Count"], &[vec!["a|b\nc".into(), "4".into()]], &opts)
+            .unwrap();
+        r.image_data_uri("data:image/png;base64,AAAA").unwrap();
+        r.image_data_uri("data:image/png;base64,BBBB").unwrap();
+        let s = render(&mut r);
+        assert!(s.starts_with("## Top Findings\n\nHosts were scanned.\n\n"));
+        assert!(s.contains("| Plugin | Count |\n| --- | ---: |\n| a\\|b<br>c | 4 |\n"));
+        assert!(s.contains("![Figure 1](data:image/png;base64,AAAA)"));
+        assert!(s.contains("![Figure 2](data:image/png;base64,BBBB)"));
+    }
+
+    #[test]
+    fn writes_sidecar_images_next_to_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("report.md");
+        let mut r = MarkdownRenderer::with_sidecar_images(&output);
+        r.image_data_uri("data:image/png;base64,iVBORw0K").unwrap();
+        let s = render(&mut r);
+        assert!(s.contains("![Figure 1](report-figure-1.png)"));
+        let bytes = std::fs::read(dir.path().join("report-figure-1.png")).unwrap();
+        assert_eq!(&bytes[1..4], b"PNG");
+    }
+}
@@ -2,30 +2,135 @@ use std::{error::Error, io::Write};
 
 mod pdf;
 mod csv;
+mod html;
+mod markdown;
 mod nil;
 mod rtf;
 mod typst;
+mod xlsx;
 
 pub use csv::CsvRenderer;
+pub use html::HtmlRenderer;
+pub use markdown::MarkdownRenderer;
 pub use nil::NilRenderer;
 pub use pdf::PdfRenderer;
 pub use rtf::RtfRenderer;
 pub use typst::TypstRenderer;
+pub use xlsx::XlsxRenderer;
+
+/// Renderer names accepted by `--renderer`, with the file extension each
+/// one is selected for when no renderer is given.
+pub const RENDERERS: &[(&str, &str)] = &[
+    ("pdf", "pdf"),
+    ("csv", "csv"),
+    ("html", "html"),
+    ("markdown", "md"),
+    ("rtf", "rtf"),
+    ("typst", "typ"),
+    ("xlsx", "xlsx"),
+    ("nil", ""),
+];
+
+/// Horizontal alignment of the cells in a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Optional layout hints for [`Renderer::table_with`].
+///
+/// Both vectors are indexed by column. Missing entries fall back to an equal
+/// share of the available width and left alignment respectively.
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    /// Relative column widths, e.g. `[3.0, 1.0]` gives the first column three
+    /// quarters of the table width.
+    pub widths: Vec<f64>,
+    /// Alignment of each column.
+    pub align: Vec<Align>,
+}
+
+impl TableOptions {
+    /// Set relative column widths.
+    pub fn widths(mut self, widths: &[f64]) -> Self {
+        self.widths = widths.to_vec();
+        self
+    }
+
+    /// Set per-column alignment.
+    pub fn align(mut self, align: &[Align]) -> Self {
+        self.align = align.to_vec();
+        self
+    }
+
+    /// Alignment for column `idx`.
+    pub fn align_of(&self, idx: usize) -> Align {
+        self.align.get(idx).copied().unwrap_or_default()
+    }
+
+    /// Column widths normalised so they sum to `1.0`.
+    pub fn fractions(&self, columns: usize) -> Vec<f64> {
+        let raw: Vec<f64> = (0..columns)
+            .map(|i| match self.widths.get(i) {
+                Some(w) if *w > 0.0 => *w,
+                _ => 1.0,
+            })
+            .collect();
+        let total: f64 = raw.iter().sum();
+        raw.into_iter().map(|w| w / total).collect()
+    }
+}
 
 /// Trait implemented by renderers that output to various formats.
 pub trait Renderer {
     /// Write free-form text to the current output position.
-    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>>;
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
     /// Begin a new page in the output, if supported.
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>>;
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>>;
     /// Finalize the document and write it to the provided writer.
-    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>>;
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>>;
     /// Record a heading for navigation structures.
-    fn heading(&mut self, _level: usize, text: &str) -> Result<(), Box<dyn Error>> {
+    fn heading(&mut self, _level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.text(text)
     }
     /// Embed an image provided as a data URI. Default fallback prints the URI.
-    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error>> {
+    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.text(data_uri)
     }
+    /// Emit a table with a header row followed by `rows`.
+    fn table(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.table_with(headers, rows, &TableOptions::default())
+    }
+    /// Emit a table using explicit column widths and alignment. The default
+    /// fallback writes each row as a line of ` | ` separated text.
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        _options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.text(&headers.join(" | "))?;
+        for row in rows {
+            self.text(&row.join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TableOptions;
+
+    #[test]
+    fn fractions_fill_missing_widths() {
+        let opts = TableOptions::default().widths(&[2.0]);
+        assert_eq!(opts.fractions(3), vec![0.5, 0.25, 0.25]);
+    }
 }
@@ -14,15 +14,15 @@ impl NilRenderer {
 }
 
 impl Renderer for NilRenderer {
-    fn text(&mut self, _text: &str) -> Result<(), Box<dyn Error>> {
+    fn text(&mut self, _text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         Ok(())
     }
 
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         Ok(())
     }
 
-    fn save(&mut self, _writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    fn save(&mut self, _writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
         Ok(())
     }
 }
@@ -1,13 +1,13 @@
 use std::{error::Error, io::Write};
 
 use printpdf::{
-    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
-    PdfPageIndex,
+    BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+    PdfPageIndex, Point,
 };
 use base64::engine::general_purpose;
 use base64::Engine;
 
-use super::Renderer;
+use super::{Align, Renderer, TableOptions};
 use printpdf::image_crate::GenericImageView;
 
 /// Renderer that produces PDF documents using the `printpdf` crate.
@@ -16,6 +16,7 @@ pub struct PdfRenderer {
     toc_layer: PdfLayerReference,
     layer: PdfLayerReference,
     font: IndirectFontRef,
+    bold_font: IndirectFontRef,
     cursor_y: Mm,
     headings: Vec<(usize, String, usize)>,
     page: PdfPageIndex,
@@ -29,6 +30,9 @@ impl PdfRenderer {
         let font = doc
             .add_builtin_font(BuiltinFont::Helvetica)
             .expect("builtin font");
+        let bold_font = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .expect("builtin font");
         let (page, layer_idx) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
         let toc_layer = doc.get_page(toc_page).get_layer(toc_layer);
         let layer = doc.get_page(page).get_layer(layer_idx);
@@ -37,16 +41,72 @@ impl PdfRenderer {
             toc_layer,
             layer,
             font,
+            bold_font,
             cursor_y: Mm(287.0),
             headings: Vec::new(),
             page,
             page_num: 2,
         }
     }
+
+    /// Draw one table row at the cursor, truncating cells that do not fit
+    /// their column. `columns` holds the left edge and width of each column.
+    fn table_row(
+        &mut self,
+        cells: &[&str],
+        columns: &[(f64, f64)],
+        options: &TableOptions,
+        bold: bool,
+    ) {
+        let font = if bold { &self.bold_font } else { &self.font };
+        for (idx, cell) in cells.iter().enumerate() {
+            let Some(&(x, width)) = columns.get(idx) else {
+                break;
+            };
+            let max_chars = ((width - 2.0 * TABLE_CELL_PAD) / TABLE_CHAR_WIDTH).max(1.0) as usize;
+            let text: String = if cell.chars().count() > max_chars {
+                let mut t: String = cell.chars().take(max_chars.saturating_sub(3)).collect();
+                t.push_str("...");
+                t
+            } else {
+                cell.to_string()
+            };
+            let text_w = text.chars().count() as f64 * TABLE_CHAR_WIDTH;
+            let text_x = match options.align_of(idx) {
+                Align::Left => x + TABLE_CELL_PAD,
+                Align::Center => x + (width - text_w) / 2.0,
+                Align::Right => x + width - TABLE_CELL_PAD - text_w,
+            };
+            self.layer
+                .use_text(text, TABLE_FONT_SIZE, Mm(text_x), self.cursor_y, font);
+        }
+        let (left, _) = columns.first().copied().unwrap_or((10.0, 0.0));
+        let right = columns.last().map(|(x, w)| x + w).unwrap_or(200.0);
+        let rule_y = self.cursor_y - Mm(2.0);
+        self.layer.set_outline_thickness(if bold { 0.8 } else { 0.3 });
+        self.layer.add_shape(Line {
+            points: vec![
+                (Point::new(Mm(left), rule_y), false),
+                (Point::new(Mm(right), rule_y), false),
+            ],
+            has_stroke: true,
+            ..Default::default()
+        });
+        self.cursor_y -= Mm(TABLE_ROW_HEIGHT);
+    }
 }
 
+/// Font size used for table cells.
+const TABLE_FONT_SIZE: f64 = 10.0;
+/// Approximate average Helvetica glyph width in mm at [`TABLE_FONT_SIZE`].
+const TABLE_CHAR_WIDTH: f64 = 1.9;
+/// Horizontal padding inside each cell in mm.
+const TABLE_CELL_PAD: f64 = 1.5;
+/// Height of a table row in mm.
+const TABLE_ROW_HEIGHT: f64 = 7.0;
+
 impl Renderer for PdfRenderer {
-    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         // Detect data URI images and embed them instead of printing the string.
         if let Some(pos) = text.find(",") {
             let (prefix, data) = text.split_at(pos + 1);
@@ -87,7 +147,7 @@ impl Renderer for PdfRenderer {
         Ok(())
     }
 
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         let doc = self.doc.as_ref().expect("document");
         let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
         self.layer = doc.get_page(page).get_layer(layer);
@@ -97,7 +157,7 @@ impl Renderer for PdfRenderer {
         Ok(())
     }
 
-    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
         if let Some(doc) = self.doc.take() {
             let mut toc_y = Mm(287.0);
             self.toc_layer
@@ -116,11 +176,45 @@ impl Renderer for PdfRenderer {
         Ok(())
     }
 
-    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error>> {
+    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         if let Some(doc) = self.doc.as_ref() {
             doc.add_bookmark(text, self.page);
             self.headings.push((level, text.to_string(), self.page_num));
         }
         self.text(text)
     }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let count = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        let mut columns = Vec::with_capacity(count);
+        let mut x = 10.0;
+        for frac in options.fractions(count) {
+            let width = frac * 190.0;
+            columns.push((x, width));
+            x += width;
+        }
+        if !headers.is_empty() {
+            self.table_row(headers, &columns, options, true);
+        }
+        for row in rows {
+            if self.cursor_y < Mm(10.0 + TABLE_ROW_HEIGHT) {
+                // Continue on a fresh page and repeat the header row.
+                self.start_new_page()?;
+                if !headers.is_empty() {
+                    self.table_row(headers, &columns, options, true);
+                }
+            }
+            let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            self.table_row(&cells, &columns, options, false);
+        }
+        self.cursor_y -= Mm(9.0);
+        Ok(())
+    }
 }
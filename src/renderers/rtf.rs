@@ -1,6 +1,9 @@
 use std::{error::Error, io::Write};
 
-use super::Renderer;
+use base64::engine::general_purpose;
+use base64::Engine;
+
+use super::{Align, Renderer, TableOptions};
 
 /// Renderer that produces RTF documents.
 pub struct RtfRenderer {
@@ -21,56 +24,103 @@ impl RtfRenderer {
             .replace('}', "\\}")
     }
 
-    /// Insert a simple table. Each inner slice is a row.
-    pub fn table(&mut self, rows: &[Vec<&str>]) -> Result<(), Box<dyn Error>> {
-        for row in rows {
-            self.content.push_str("\\trowd ");
-            let mut cellx = 1000;
-            for _ in row {
-                self.content.push_str(&format!("\\cellx{}", cellx));
-                cellx += 1000;
-            }
-            for cell in row {
-                let esc = Self::escape(cell);
-                self.content.push_str(&format!("{esc}\\cell "));
+    /// Append a single table row. `cellx` holds the right boundary of each
+    /// cell in twips.
+    fn table_row(&mut self, cells: &[&str], cellx: &[u32], options: &TableOptions, bold: bool) {
+        self.content.push_str("\\trowd\\trgaph108 ");
+        for x in cellx {
+            self.content.push_str(&format!("\\clbrdrb\\brdrs\\cellx{x}"));
+        }
+        for (idx, cell) in cells.iter().enumerate() {
+            let align = match options.align_of(idx) {
+                Align::Left => "\\ql",
+                Align::Center => "\\qc",
+                Align::Right => "\\qr",
+            };
+            let esc = Self::escape(cell);
+            if bold {
+                self.content
+                    .push_str(&format!("\\pard\\intbl{align} {{\\b {esc}}}\\cell "));
+            } else {
+                self.content
+                    .push_str(&format!("\\pard\\intbl{align} {esc}\\cell "));
             }
-            self.content.push_str("\\row\n");
         }
-        Ok(())
+        self.content.push_str("\\row\n");
     }
 
     /// Embed an image from raw bytes (PNG/JPEG).
-    pub fn image(&mut self, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    pub fn image(&mut self, data: &[u8]) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let blip = if data.starts_with(&[0xff, 0xd8]) { "jpegblip" } else { "pngblip" };
         let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
         self.content
-            .push_str(&format!("{{\\pict\\pngblip {hex}}}\\par\n"));
+            .push_str(&format!("{{\\pict\\{blip} {hex}}}\\par\n"));
         Ok(())
     }
 }
 
 impl Renderer for RtfRenderer {
-    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         let esc = Self::escape(text);
         self.content.push_str(&format!("{esc}\\par\n"));
         Ok(())
     }
 
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.content.push_str("\\page\n");
         Ok(())
     }
 
-    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.content.push('}');
         writer.write_all(self.content.as_bytes())?;
         Ok(())
     }
 
-    fn heading(&mut self, _level: usize, text: &str) -> Result<(), Box<dyn Error>> {
+    fn heading(&mut self, _level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         let esc = Self::escape(text);
         self.content.push_str(&format!("{{\\b {esc}\\b0}}\\par\n"));
         Ok(())
     }
+
+    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(data) = data_uri
+            .strip_prefix("data:image/")
+            .and_then(|rest| rest.split_once(";base64,"))
+            .and_then(|(_, data)| general_purpose::STANDARD.decode(data).ok())
+        {
+            return self.image(&data);
+        }
+        self.text(data_uri)
+    }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // Letter paper with one inch margins leaves 6.5in (9360 twips).
+        const TABLE_WIDTH: f64 = 9360.0;
+        let columns = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        let mut cellx = Vec::with_capacity(columns);
+        let mut right = 0.0;
+        for frac in options.fractions(columns) {
+            right += frac * TABLE_WIDTH;
+            cellx.push(right.round() as u32);
+        }
+        if !headers.is_empty() {
+            self.table_row(headers, &cellx, options, true);
+        }
+        for row in rows {
+            let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            self.table_row(&cells, &cellx, options, false);
+        }
+        self.content.push_str("\\pard\\par\n");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -89,4 +139,31 @@ mod tests {
         assert!(s.starts_with("{\\rtf1"));
         assert!(s.contains("Title"));
     }
+
+    #[test]
+    fn writes_table_rows() {
+        let mut r = RtfRenderer::new();
+        r.table(&["Host", "Port"], &[vec!["10.0.0.1".into(), "22".into()]])
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("\\cellx4680\\clbrdrb\\brdrs\\cellx9360"));
+        assert!(s.contains("{\\b Host}\\cell"));
+        assert!(s.contains("10.0.0.1\\cell"));
+        assert_eq!(s.matches("\\row").count(), 2);
+    }
+
+    #[test]
+    fn embeds_data_uri_images() {
+        let mut r = RtfRenderer::new();
+        // "/9j/" is the base64 of a JPEG's FF D8 FF start of image marker.
+        r.image_data_uri("data:image/jpeg;base64,/9j/").unwrap();
+        r.image_data_uri("data:text/plain,hi").unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("{\\pict\\jpegblip ffd8ff}\\par"));
+        assert!(s.contains("data:text/plain,hi\\par"));
+    }
 }
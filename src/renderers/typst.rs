@@ -1,6 +1,6 @@
 use std::{error::Error, io::Write};
 
-use super::Renderer;
+use super::{Align, Renderer, TableOptions};
 
 /// Renderer that emits Typst markup for later compilation.
 pub struct TypstRenderer {
@@ -13,32 +13,45 @@ impl TypstRenderer {
             content: String::new(),
         }
     }
+
+    /// Escape characters with special meaning in Typst markup so that cell
+    /// contents are rendered literally.
+    fn escape(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '[' | ']' | '#' | '*' | '_' | '$' | '@' | '<' | '`' | '~') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out
+    }
 }
 
 impl Renderer for TypstRenderer {
-    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.content.push_str(text);
         self.content.push_str("\n\n");
         Ok(())
     }
 
-    fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.content.push_str("#pagebreak()\n\n");
         Ok(())
     }
 
-    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
         writer.write_all(self.content.as_bytes())?;
         Ok(())
     }
 
-    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error>> {
+    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         let marks = "=".repeat(level.max(1));
         self.content.push_str(&format!("{marks} {text}\n\n"));
         Ok(())
     }
 
-    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error>> {
+    fn image_data_uri(&mut self, data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         if let Some(pos) = data_uri.find(',') {
             let (prefix, data) = data_uri.split_at(pos + 1);
             if prefix.starts_with("data:image/") && prefix.contains(";base64,") {
@@ -51,4 +64,87 @@ impl Renderer for TypstRenderer {
         }
         self.text(data_uri)
     }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let columns = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        if columns == 0 {
+            return Ok(());
+        }
+        let widths: Vec<String> = options
+            .fractions(columns)
+            .iter()
+            .map(|f| format!("{}fr", (f * 100.0).round()))
+            .collect();
+        let aligns: Vec<&str> = (0..columns)
+            .map(|i| match options.align_of(i) {
+                Align::Left => "left",
+                Align::Center => "center",
+                Align::Right => "right",
+            })
+            .collect();
+        self.content.push_str("#table(\n");
+        self.content
+            .push_str(&format!("  columns: ({},),\n", widths.join(", ")));
+        self.content
+            .push_str(&format!("  align: ({},),\n", aligns.join(", ")));
+        if !headers.is_empty() {
+            let cells: Vec<String> = (0..columns)
+                .map(|i| format!("[*{}*]", Self::escape(headers.get(i).copied().unwrap_or(""))))
+                .collect();
+            self.content
+                .push_str(&format!("  table.header({}),\n", cells.join(", ")));
+        }
+        // Pad short rows so every row fills the declared columns.
+        for row in rows {
+            let cells: Vec<String> = (0..columns)
+                .map(|i| format!("[{}]", Self::escape(row.get(i).map_or("", String::as_str))))
+                .collect();
+            self.content.push_str(&format!("  {},\n", cells.join(", ")));
+        }
+        self.content.push_str(")\n\n");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypstRenderer;
+    use crate::renderer::{Align, Renderer, TableOptions};
+
+    #[test]
+    fn writes_table_markup() {
+        let mut r = TypstRenderer::new();
+        let opts = TableOptions::default()
+            .widths(&[3.0, 1.0])
+            .align(&[Align::Left, Align::Right]);
+        r.table_with(&["Plugin", "Count"], &[vec!["SMB #1".into(), "4".into()]], &opts)
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("columns: (75fr, 25fr,)"));
+        assert!(s.contains("align: (left, right,)"));
+        assert!(s.contains("table.header([*Plugin*], [*Count*])"));
+        assert!(s.contains("[SMB \\#1], [4],"));
+    }
+
+    #[test]
+    fn pads_short_rows_and_skips_empty_tables() {
+        let mut r = TypstRenderer::new();
+        r.table_with(&[], &[], &TableOptions::default()).unwrap();
+        r.table_with(&["Host", "Port"], &[vec!["a".into()]], &TableOptions::default())
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(s.matches("#table(").count(), 1);
+        assert!(s.contains("  [a], [],\n"));
+    }
 }
@@ -0,0 +1,650 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::{Renderer, TableOptions};
+
+/// Excel limits that would otherwise make the workbook fail to open.
+const MAX_SHEET_NAME: usize = 31;
+const MAX_CELL_CHARS: usize = 32_767;
+
+/// Cell styles defined in `styles.xml`.
+const STYLE_BOLD: u32 = 1;
+const STYLE_HEADING: u32 = 2;
+
+/// Table headers whose values are written as numbers, besides any CVSS or
+/// score column.
+const NUMERIC_COLUMNS: [&str; 4] = ["count", "findings", "severity", "total"];
+
+const NS_MAIN: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const NS_REL: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const NS_PKG_REL: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+struct Row {
+    cells: Vec<Cell>,
+    style: u32,
+}
+
+/// A `table_with` call, written as an Excel table with its own auto filter.
+struct TableRange {
+    /// Zero-based row of the header.
+    header_row: usize,
+    last_row: usize,
+    columns: Vec<String>,
+}
+
+struct Sheet {
+    name: String,
+    rows: Vec<Row>,
+    tables: Vec<TableRange>,
+    /// Relative column widths from the first table, used for column sizing.
+    widths: Vec<f64>,
+}
+
+impl Sheet {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            rows: Vec::new(),
+            tables: Vec::new(),
+            widths: Vec::new(),
+        }
+    }
+}
+
+/// Renderer that produces an Excel workbook (`.xlsx`).
+///
+/// Every level-1 heading and every [`Renderer::start_new_page`] starts a new
+/// worksheet. Text becomes one cell per line and each table becomes an Excel
+/// table with a bold header row and an auto filter. The pane is frozen below
+/// the header of the first table on each sheet.
+pub struct XlsxRenderer {
+    sheets: Vec<Sheet>,
+    /// Whether the current sheet was named by a heading rather than a default.
+    named: bool,
+}
+
+impl XlsxRenderer {
+    /// Create a renderer whose first worksheet is named after `title`.
+    pub fn new(title: &str) -> Self {
+        let mut r = Self {
+            sheets: Vec::new(),
+            named: false,
+        };
+        let name = r.unique_sheet_name(title);
+        r.sheets.push(Sheet::new(name));
+        r
+    }
+
+    fn current(&mut self) -> &mut Sheet {
+        self.sheets.last_mut().expect("workbook has a sheet")
+    }
+
+    /// Start a new worksheet, or rename the current one if it is still empty.
+    fn begin_sheet(&mut self, name: &str, named: bool) {
+        if self.current().rows.is_empty() && !self.named {
+            self.current().name = String::new();
+            let name = self.unique_sheet_name(name);
+            self.current().name = name;
+        } else {
+            let name = self.unique_sheet_name(name);
+            self.sheets.push(Sheet::new(name));
+        }
+        self.named = named;
+    }
+
+    /// Excel sheet names are at most 31 characters, exclude `[]:*?/\` and must
+    /// be unique ignoring case.
+    fn unique_sheet_name(&self, wanted: &str) -> String {
+        let cleaned: String = wanted
+            .chars()
+            .map(|c| if "[]:*?/\\".contains(c) || c.is_control() { ' ' } else { c })
+            .collect();
+        let cleaned = cleaned.trim().trim_matches('\'').trim();
+        let base = if cleaned.is_empty() { "Sheet" } else { cleaned };
+        let taken: HashSet<String> =
+            self.sheets.iter().map(|s| s.name.to_lowercase()).collect();
+        let mut n = 1;
+        loop {
+            let suffix = if n == 1 { String::new() } else { format!(" ({n})") };
+            let keep = MAX_SHEET_NAME - suffix.chars().count();
+            let candidate: String = base.chars().take(keep).collect::<String>() + &suffix;
+            if !taken.contains(&candidate.to_lowercase()) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+
+    fn push_row(&mut self, cells: Vec<Cell>, style: u32) {
+        self.current().rows.push(Row { cells, style });
+    }
+
+    /// Whether the column titled `header` holds severities, counts or scores.
+    /// Only those are stored as numbers; IDs, ports and versions stay text.
+    fn numeric_column(header: &str) -> bool {
+        let header = header.trim().to_ascii_lowercase();
+        NUMERIC_COLUMNS.contains(&header.as_str())
+            || header.contains("cvss")
+            || header.contains("score")
+    }
+
+    /// Store the values of numeric columns as numbers so they sort and sum in
+    /// Excel, keeping anything with leading zeros or spaces as text.
+    fn cell(value: &str, numeric: bool) -> Cell {
+        let trimmed = value.trim();
+        let leading_zero = trimmed.len() > 1 && trimmed.starts_with('0') && !trimmed.contains('.');
+        match trimmed.parse::<f64>() {
+            Ok(n) if numeric && n.is_finite() && !leading_zero && trimmed == value => {
+                Cell::Number(n)
+            }
+            _ => Cell::Text(value.to_string()),
+        }
+    }
+
+    fn write_workbook(&self) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let mut strings = SharedStrings::default();
+        let mut sheet_xml = Vec::new();
+        let mut table_xml = Vec::new();
+        for sheet in &self.sheets {
+            for table in &sheet.tables {
+                table_xml.push(table_part(table_xml.len() + 1, table));
+            }
+            sheet_xml.push(worksheet(sheet, &mut strings));
+        }
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let opts = SimpleFileOptions::default();
+        let mut add = |name: &str, body: String| -> Result<(), Box<dyn Error + Send + Sync>> {
+            zip.start_file(name, opts)?;
+            zip.write_all(XML_DECL.as_bytes())?;
+            zip.write_all(body.as_bytes())?;
+            Ok(())
+        };
+
+        add("[Content_Types].xml", content_types(self.sheets.len(), table_xml.len()))?;
+        add(
+            "_rels/.rels",
+            format!(
+                "<Relationships xmlns=\"{NS_PKG_REL}\"><Relationship Id=\"rId1\" \
+                 Type=\"{NS_REL}/officeDocument\" Target=\"xl/workbook.xml\"/></Relationships>"
+            ),
+        )?;
+
+        let mut sheets = String::new();
+        let mut rels = String::new();
+        for (i, sheet) in self.sheets.iter().enumerate() {
+            let n = i + 1;
+            sheets.push_str(&format!(
+                "<sheet name=\"{}\" sheetId=\"{n}\" r:id=\"rId{n}\"/>",
+                escape(&sheet.name)
+            ));
+            rels.push_str(&format!(
+                "<Relationship Id=\"rId{n}\" Type=\"{NS_REL}/worksheet\" \
+                 Target=\"worksheets/sheet{n}.xml\"/>"
+            ));
+        }
+        let n = self.sheets.len();
+        rels.push_str(&format!(
+            "<Relationship Id=\"rId{}\" Type=\"{NS_REL}/styles\" Target=\"styles.xml\"/>\
+             <Relationship Id=\"rId{}\" Type=\"{NS_REL}/sharedStrings\" \
+             Target=\"sharedStrings.xml\"/>",
+            n + 1,
+            n + 2
+        ));
+        add(
+            "xl/workbook.xml",
+            format!(
+                "<workbook xmlns=\"{NS_MAIN}\" xmlns:r=\"{NS_REL}\"><sheets>{sheets}</sheets>\
+                 </workbook>"
+            ),
+        )?;
+        add(
+            "xl/_rels/workbook.xml.rels",
+            format!("<Relationships xmlns=\"{NS_PKG_REL}\">{rels}</Relationships>"),
+        )?;
+        add("xl/styles.xml", STYLES.to_string())?;
+
+        let mut table_no = 1;
+        for (i, (sheet, xml)) in self.sheets.iter().zip(sheet_xml).enumerate() {
+            add(&format!("xl/worksheets/sheet{}.xml", i + 1), xml)?;
+            if sheet.tables.is_empty() {
+                continue;
+            }
+            let rels: String = (0..sheet.tables.len())
+                .map(|t| {
+                    format!(
+                        "<Relationship Id=\"rId{}\" Type=\"{NS_REL}/table\" \
+                         Target=\"../tables/table{}.xml\"/>",
+                        t + 1,
+                        table_no + t
+                    )
+                })
+                .collect();
+            table_no += sheet.tables.len();
+            add(
+                &format!("xl/worksheets/_rels/sheet{}.xml.rels", i + 1),
+                format!("<Relationships xmlns=\"{NS_PKG_REL}\">{rels}</Relationships>"),
+            )?;
+        }
+        for (i, xml) in table_xml.into_iter().enumerate() {
+            add(&format!("xl/tables/table{}.xml", i + 1), xml)?;
+        }
+        add("xl/sharedStrings.xml", strings.xml())?;
+
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+impl Renderer for XlsxRenderer {
+    fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for line in text.lines() {
+            self.push_row(vec![Cell::Text(line.to_string())], 0);
+        }
+        Ok(())
+    }
+
+    fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if !self.current().rows.is_empty() {
+            let name = format!("Sheet{}", self.sheets.len() + 1);
+            self.begin_sheet(&name, false);
+        }
+        Ok(())
+    }
+
+    fn save(&mut self, writer: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
+        writer.write_all(&self.write_workbook()?)?;
+        Ok(())
+    }
+
+    fn heading(&mut self, level: usize, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if level <= 1 {
+            self.begin_sheet(text, true);
+            self.push_row(vec![Cell::Text(text.to_string())], STYLE_HEADING);
+        } else {
+            self.push_row(vec![Cell::Text(text.to_string())], STYLE_BOLD);
+        }
+        Ok(())
+    }
+
+    fn image_data_uri(&mut self, _data_uri: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.text("(image omitted; use the PDF or HTML renderer for graphs)")
+    }
+
+    fn table_with(
+        &mut self,
+        headers: &[&str],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let columns = headers
+            .len()
+            .max(rows.iter().map(Vec::len).max().unwrap_or(0));
+        if columns == 0 {
+            return Ok(());
+        }
+        // Excel tables need unique, non-empty column names.
+        let mut seen = HashSet::new();
+        let names: Vec<String> = (0..columns)
+            .map(|i| {
+                let base = headers
+                    .get(i)
+                    .map(|h| clean(h).replace(['\r', '\n'], " ").trim().to_string())
+                    .filter(|h| !h.is_empty())
+                    .unwrap_or_else(|| format!("Column{}", i + 1));
+                let mut name = base.clone();
+                let mut n = 2;
+                while !seen.insert(name.to_lowercase()) {
+                    name = format!("{base}{n}");
+                    n += 1;
+                }
+                name
+            })
+            .collect();
+
+        let sheet = self.current();
+        if sheet.widths.is_empty() {
+            sheet.widths = options.fractions(columns);
+        }
+        let header_row = sheet.rows.len();
+        sheet.rows.push(Row {
+            cells: names.iter().map(|n| Cell::Text(n.clone())).collect(),
+            style: STYLE_BOLD,
+        });
+        let numeric: Vec<bool> = (0..columns)
+            .map(|i| headers.get(i).is_some_and(|h| Self::numeric_column(h)))
+            .collect();
+        for row in rows {
+            sheet.rows.push(Row {
+                cells: row
+                    .iter()
+                    .zip(&numeric)
+                    .map(|(v, &numeric)| Self::cell(v, numeric))
+                    .collect(),
+                style: 0,
+            });
+        }
+        if rows.is_empty() {
+            // A table needs at least one data row.
+            sheet.rows.push(Row {
+                cells: Vec::new(),
+                style: 0,
+            });
+        }
+        let last_row = sheet.rows.len() - 1;
+        sheet.tables.push(TableRange {
+            header_row,
+            last_row,
+            columns: names,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct SharedStrings {
+    index: HashMap<String, usize>,
+    strings: Vec<String>,
+}
+
+impl SharedStrings {
+    fn get(&mut self, s: &str) -> usize {
+        if let Some(i) = self.index.get(s) {
+            return *i;
+        }
+        self.strings.push(s.to_string());
+        self.index.insert(s.to_string(), self.strings.len() - 1);
+        self.strings.len() - 1
+    }
+
+    fn xml(&self) -> String {
+        let mut out = format!(
+            "<sst xmlns=\"{NS_MAIN}\" count=\"{0}\" uniqueCount=\"{0}\">",
+            self.strings.len()
+        );
+        for s in &self.strings {
+            out.push_str(&format!("<si><t xml:space=\"preserve\">{}</t></si>", escape(s)));
+        }
+        out.push_str("</sst>");
+        out
+    }
+}
+
+fn worksheet(sheet: &Sheet, strings: &mut SharedStrings) -> String {
+    let mut xml = format!("<worksheet xmlns=\"{NS_MAIN}\" xmlns:r=\"{NS_REL}\">");
+    xml.push_str("<sheetViews><sheetView workbookViewId=\"0\">");
+    if let Some(table) = sheet.tables.first() {
+        let split = table.header_row + 1;
+        xml.push_str(&format!(
+            "<pane ySplit=\"{split}\" topLeftCell=\"A{}\" activePane=\"bottomLeft\" \
+             state=\"frozen\"/><selection pane=\"bottomLeft\"/>",
+            split + 1
+        ));
+    }
+    xml.push_str("</sheetView></sheetViews>");
+
+    // Size columns from the first table's relative widths over ~120 characters,
+    // but never narrower than its content up to a sensible maximum. Single-cell
+    // rows (text and headings) overflow into empty cells and are ignored.
+    let columns = sheet.rows.iter().map(|r| r.cells.len()).max().unwrap_or(0);
+    if columns > 0 {
+        xml.push_str("<cols>");
+        for c in 0..columns {
+            let content = sheet
+                .rows
+                .iter()
+                .filter(|r| r.cells.len() > 1 || c > 0)
+                .filter_map(|r| r.cells.get(c))
+                .map(|cell| match cell {
+                    Cell::Text(t) => t.chars().count(),
+                    Cell::Number(n) => n.to_string().len(),
+                })
+                .max()
+                .unwrap_or(0) as f64;
+            let share = sheet.widths.get(c).map_or(0.0, |f| f * 120.0);
+            let width = content.max(share).clamp(10.0, 80.0) + 2.0;
+            xml.push_str(&format!(
+                "<col min=\"{0}\" max=\"{0}\" width=\"{width:.1}\" customWidth=\"1\"/>",
+                c + 1
+            ));
+        }
+        xml.push_str("</cols>");
+    }
+
+    xml.push_str("<sheetData>");
+    for (r, row) in sheet.rows.iter().enumerate() {
+        let rn = r + 1;
+        if row.cells.is_empty() {
+            xml.push_str(&format!("<row r=\"{rn}\"/>"));
+            continue;
+        }
+        xml.push_str(&format!("<row r=\"{rn}\">"));
+        for (c, cell) in row.cells.iter().enumerate() {
+            let reference = format!("{}{rn}", column_name(c));
+            let style = if row.style == 0 {
+                String::new()
+            } else {
+                format!(" s=\"{}\"", row.style)
+            };
+            match cell {
+                Cell::Number(n) => {
+                    xml.push_str(&format!("<c r=\"{reference}\"{style}><v>{n}</v></c>"))
+                }
+                Cell::Text(t) => {
+                    let idx = strings.get(&truncate(&clean(t)));
+                    xml.push_str(&format!(
+                        "<c r=\"{reference}\"{style} t=\"s\"><v>{idx}</v></c>"
+                    ));
+                }
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData>");
+
+    if !sheet.tables.is_empty() {
+        xml.push_str(&format!("<tableParts count=\"{}\">", sheet.tables.len()));
+        for i in 0..sheet.tables.len() {
+            xml.push_str(&format!("<tablePart r:id=\"rId{}\"/>", i + 1));
+        }
+        xml.push_str("</tableParts>");
+    }
+    xml.push_str("</worksheet>");
+    xml
+}
+
+fn table_part(id: usize, table: &TableRange) -> String {
+    let range = format!(
+        "A{}:{}{}",
+        table.header_row + 1,
+        column_name(table.columns.len() - 1),
+        table.last_row + 1
+    );
+    let columns: String = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!("<tableColumn id=\"{}\" name=\"{}\"/>", i + 1, escape(name)))
+        .collect();
+    format!(
+        "<table xmlns=\"{NS_MAIN}\" id=\"{id}\" name=\"Table{id}\" displayName=\"Table{id}\" \
+         ref=\"{range}\" totalsRowShown=\"0\"><autoFilter ref=\"{range}\"/>\
+         <tableColumns count=\"{}\">{columns}</tableColumns>\
+         <tableStyleInfo name=\"TableStyleLight9\" showFirstColumn=\"0\" showLastColumn=\"0\" \
+         showRowStripes=\"1\" showColumnStripes=\"0\"/></table>",
+        table.columns.len()
+    )
+}
+
+fn content_types(sheets: usize, tables: usize) -> String {
+    let ct = "application/vnd.openxmlformats-officedocument.spreadsheetml";
+    let mut xml = format!(
+        "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+         <Default Extension=\"rels\" \
+         ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+         <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+         <Override PartName=\"/xl/workbook.xml\" ContentType=\"{ct}.sheet.main+xml\"/>\
+         <Override PartName=\"/xl/styles.xml\" ContentType=\"{ct}.styles+xml\"/>\
+         <Override PartName=\"/xl/sharedStrings.xml\" ContentType=\"{ct}.sharedStrings+xml\"/>"
+    );
+    for i in 1..=sheets {
+        xml.push_str(&format!(
+            "<Override PartName=\"/xl/worksheets/sheet{i}.xml\" \
+             ContentType=\"{ct}.worksheet+xml\"/>"
+        ));
+    }
+    for i in 1..=tables {
+        xml.push_str(&format!(
+            "<Override PartName=\"/xl/tables/table{i}.xml\" ContentType=\"{ct}.table+xml\"/>"
+        ));
+    }
+    xml.push_str("</Types>");
+    xml
+}
+
+/// Default, bold and heading cell formats.
+const STYLES: &str = "\
+<styleSheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\
+<fonts count=\"3\"><font><sz val=\"11\"/><name val=\"Calibri\"/></font>\
+<font><b/><sz val=\"11\"/><name val=\"Calibri\"/></font>\
+<font><b/><sz val=\"14\"/><name val=\"Calibri\"/></font></fonts>\
+<fills count=\"2\"><fill><patternFill patternType=\"none\"/></fill>\
+<fill><patternFill patternType=\"gray125\"/></fill></fills>\
+<borders count=\"1\"><border><left/><right/><top/><bottom/><diagonal/></border></borders>\
+<cellStyleXfs count=\"1\">\
+<xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\"/></cellStyleXfs>\
+<cellXfs count=\"3\"><xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\"/>\
+<xf numFmtId=\"0\" fontId=\"1\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\"/>\
+<xf numFmtId=\"0\" fontId=\"2\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\"/></cellXfs>\
+<cellStyles count=\"1\"><cellStyle name=\"Normal\" xfId=\"0\" builtinId=\"0\"/></cellStyles>\
+</styleSheet>";
+
+/// Spreadsheet column letters: 0 -> A, 25 -> Z, 26 -> AA.
+fn column_name(mut idx: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("ASCII column name")
+}
+
+/// Drop control characters that are not allowed in XML.
+fn clean(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+fn truncate(text: &str) -> String {
+    text.chars().take(MAX_CELL_CHARS).collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn part(bytes: &[u8], name: &str) -> String {
+        let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut out = String::new();
+        zip.by_name(name).unwrap().read_to_string(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn level_one_headings_start_sheets() {
+        let mut r = XlsxRenderer::new("Report");
+        r.heading(1, "Top Findings").unwrap();
+        r.text("first\nsecond").unwrap();
+        r.heading(1, "Hosts/Services").unwrap();
+        r.heading(2, "10.0.0.1").unwrap();
+        r.start_new_page().unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+
+        let workbook = part(&out, "xl/workbook.xml");
+        assert!(workbook.contains("<sheet name=\"Top Findings\" sheetId=\"1\""));
+        assert!(workbook.contains("<sheet name=\"Hosts Services\" sheetId=\"2\""));
+        assert!(workbook.contains("<sheet name=\"Sheet3\" sheetId=\"3\""));
+        let sheet = part(&out, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("<c r=\"A1\" s=\"2\" t=\"s\">"));
+        assert!(sheet.contains("<c r=\"A3\" t=\"s\">"));
+        let strings = part(&out, "xl/sharedStrings.xml");
+        assert!(strings.contains(">second</t>"));
+    }
+
+    #[test]
+    fn tables_get_filters_bold_headers_and_frozen_panes() {
+        let mut r = XlsxRenderer::new("Fix List");
+        r.heading(2, "10.0.0.1").unwrap();
+        let rows = vec![vec!["Critical".to_string(), "SMB <1>".to_string(), "0042".to_string()]];
+        r.table_with(&["Severity", "Finding", "ID"], &rows, &TableOptions::default())
+            .unwrap();
+        r.table_with(&["Severity", "Severity"], &[vec!["4".into()]], &TableOptions::default())
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+
+        let sheet = part(&out, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("<pane ySplit=\"2\" topLeftCell=\"A3\""));
+        assert!(sheet.contains("<c r=\"A2\" s=\"1\" t=\"s\">"));
+        assert!(sheet.contains("<c r=\"A5\"><v>4</v></c>"));
+        assert!(sheet.contains("<tableParts count=\"2\">"));
+        let table = part(&out, "xl/tables/table1.xml");
+        assert!(table.contains("ref=\"A2:C3\""));
+        assert!(table.contains("<autoFilter ref=\"A2:C3\"/>"));
+        let second = part(&out, "xl/tables/table2.xml");
+        assert!(second.contains("name=\"Severity2\""));
+        let strings = part(&out, "xl/sharedStrings.xml");
+        assert!(strings.contains("SMB &lt;1&gt;"));
+        assert!(strings.contains(">0042</t>"));
+    }
+
+    #[test]
+    fn only_numeric_columns_hold_numbers() {
+        let mut r = XlsxRenderer::new("Services");
+        let rows = vec![vec!["22".to_string(), "10.4".to_string(), "3".to_string(), "7.5".into()]];
+        r.table_with(&["Port", "Version", "Count", "CVSS v3"], &rows, &TableOptions::default())
+            .unwrap();
+        let mut out = Vec::new();
+        r.save(&mut out).unwrap();
+
+        let sheet = part(&out, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("<c r=\"A2\" t=\"s\">"));
+        assert!(sheet.contains("<c r=\"B2\" t=\"s\">"));
+        assert!(sheet.contains("<c r=\"C2\"><v>3</v></c>"));
+        assert!(sheet.contains("<c r=\"D2\"><v>7.5</v></c>"));
+        let strings = part(&out, "xl/sharedStrings.xml");
+        assert!(strings.contains(">22</t>"));
+        assert!(strings.contains(">10.4</t>"));
+    }
+}
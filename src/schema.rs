@@ -150,6 +150,17 @@ diesel::table! {
         engagement_id -> Nullable<Integer>,
         policy_id -> Nullable<Integer>,
         scanner_id -> Nullable<Integer>,
+        oid -> Nullable<Text>,
+        cvss3_base_score -> Nullable<Float>,
+        cvss3_vector -> Nullable<Text>,
+        cvss3_temporal_score -> Nullable<Float>,
+        cvss3_temporal_vector -> Nullable<Text>,
+        cvss4_base_score -> Nullable<Float>,
+        cvss4_vector -> Nullable<Text>,
+        vpr_score -> Nullable<Float>,
+        epss_score -> Nullable<Float>,
+        exploit_code_maturity -> Nullable<Text>,
+        threat_intensity_last_28 -> Nullable<Text>,
     }
 }
 
@@ -201,6 +212,20 @@ diesel::table! {
         engagement_id -> Nullable<Integer>,
         rollup_finding -> Nullable<Bool>,
         scanner_id -> Nullable<Integer>,
+        confidence -> Nullable<Integer>,
+        first_discovered -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+        cvss3_base_score -> Nullable<Float>,
+        cvss3_vector -> Nullable<Text>,
+        cvss3_temporal_score -> Nullable<Float>,
+        cvss3_temporal_vector -> Nullable<Text>,
+        cvss4_base_score -> Nullable<Float>,
+        cvss4_vector -> Nullable<Text>,
+        vpr_score -> Nullable<Float>,
+        epss_score -> Nullable<Float>,
+        exploit_code_maturity -> Nullable<Text>,
+        threat_intensity_last_28 -> Nullable<Text>,
+        suppressed -> Nullable<Bool>,
     }
 }
 
@@ -2,9 +2,18 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::error::Error;
+use crate::renderer::RENDERERS;
 
 /// Generate a template skeleton source file under the user's template directory.
 pub fn scaffold(name: &str, author: &str, renderer: &str) -> Result<(), Error> {
+    let Some((_, extension)) = RENDERERS.iter().find(|(r, _)| *r == renderer) else {
+        return Err(Error::Template(format!("unknown renderer '{renderer}'").into()));
+    };
+    let usage = if extension.is_empty() {
+        format!("risu-rs render -t {name} --renderer {renderer}")
+    } else {
+        format!("risu-rs render -t {name} -o report.{extension}")
+    };
     let dir: PathBuf = std::env::var_os("HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|| PathBuf::from("."))
@@ -13,9 +22,10 @@ pub fn scaffold(name: &str, author: &str, renderer: &str) -> Result<(), Error> {
     fs::create_dir_all(&dir)?;
     let file_path = dir.join(format!("{name}.rs"));
     let contents = format!(
-        "use std::collections::HashMap;\nuse std::error::Error;\nuse risu_rs::parser::NessusReport;\nuse risu_rs::renderer::Renderer;\nuse risu_rs::template::Template;\n\n/// Template generated by risu-rs.\n/// Author: {author}\n/// Renderer: {renderer}\npub struct {struct_name};\n\nimpl Template for {struct_name} {{\n    fn name(&self) -> &str {{\n        \"{name}\"\n    }}\n\n    fn generate(&self, _report: &NessusReport, renderer: &mut dyn Renderer, _args: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {{\n        renderer.heading(1, \"TODO\")?;\n        Ok(())\n    }}\n}}\n\n/// Metadata about this template.\npub struct Metadata {{\n    pub name: &'static str,\n    pub author: &'static str,\n    pub renderer: &'static str,\n}}\n\npub static METADATA: Metadata = Metadata {{\n    name: \"{name}\",\n    author: \"{author}\",\n    renderer: \"{renderer}\",\n}};\n\n#[no_mangle]\npub extern \"C\" fn create_template() -> Box<dyn Template> {{\n    Box::new({struct_name})\n}}\n",
+        "use std::collections::HashMap;\nuse std::error::Error;\nuse risu_rs::parser::NessusReport;\nuse risu_rs::renderer::Renderer;\nuse risu_rs::template::Template;\n\n/// Template generated by risu-rs.\n/// Author: {author}\n/// Renderer: {renderer}\n/// Usage: {usage}\npub struct {struct_name};\n\nimpl Template for {struct_name} {{\n    fn name(&self) -> &str {{\n        \"{name}\"\n    }}\n\n    fn generate(&self, _report: &NessusReport, renderer: &mut dyn Renderer, _args: &HashMap<String, String>) -> Result<(), Box<dyn Error + Send + Sync>> {{\n        renderer.heading(1, \"TODO\")?;\n        Ok(())\n    }}\n}}\n\n/// Metadata about this template.\npub struct Metadata {{\n    pub name: &'static str,\n    pub author: &'static str,\n    pub renderer: &'static str,\n}}\n\npub static METADATA: Metadata = Metadata {{\n    name: \"{name}\",\n    author: \"{author}\",\n    renderer: \"{renderer}\",\n}};\n\n#[no_mangle]\npub extern \"C\" fn create_template() -> Box<dyn Template> {{\n    Box::new({struct_name})\n}}\n",
         author = author,
         renderer = renderer,
+        usage = usage,
         name = name,
         struct_name = to_camel_case(name),
     );
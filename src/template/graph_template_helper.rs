@@ -10,7 +10,7 @@ use super::helpers;
 pub fn os_distribution_data_uri(
     report: &NessusReport,
     dir: &Path,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let path = graphs::os_distribution(report, dir)?;
     let bytes = fs::read(path)?;
     helpers::embed_graph(&bytes)
@@ -21,7 +21,7 @@ pub fn top_vuln_data_uri(
     report: &NessusReport,
     dir: &Path,
     n: usize,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let path = graphs::top_vulnerabilities(report, dir, n)?;
     let bytes = fs::read(path)?;
     helpers::embed_graph(&bytes)
@@ -31,7 +31,7 @@ pub fn top_vuln_data_uri(
 pub fn malware_data_uri(
     report: &NessusReport,
     dir: &Path,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let path = graphs::malware(report, dir)?;
     let bytes = fs::read(path)?;
     helpers::embed_graph(&bytes)
@@ -137,6 +137,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: Default::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         }
     }
@@ -5,15 +5,17 @@ use std::path::PathBuf;
 
 use base64::{Engine, engine::general_purpose};
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 
 use crate::graphs::{
     HostSeverityCountsGraph, OsDistributionGraph, TopVulnGraph, VulnCategoryGraph,
     VulnsByServiceGraph, WindowsOsGraph,
 };
 use crate::models::{
-    Attachment, FamilySelection, Host, HostProperty, Item, PolicyPlugin, ServiceDescription,
+    Attachment, CvssVersion, FamilySelection, Host, HostProperty, Item, PolicyPlugin,
+    ServiceDescription,
 };
+use crate::parser::NessusReport;
 use crate::renderer::Renderer;
 
 /// Produce a message indicating the operating system is unsupported.
@@ -31,20 +33,20 @@ pub fn section(
     renderer: &mut dyn Renderer,
     level: usize,
     title: &str,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), Box<dyn Error + Send + Sync>> {
     renderer.heading(level, title)
 }
 
 /// Embed a graph image as a base64 data URI.
 ///
 /// The image bytes are expected to be in PNG format.
-pub fn embed_graph(bytes: &[u8]) -> Result<String, Box<dyn Error>> {
+pub fn embed_graph(bytes: &[u8]) -> Result<String, Box<dyn Error + Send + Sync>> {
     let encoded = general_purpose::STANDARD.encode(bytes);
     Ok(format!("data:image/png;base64,{encoded}"))
 }
 
 /// Generate the top vulnerabilities graph and return it as a data URI.
-pub fn top_vuln_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Error>> {
+pub fn top_vuln_graph(conn: &mut DbConnection) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
         let path = TopVulnGraph::generate(conn, &dir, 10, None)?;
     let bytes = fs::read(path)?;
@@ -52,7 +54,7 @@ pub fn top_vuln_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Err
 }
 
 /// Generate the Windows OS distribution graph and return it as a data URI.
-pub fn windows_os_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Error>> {
+pub fn windows_os_graph(conn: &mut DbConnection) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
     let path = WindowsOsGraph::generate(conn, &dir)?;
     let bytes = fs::read(path)?;
@@ -60,7 +62,9 @@ pub fn windows_os_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn E
 }
 
 /// Generate a general OS distribution graph and return it as a data URI.
-pub fn os_distribution_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Error>> {
+pub fn os_distribution_graph(
+    conn: &mut DbConnection,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
     let path = OsDistributionGraph::generate(conn, &dir, None)?;
     let bytes = fs::read(path)?;
@@ -68,7 +72,9 @@ pub fn os_distribution_graph(conn: &mut SqliteConnection) -> Result<String, Box<
 }
 
 /// Generate a vulnerabilities by service graph and return it as a data URI.
-pub fn vulns_by_service_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Error>> {
+pub fn vulns_by_service_graph(
+    conn: &mut DbConnection,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
     let path = VulnsByServiceGraph::generate(conn, &dir, 10, None)?;
     let bytes = fs::read(path)?;
@@ -76,7 +82,9 @@ pub fn vulns_by_service_graph(conn: &mut SqliteConnection) -> Result<String, Box
 }
 
 /// Generate a vulnerability category graph and return it as a data URI.
-pub fn vuln_category_graph(conn: &mut SqliteConnection) -> Result<String, Box<dyn Error>> {
+pub fn vuln_category_graph(
+    conn: &mut DbConnection,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
     let path = VulnCategoryGraph::generate(conn, &dir, 10, None)?;
     let bytes = fs::read(path)?;
@@ -85,8 +93,8 @@ pub fn vuln_category_graph(conn: &mut SqliteConnection) -> Result<String, Box<dy
 
 /// Generate a host severity counts graph and return it as a data URI.
 pub fn host_severity_counts_graph(
-    conn: &mut SqliteConnection,
-) -> Result<String, Box<dyn Error>> {
+    conn: &mut DbConnection,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
     let dir: PathBuf = std::env::temp_dir();
     let path = HostSeverityCountsGraph::generate(conn, &dir, None)?;
     let bytes = fs::read(path)?;
@@ -94,7 +102,7 @@ pub fn host_severity_counts_graph(
 }
 
 /// Embed a previously saved attachment as a data URI.
-pub fn embed_attachment(att: &Attachment) -> Result<String, Box<dyn Error>> {
+pub fn embed_attachment(att: &Attachment) -> Result<String, Box<dyn Error + Send + Sync>> {
     let path = att
         .path
         .as_ref()
@@ -113,6 +121,14 @@ pub fn attachment_path(att: &Attachment) -> Option<&str> {
     att.path.as_deref()
 }
 
+/// CVSS version a template should prefer: the `cvss_version` template
+/// argument when it is valid, otherwise the report's setting.
+pub fn cvss_version(report: &NessusReport, args: &HashMap<String, String>) -> CvssVersion {
+    args.get("cvss_version")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(report.cvss_version)
+}
+
 /// Plugins that indicate default credentials were accepted.
 pub static DEFAULT_CREDENTIAL_PLUGINS: &[i32] = &[1000, 2000, 3000];
 
@@ -178,7 +194,7 @@ pub fn default_credentials_appendix_section(plugin_ids: &[i32]) -> String {
 
 /// Fetch a host property by name for a given host.
 pub fn host_property(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     host_id_val: i32,
     prop_name: &str,
 ) -> QueryResult<Option<String>> {
@@ -195,7 +211,7 @@ pub fn host_property(
 ///
 /// This simply calls [`host_property`] with the `netbios-name` key.
 pub fn host_netbios_name(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     host_id_val: i32,
 ) -> QueryResult<Option<String>> {
     host_property(conn, host_id_val, "netbios-name")
@@ -203,7 +219,7 @@ pub fn host_netbios_name(
 
 /// Retrieve all properties for a host.
 pub fn host_properties(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     host_id_val: i32,
 ) -> QueryResult<Vec<HostProperty>> {
     use crate::schema::nessus_host_properties::dsl::*;
@@ -214,7 +230,7 @@ pub fn host_properties(
 
 /// Fetch enabled plugin families for a policy.
 pub fn enabled_families(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     policy_id_val: i32,
 ) -> QueryResult<Vec<FamilySelection>> {
     use crate::schema::nessus_family_selections::dsl::*;
@@ -225,7 +241,7 @@ pub fn enabled_families(
 
 /// Fetch enabled plugins for a policy.
 pub fn enabled_plugins(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     policy_id_val: i32,
 ) -> QueryResult<Vec<PolicyPlugin>> {
     use crate::schema::nessus_policy_plugins::dsl::*;
@@ -236,7 +252,7 @@ pub fn enabled_plugins(
 
 /// Fetch a server preference by name for a policy.
 pub fn server_preference(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     policy_id_val: i32,
     pref_name: &str,
 ) -> QueryResult<Option<String>> {
@@ -250,7 +266,7 @@ pub fn server_preference(
 }
 
 /// Fetch CVE identifiers for a given finding (item).
-pub fn cve_identifiers(conn: &mut SqliteConnection, item_id_val: i32) -> QueryResult<Vec<String>> {
+pub fn cve_identifiers(conn: &mut DbConnection, item_id_val: i32) -> QueryResult<Vec<String>> {
     use crate::schema::nessus_references::dsl::*;
     nessus_references
         .filter(item_id.eq(item_id_val).and(source.eq("CVE")))
@@ -260,7 +276,7 @@ pub fn cve_identifiers(conn: &mut SqliteConnection, item_id_val: i32) -> QueryRe
 }
 
 /// Fetch BID identifiers for a given finding (item).
-pub fn bid_identifiers(conn: &mut SqliteConnection, item_id_val: i32) -> QueryResult<Vec<String>> {
+pub fn bid_identifiers(conn: &mut DbConnection, item_id_val: i32) -> QueryResult<Vec<String>> {
     use crate::schema::nessus_references::dsl::*;
     nessus_references
         .filter(item_id.eq(item_id_val).and(source.eq("BID")))
@@ -271,7 +287,7 @@ pub fn bid_identifiers(conn: &mut SqliteConnection, item_id_val: i32) -> QueryRe
 
 /// Fetch CVE identifiers for a plugin via indexed metadata.
 pub fn plugin_cve_identifiers(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     plugin_id_val: i32,
 ) -> QueryResult<Vec<String>> {
     use crate::models::NessusPluginMetadata;
@@ -283,7 +299,7 @@ pub fn plugin_cve_identifiers(
 
 /// Fetch BID identifiers for a plugin via indexed metadata.
 pub fn plugin_bid_identifiers(
-    conn: &mut SqliteConnection,
+    conn: &mut DbConnection,
     plugin_id_val: i32,
 ) -> QueryResult<Vec<String>> {
     use crate::models::NessusPluginMetadata;
@@ -511,10 +527,10 @@ mod tests {
         bid: Option<&'a str>,
     }
 
-    fn setup() -> SqliteConnection {
+    fn setup() -> DbConnection {
         let mut conn = SqliteConnection::establish(":memory:").unwrap();
         conn.run_pending_migrations(MIGRATIONS).unwrap();
-        conn
+        conn.into()
     }
 
     #[test]
@@ -636,23 +652,25 @@ mod tests {
             })
             .execute(&mut conn)
             .unwrap();
-        diesel::insert_into(nessus_references::table)
-            .values(&[
-                NewReference {
-                    plugin_id: Some(1),
-                    item_id: Some(1),
-                    source: Some("CVE"),
-                    value: Some("CVE-2023-0001"),
-                },
-                NewReference {
-                    plugin_id: Some(1),
-                    item_id: Some(1),
-                    source: Some("BID"),
-                    value: Some("BID-1000"),
-                },
-            ])
-            .execute(&mut conn)
-            .unwrap();
+        for reference in [
+            NewReference {
+                plugin_id: Some(1),
+                item_id: Some(1),
+                source: Some("CVE"),
+                value: Some("CVE-2023-0001"),
+            },
+            NewReference {
+                plugin_id: Some(1),
+                item_id: Some(1),
+                source: Some("BID"),
+                value: Some("BID-1000"),
+            },
+        ] {
+            diesel::insert_into(nessus_references::table)
+                .values(&reference)
+                .execute(&mut conn)
+                .unwrap();
+        }
 
         let cves = cve_identifiers(&mut conn, 1).unwrap();
         assert_eq!(cves, vec!["CVE-2023-0001".to_string()]);
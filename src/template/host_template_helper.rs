@@ -1,6 +1,7 @@
 use super::{helpers, template_helper};
 use crate::{
-    models::{Host, host::UNSUPPORTED_WINDOWS_PLUGINS},
+    analysis::scoring,
+    models::{Host, host::UNSUPPORTED_WINDOWS_PLUGINS, host_property::{ASSET_CRITICALITY, ASSET_TAG}},
     parser::NessusReport,
 };
 use std::collections::BTreeMap;
@@ -22,6 +23,52 @@ pub fn host_label(host: &Host) -> String {
     }
 }
 
+/// Asset-context tags attached to the host at position `idx`.
+pub fn host_tags(report: &NessusReport, idx: usize) -> Vec<&str> {
+    let host_id = Some(report.hosts[idx].id);
+    report
+        .host_properties
+        .iter()
+        .filter(|p| p.host_id == host_id && p.name.as_deref() == Some(ASSET_TAG))
+        .filter_map(|p| p.value.as_deref())
+        .collect()
+}
+
+/// Criticality tier of the host at position `idx`, if one was assigned.
+pub fn host_tier(report: &NessusReport, idx: usize) -> Option<&str> {
+    let host_id = Some(report.hosts[idx].id);
+    report
+        .host_properties
+        .iter()
+        .filter(|p| p.host_id == host_id)
+        .find(|p| p.name.as_deref() == Some(ASSET_CRITICALITY))
+        .and_then(|p| p.value.as_deref())
+}
+
+/// Host positions grouped by criticality tier, most critical tier first as
+/// ranked by the configured criticality multipliers. Hosts without a tier
+/// come last under `None`.
+pub fn hosts_by_tier(report: &NessusReport) -> Vec<(Option<&str>, Vec<usize>)> {
+    let tiers = scoring::host_criticality(report);
+    let mut groups: BTreeMap<Option<&str>, Vec<usize>> = BTreeMap::new();
+    for (idx, host) in report.hosts.iter().enumerate() {
+        groups
+            .entry(tiers.get(&host.id).copied())
+            .or_default()
+            .push(idx);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    let rank = |tier: Option<&str>| {
+        tier.map(|t| report.risk_scoring.criticality_multiplier(Some(t)))
+    };
+    groups.sort_by(|(a, _), (b, _)| {
+        rank(*b)
+            .partial_cmp(&rank(*a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    groups
+}
+
 fn unsupported_os(title: &str, plugin_name: &str, report: &NessusReport) -> String {
     let hosts: Vec<String> = report
         .items
@@ -157,6 +204,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: crate::parser::Filters::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         };
 
@@ -193,6 +244,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: crate::parser::Filters::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         };
 
@@ -66,7 +66,7 @@ impl TemplateManager {
     /// Load templates from all configured paths. Each dynamic library is
     /// expected to expose a `create_template` function returning
     /// `Box<dyn Template>`.
-    pub fn load_templates(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn load_templates(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         for path in &self.paths {
             if let Ok(entries) = fs::read_dir(path) {
                 for entry in entries.flatten() {
@@ -29,12 +29,16 @@ pub trait Template {
     /// Name used to reference the template.
     fn name(&self) -> &str;
     /// Generate output for the given report using the provided renderer.
+    ///
+    /// Errors are `Send + Sync` so they fit [`crate::error::Error::Template`];
+    /// templates built for the older `Box<dyn Error>` signature must be
+    /// rebuilt.
     fn generate(
         &self,
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>>;
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
 
     /// Determine if a plugin indicates default credentials.
     fn has_default_credentials(&self, plugin_id: i32) -> bool {
@@ -65,7 +69,7 @@ impl Template for SimpleTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
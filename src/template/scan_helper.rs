@@ -92,6 +92,12 @@ pub fn filter_summary(report: &NessusReport) -> String {
     if let Some(pid) = report.filters.plugin_id {
         parts.push(format!("plugin-id {pid}"));
     }
+    if let Some(cutoff) = report.filters.older_than {
+        parts.push(format!("older-than {}", cutoff.format("%Y-%m-%d")));
+    }
+    if let Some(tag) = &report.filters.tag {
+        parts.push(format!("tag {tag}"));
+    }
     if parts.is_empty() {
         "No filters applied".into()
     } else {
@@ -141,6 +147,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: Default::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         }
     }
@@ -69,16 +69,20 @@ pub fn classification_banner(text: &str) -> String {
     format!("{line}\n{line}")
 }
 
-/// Return `Item` references whose associated host scan end time is earlier
-/// than the provided cutoff. Items lacking an end time are included.
+/// Return `Item` references last seen before the provided cutoff. An item's
+/// own `last_observed` date is used when present, otherwise the associated
+/// host's scan end time. Items lacking both are included.
 pub fn items_older_than<'a>(report: &'a NessusReport, cutoff: NaiveDateTime) -> Vec<&'a Item> {
     report
         .items
         .iter()
         .filter(|item| {
-            item.host_id
-                .and_then(|hid| report.hosts.get(hid as usize))
-                .and_then(|h| h.end)
+            item.last_observed
+                .or_else(|| {
+                    item.host_id
+                        .and_then(|hid| report.hosts.get(hid as usize))
+                        .and_then(|h| h.end)
+                })
                 .map_or(true, |end| end < cutoff)
         })
         .collect()
@@ -110,4 +114,33 @@ mod tests {
         let b = classification_banner("CLASS");
         assert!(b.contains("CLASS"));
     }
+
+    #[test]
+    fn older_than_prefers_item_last_observed() {
+        let date = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+        let report = NessusReport {
+            items: vec![
+                Item {
+                    plugin_id: Some(1),
+                    last_observed: Some(date("2023-01-01 00:00:00")),
+                    ..Item::default()
+                },
+                Item {
+                    plugin_id: Some(2),
+                    last_observed: Some(date("2024-06-01 00:00:00")),
+                    ..Item::default()
+                },
+                Item {
+                    plugin_id: Some(3),
+                    ..Item::default()
+                },
+            ],
+            ..NessusReport::default()
+        };
+        let ids: Vec<_> = items_older_than(&report, date("2024-01-01 00:00:00"))
+            .iter()
+            .filter_map(|i| i.plugin_id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
 }
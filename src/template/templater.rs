@@ -4,7 +4,7 @@ use std::fs::File;
 use std::io;
 use std::path::PathBuf;
 
-use diesel::sqlite::SqliteConnection;
+use crate::db::DbConnection;
 
 use crate::{
     parser::NessusReport,
@@ -16,7 +16,7 @@ use crate::{
 pub struct Templater<'a> {
     template_name: String,
     #[allow(unused)]
-    conn: &'a mut SqliteConnection,
+    conn: &'a mut DbConnection,
     output: PathBuf,
     manager: TemplateManager,
 }
@@ -25,7 +25,7 @@ impl<'a> Templater<'a> {
     /// Create a new templater.
     pub fn new(
         template_name: String,
-        conn: &'a mut SqliteConnection,
+        conn: &'a mut DbConnection,
         output: PathBuf,
         manager: TemplateManager,
     ) -> Self {
@@ -43,7 +43,7 @@ impl<'a> Templater<'a> {
         report: &NessusReport,
         renderer_choice: Option<&str>,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let tmpl = self.manager.get(&self.template_name).ok_or_else(|| {
             format!(
                 "unknown template '{}'. available: {:?}",
@@ -57,26 +57,48 @@ impl<'a> Templater<'a> {
             .cloned()
             .unwrap_or_else(|| "Report".to_string());
 
-        let mut rend: Box<dyn Renderer> = match renderer_choice {
-            Some("csv") => Box::new(renderer::CsvRenderer::new()),
-            Some("nil") => Box::new(renderer::NilRenderer::new()),
-            Some("pdf") => Box::new(renderer::PdfRenderer::new(&title_arg)),
-            Some("typst") => Box::new(renderer::TypstRenderer::new()),
-            Some("rtf") => Box::new(renderer::RtfRenderer::new()),
-            None => match self.output.extension().and_then(|s| s.to_str()) {
-                Some("csv") => Box::new(renderer::CsvRenderer::new()),
-                Some("rtf") => Box::new(renderer::RtfRenderer::new()),
-                Some("typ") => Box::new(renderer::TypstRenderer::new()),
-                _ => Box::new(renderer::PdfRenderer::new(&title_arg)),
-            },
-            Some(other) => {
+        // Markdown images are inlined unless `images=sidecar` is given.
+        let markdown = || -> Box<dyn Renderer> {
+            match args.get("images").map(String::as_str) {
+                Some("sidecar") => Box::new(renderer::MarkdownRenderer::with_sidecar_images(
+                    &self.output,
+                )),
+                _ => Box::new(renderer::MarkdownRenderer::new()),
+            }
+        };
+
+        // Without an explicit renderer, pick the one whose extension matches
+        // the output file, falling back to PDF.
+        let choice = renderer_choice.unwrap_or_else(|| {
+            let ext = match self.output.extension().and_then(|s| s.to_str()) {
+                Some("htm") => "html",
+                Some("markdown") => "md",
+                Some(ext) => ext,
+                None => "",
+            };
+            renderer::RENDERERS
+                .iter()
+                .find(|(_, e)| !e.is_empty() && *e == ext)
+                .map_or("pdf", |(name, _)| *name)
+        });
+
+        let mut rend: Box<dyn Renderer> = match choice {
+            "csv" => Box::new(renderer::CsvRenderer::new()),
+            "html" => Box::new(renderer::HtmlRenderer::new(&title_arg)),
+            "markdown" => markdown(),
+            "nil" => Box::new(renderer::NilRenderer::new()),
+            "pdf" => Box::new(renderer::PdfRenderer::new(&title_arg)),
+            "typst" => Box::new(renderer::TypstRenderer::new()),
+            "rtf" => Box::new(renderer::RtfRenderer::new()),
+            "xlsx" => Box::new(renderer::XlsxRenderer::new(&title_arg)),
+            other => {
                 return Err(format!("unsupported renderer '{other}'").into());
             }
         };
 
         tmpl.generate(report, rend.as_mut(), args)?;
 
-        if renderer_choice != Some("nil") {
+        if choice != "nil" {
             let mut f = File::create(&self.output)?;
             rend.save(&mut f)?;
         } else {
@@ -83,41 +83,80 @@ impl Template for AssetsTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
             .unwrap_or("Assets Report");
         renderer.text(&template_helper::heading(1, title))?;
 
-        for host in &report.hosts {
-            if let Some(name) = &host.name {
-                renderer.text(&template_helper::heading(2, name))?;
+        if args.get("group_by").map(String::as_str) == Some("tier") {
+            for (tier, hosts) in template_helper::host::hosts_by_tier(report) {
+                let heading = tier.map_or("No Tier".to_string(), |t| format!("Tier: {t}"));
+                renderer.text(&template_helper::heading(2, &heading))?;
+                for idx in hosts {
+                    host_details(report, idx, 3, renderer)?;
+                }
             }
-            let mut fields = Vec::new();
-            if let Some(name) = &host.name {
-                fields.push(template_helper::field("Name", name));
-            }
-            if let Some(fqdn) = &host.fqdn {
-                fields.push(template_helper::field("FQDN", fqdn));
-            }
-            if let Some(ip) = &host.ip {
-                fields.push(template_helper::field("IP", ip));
-            }
-            if let Some(netbios) = &host.netbios {
-                fields.push(template_helper::field("NetBIOS", netbios));
-            }
-            if let Some(mac) = &host.mac {
-                fields.push(template_helper::field("MAC", mac));
-            }
-            if let Some(os) = &host.os {
-                fields.push(template_helper::field("OS", os));
-            }
-            if !fields.is_empty() {
-                renderer.text(&fields.join("\n"))?;
+        } else {
+            for idx in 0..report.hosts.len() {
+                host_details(report, idx, 2, renderer)?;
             }
         }
         Ok(())
     }
 }
 
+/// Heading and fields of the host at position `idx`.
+fn host_details(
+    report: &NessusReport,
+    idx: usize,
+    level: usize,
+    renderer: &mut dyn Renderer,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let host = &report.hosts[idx];
+    if let Some(name) = &host.name {
+        renderer.text(&template_helper::heading(level, name))?;
+    }
+    let mut fields = Vec::new();
+    if let Some(name) = &host.name {
+        fields.push(template_helper::field("Name", name));
+    }
+    if let Some(fqdn) = &host.fqdn {
+        fields.push(template_helper::field("FQDN", fqdn));
+    }
+    if let Some(ip) = &host.ip {
+        fields.push(template_helper::field("IP", ip));
+    }
+    if let Some(netbios) = &host.netbios {
+        fields.push(template_helper::field("NetBIOS", netbios));
+    }
+    if let Some(mac) = &host.mac {
+        fields.push(template_helper::field("MAC", mac));
+    }
+    if let Some(os) = &host.os {
+        fields.push(template_helper::field("OS", os));
+    }
+    if let Some(tier) = template_helper::host::host_tier(report, idx) {
+        fields.push(template_helper::field("Tier", tier));
+    }
+    let tags = template_helper::host::host_tags(report, idx);
+    if !tags.is_empty() {
+        fields.push(template_helper::field("Tags", &tags.join(", ")));
+    }
+    // Identifiers of other scans' hosts folded into this one by `merge`.
+    let aliases: Vec<&str> = report
+        .host_properties
+        .iter()
+        .filter(|p| p.host_id == Some(host.id))
+        .filter(|p| p.name.as_deref().is_some_and(|n| n.starts_with("alias-")))
+        .filter_map(|p| p.value.as_deref())
+        .collect();
+    if !aliases.is_empty() {
+        fields.push(template_helper::field("Aliases", &aliases.join(", ")));
+    }
+    if !fields.is_empty() {
+        renderer.text(&fields.join("\n"))?;
+    }
+    Ok(())
+}
@@ -18,7 +18,7 @@ impl Template for AuthenticationSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
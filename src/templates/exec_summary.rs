@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use std::error::Error;
 
-use crate::analysis::risk;
+use chrono::Local;
+
+use crate::analysis::scoring;
 use crate::parser::NessusReport;
+use crate::postprocess::ReportIndex;
 use crate::renderer::Renderer;
 use crate::template::{
     Template,
@@ -22,7 +25,7 @@ impl Template for ExecSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
@@ -49,20 +52,16 @@ impl Template for ExecSummaryTemplate {
             template_helper::field("Info", &severities[0].to_string()),
         ]
         .join("\n");
-        let network = risk::Network {
-            critical: severities[4],
-            high: severities[3],
-            medium: severities[2],
-            low: severities[1],
-        };
-        let risk_score = network.risk_score();
+        let today = Local::now().naive_local().date();
+        let risk = scoring::explain_report(report, &ReportIndex::new(report), today);
+        let risk_score = risk.network.total();
         let risk_field = template_helper::field("Risk Score", &format!("{:.2}", risk_score));
         let severity_text = format!(
             "{}\n{}\n{}\n{}",
             template_helper::heading(2, "Severity Breakdown"),
             severity_fields,
             risk_field,
-            "Risk scores derived from weighted averages of finding severities (Critical=9, High=7, Medium=4, Low=1).",
+            "Risk score is the sum of every finding scored with the `risk_scoring` weights; run `risk` to see the factors.",
         );
         renderer.text(&severity_text)?;
 
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use std::error::Error;
 
-use crate::analysis::risk;
+use chrono::Local;
+
+use crate::analysis::scoring;
 use crate::parser::NessusReport;
+use crate::postprocess::ReportIndex;
 use crate::renderer::Renderer;
 use crate::template::Template;
 
@@ -19,7 +22,7 @@ impl Template for ExecutiveSummaryDetailedTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
@@ -35,16 +38,12 @@ impl Template for ExecutiveSummaryDetailedTemplate {
                 }
             }
         }
-        let network = risk::Network {
-            critical: severities[4],
-            high: severities[3],
-            medium: severities[2],
-            low: severities[1],
-        };
-        let risk_score = network.risk_score();
+        let today = Local::now().naive_local().date();
+        let risk = scoring::explain_report(report, &ReportIndex::new(report), today);
+        let risk_score = risk.network.total();
         renderer.text(&format!("Risk Score: {:.2}", risk_score))?;
         renderer.text(
-            "Risk scores derived from weighted averages of finding severities (Critical=9, High=7, Medium=4, Low=1).",
+            "Risk score is the sum of every finding scored with the `risk_scoring` weights; run `risk` to see the factors.",
         )?;
         Ok(())
     }
@@ -19,7 +19,7 @@ impl Template for FindingsHostTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("Findings Summary by Host Report")?;
 
         for host in &report.hosts {
@@ -3,7 +3,7 @@ use std::error::Error;
 
 use crate::models::Item;
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Align, Renderer, TableOptions};
 use crate::template::Template;
 
 /// Port of the Ruby `findings_summary.rb` template.
@@ -19,7 +19,7 @@ impl Template for FindingsSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("Findings Summary Report")?;
 
         let mut by_sev: BTreeMap<i32, Vec<&Item>> = BTreeMap::new();
@@ -54,9 +54,17 @@ impl Template for FindingsSummaryTemplate {
                     .and_modify(|e| e.1 += 1)
                     .or_insert((name, 1));
             }
-            for (_pid, (name, count)) in counts {
-                renderer.text(&format!("{count} - {name}"))?;
-            }
+            let rows: Vec<Vec<String>> = counts
+                .into_values()
+                .map(|(name, count)| vec![count.to_string(), name])
+                .collect();
+            renderer.table_with(
+                &["Count", "Finding"],
+                &rows,
+                &TableOptions::default()
+                    .widths(&[1.0, 6.0])
+                    .align(&[Align::Right, Align::Left]),
+            )?;
         }
         Ok(())
     }
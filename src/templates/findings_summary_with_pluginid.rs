@@ -3,7 +3,7 @@ use std::error::Error;
 
 use crate::models::Item;
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Align, Renderer, TableOptions};
 use crate::template::Template;
 
 /// Port of the Ruby `findings_summary_with_pluginid.rb` template.
@@ -19,7 +19,7 @@ impl Template for FindingsSummaryWithPluginIdTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("Findings Summary Report")?;
 
         let mut by_sev: BTreeMap<i32, Vec<&Item>> = BTreeMap::new();
@@ -53,9 +53,17 @@ impl Template for FindingsSummaryWithPluginIdTemplate {
                     .and_modify(|e| e.1 += 1)
                     .or_insert((name, 1));
             }
-            for (pid, (name, count)) in counts {
-                renderer.text(&format!("{count} - {name} - {pid}"))?;
-            }
+            let rows: Vec<Vec<String>> = counts
+                .into_iter()
+                .map(|(pid, (name, count))| vec![count.to_string(), name, pid.to_string()])
+                .collect();
+            renderer.table_with(
+                &["Count", "Finding", "Plugin ID"],
+                &rows,
+                &TableOptions::default()
+                    .widths(&[1.0, 6.0, 1.5])
+                    .align(&[Align::Right, Align::Left, Align::Center]),
+            )?;
         }
         Ok(())
     }
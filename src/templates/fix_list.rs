@@ -3,7 +3,7 @@ use std::error::Error;
 
 use crate::models::Item;
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Renderer, TableOptions};
 use crate::template::Template;
 
 /// Generate a "fix list" of high and critical findings grouped by host.
@@ -51,7 +51,7 @@ impl Template for FixListTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         // Group findings by host using a BTreeMap for sorted output.
         let mut by_host: BTreeMap<String, Vec<&Item>> = BTreeMap::new();
         for item in &report.items {
@@ -75,33 +75,31 @@ impl Template for FixListTemplate {
             // Write the host heading (works for CSV/PDF renderers).
             renderer.heading(2, &host)?;
 
-            for item in items {
-                let name = item
-                    .plugin_name
-                    .clone()
-                    .unwrap_or_else(|| format!("Plugin {}", item.plugin_id.unwrap_or(0)));
-                let severity = Self::severity_label(item.severity.unwrap_or(0));
-                // Collect CVE identifiers from references.
-                let cves: Vec<String> = report
-                    .references
-                    .iter()
-                    .filter(|r| {
-                        r.item_id == Some(item.id) && r.source.as_deref() == Some("CVE")
-                    })
-                    .filter_map(|r| r.value.clone())
-                    .collect();
-                let cve_str = if cves.is_empty() {
-                    String::new()
-                } else {
-                    cves.join(";")
-                };
-                let line = if cve_str.is_empty() {
-                    format!("{severity},{name}")
-                } else {
-                    format!("{severity},{name},{cve_str}")
-                };
-                renderer.text(&line)?;
-            }
+            let rows: Vec<Vec<String>> = items
+                .into_iter()
+                .map(|item| {
+                    let name = item
+                        .plugin_name
+                        .clone()
+                        .unwrap_or_else(|| format!("Plugin {}", item.plugin_id.unwrap_or(0)));
+                    let severity = Self::severity_label(item.severity.unwrap_or(0));
+                    // Collect CVE identifiers from references.
+                    let cves: Vec<String> = report
+                        .references
+                        .iter()
+                        .filter(|r| {
+                            r.item_id == Some(item.id) && r.source.as_deref() == Some("CVE")
+                        })
+                        .filter_map(|r| r.value.clone())
+                        .collect();
+                    vec![severity.to_string(), name, cves.join(";")]
+                })
+                .collect();
+            renderer.table_with(
+                &["Severity", "Finding", "CVE"],
+                &rows,
+                &TableOptions::default().widths(&[1.0, 4.0, 2.0]),
+            )?;
         }
         Ok(())
     }
@@ -224,6 +222,10 @@ mod tests {
             server_preferences: Vec::new(),
             scanner: Scanner::default(),
             filters: Filters::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
         };
 
         let mut renderer = CsvRenderer::new();
@@ -235,17 +237,20 @@ mod tests {
         let out = String::from_utf8(buf).unwrap();
         let mut rdr = csv::ReaderBuilder::new()
             .has_headers(false)
+            .flexible(true)
             .from_reader(out.as_bytes());
         let lines: Vec<String> = rdr
             .records()
-            .map(|r| r.unwrap().get(0).unwrap().to_string())
+            .map(|r| r.unwrap().iter().collect::<Vec<_>>().join(","))
             .collect();
 
         assert_eq!(lines[0], "a");
-        assert_eq!(lines[1], "Critical,CriticalB,CVE-0003");
-        assert_eq!(lines[2], "b");
-        assert_eq!(lines[3], "Critical,CriticalA,CVE-0001");
-        assert_eq!(lines[4], "High,HighA,CVE-0002");
+        assert_eq!(lines[1], "Severity,Finding,CVE");
+        assert_eq!(lines[2], "Critical,CriticalB,CVE-0003");
+        assert_eq!(lines[3], "b");
+        assert_eq!(lines[4], "Severity,Finding,CVE");
+        assert_eq!(lines[5], "Critical,CriticalA,CVE-0001");
+        assert_eq!(lines[6], "High,HighA,CVE-0002");
     }
 }
 
@@ -18,7 +18,7 @@ impl Template for GraphsTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args.get("title").map(String::as_str).unwrap_or("Graphs");
         renderer.heading(1, title)?;
         let tmp = std::env::temp_dir();
@@ -18,7 +18,8 @@ impl Template for HostFindingsCsvTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut rows = Vec::new();
         for item in &report.items {
             if item.severity.unwrap_or(0) < 4 {
                 continue;
@@ -29,9 +30,12 @@ impl Template for HostFindingsCsvTemplate {
             let netbios = host.and_then(|h| h.netbios.clone()).unwrap_or_default();
             let finding = item.plugin_name.clone().unwrap_or_default();
             let risk = item.risk_factor.clone().unwrap_or_default();
-            let row = format!("{ip}, {fqdn}, {netbios}, {finding}, {risk}");
-            renderer.text(&row)?;
+            rows.push(vec![ip, fqdn, netbios, finding, risk]);
         }
+        renderer.table(
+            &["IP Address", "FQDN", "Netbios Name", "Finding", "Risk Factor"],
+            &rows,
+        )?;
         Ok(())
     }
 }
@@ -20,13 +20,14 @@ impl Template for HostFindingsCsvOlderThanTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let cutoff_str = match args.get("cutoff_date") {
             Some(s) => s,
             None => return Err("missing cutoff_date argument".into()),
         };
         let cutoff = NaiveDateTime::parse_from_str(cutoff_str, "%Y-%m-%d %H:%M:%S")?;
 
+        let mut rows = Vec::new();
         for item in template_helper::items_older_than(report, cutoff) {
             if item.severity.unwrap_or(0) < 4 {
                 continue;
@@ -37,9 +38,12 @@ impl Template for HostFindingsCsvOlderThanTemplate {
             let netbios = host.and_then(|h| h.netbios.clone()).unwrap_or_default();
             let finding = item.plugin_name.clone().unwrap_or_default();
             let risk = item.risk_factor.clone().unwrap_or_default();
-            let row = format!("{ip}, {fqdn}, {netbios}, {finding}, {risk}");
-            renderer.text(&row)?;
+            rows.push(vec![ip, fqdn, netbios, finding, risk]);
         }
+        renderer.table(
+            &["IP Address", "FQDN", "Netbios Name", "Finding", "Risk Factor"],
+            &rows,
+        )?;
         Ok(())
     }
 }
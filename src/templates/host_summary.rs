@@ -2,10 +2,10 @@ use std::collections::HashMap;
 use std::error::Error;
 
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Align, Renderer, TableOptions};
 use crate::template::{
     Template, helpers,
-    template_helper::{self, host, shares},
+    template_helper::{host, shares},
 };
 
 /// Rough port of the Host Summary report from the Ruby implementation.
@@ -21,23 +21,36 @@ impl Template for HostSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
             .unwrap_or("Host Summary Report");
         renderer.text(title)?;
         renderer.text(&format!("Total Hosts: {}", report.hosts.len()))?;
-        for (idx, host) in report.hosts.iter().enumerate() {
+        // `sort=tier` lists the most critical asset tiers first.
+        let order: Vec<usize> = if args.get("sort").map(String::as_str) == Some("tier") {
+            host::hosts_by_tier(report)
+                .into_iter()
+                .flat_map(|(_, hosts)| hosts)
+                .collect()
+        } else {
+            (0..report.hosts.len()).collect()
+        };
+        for idx in order {
+            let host = &report.hosts[idx];
             renderer.text(&host::host_heading(host))?;
             renderer.text(&host::host_label(host))?;
+            if let Some(tier) = host::host_tier(report, idx) {
+                renderer.text(&format!("Tier: {tier}"))?;
+            }
 
             // Gather vulnerability counts for this host.
             let items: Vec<&crate::models::Item> = report
                 .items
                 .iter()
                 .filter(|it| {
-                    it.host_id == Some(idx as i32)
+                    it.host_id == Some(host.id)
                         || (it.host_id.is_none() && report.hosts.len() == 1)
                 })
                 .collect();
@@ -49,21 +62,25 @@ impl Template for HostSummaryTemplate {
                     }
                 }
             }
-            let sev_fields = [
-                template_helper::field("Critical", &counts[4].to_string()),
-                template_helper::field("High", &counts[3].to_string()),
-                template_helper::field("Medium", &counts[2].to_string()),
-                template_helper::field("Low", &counts[1].to_string()),
-                template_helper::field("Info", &counts[0].to_string()),
-            ]
-            .join("\n");
-            renderer.text(&sev_fields)?;
+            let sev_rows: Vec<Vec<String>> = ["Info", "Low", "Medium", "High", "Critical"]
+                .iter()
+                .zip(counts)
+                .rev()
+                .map(|(label, count)| vec![label.to_string(), count.to_string()])
+                .collect();
+            renderer.table_with(
+                &["Severity", "Count"],
+                &sev_rows,
+                &TableOptions::default()
+                    .widths(&[3.0, 1.0])
+                    .align(&[Align::Left, Align::Right]),
+            )?;
 
             // Enumerate shares for this host from host properties with a `share-` prefix.
             let shares_vec: Vec<(String, String)> = report
                 .host_properties
                 .iter()
-                .filter(|p| p.host_id == Some(idx as i32))
+                .filter(|p| p.host_id == Some(host.id))
                 .filter_map(|p| {
                     let name = p.name.as_ref()?;
                     let value = p.value.as_ref()?;
@@ -19,7 +19,7 @@ impl Template for MaliciousProcessDetectionTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("Malicious Process Detection Findings")?;
         let items: Vec<&Item> = report
             .items
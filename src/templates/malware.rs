@@ -19,7 +19,7 @@ impl Template for MalwareTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.heading(1, "Malware Report")?;
 
         // Embed infection graph
@@ -32,10 +32,12 @@ pub mod ssl_medium_str_cipher_support;
 pub mod ssl_summary;
 pub mod stig_findings_summary;
 pub mod stig_detailed;
+pub mod suppressions;
 pub mod talking_points;
 pub mod technical_findings;
 pub mod template;
 pub mod sans_top;
+pub mod scan_diff;
 pub mod top_25;
 pub mod unsupported_os;
 pub mod unsupported_software;
@@ -75,10 +77,12 @@ pub use ssl_medium_str_cipher_support::SslMediumStrCipherSupportTemplate;
 pub use ssl_summary::SslSummaryTemplate;
 pub use stig_findings_summary::StigFindingsSummaryTemplate;
 pub use stig_detailed::StigDetailedTemplate;
+pub use suppressions::SuppressionsTemplate;
 pub use talking_points::TalkingPointsTemplate;
 pub use technical_findings::TechnicalFindingsTemplate;
 pub use template::TemplateTemplate;
 pub use sans_top::SansTopTemplate;
+pub use scan_diff::ScanDiffTemplate;
 pub use top_25::Top25Template;
 pub use unsupported_os::UnsupportedOsTemplate;
 pub use unsupported_software::UnsupportedSoftwareTemplate;
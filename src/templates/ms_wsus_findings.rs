@@ -20,7 +20,7 @@ impl Template for MSWSUSFindingsTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("Patch Management: WSUS Report")?;
         let items: Vec<&Item> = report
             .items
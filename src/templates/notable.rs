@@ -23,7 +23,7 @@ impl Template for NotableTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
@@ -100,10 +100,8 @@ impl Template for NotableTemplate {
                 renderer.text(&desc)?;
             }
             renderer.text("Affected Hosts:")?;
-            renderer.text("Host")?;
-            for host in hosts {
-                renderer.text(&host)?;
-            }
+            let rows: Vec<Vec<String>> = hosts.into_iter().map(|h| vec![h]).collect();
+            renderer.table(&["Host"], &rows)?;
             renderer.start_new_page()?;
         }
 
@@ -1,9 +1,10 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error;
 
+use crate::models::{CvssScores, CvssVersion};
 use crate::parser::NessusReport;
 use crate::renderer::Renderer;
-use crate::template::Template;
+use crate::template::{helpers, Template};
 
 /// Detailed listing of top findings with affected hosts and descriptions.
 pub struct NotableDetailedTemplate;
@@ -18,12 +19,13 @@ impl Template for NotableDetailedTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
             .unwrap_or("Notable Vulnerabilities");
         renderer.heading(1, title)?;
+        let cvss_version = helpers::cvss_version(report, args);
 
         // Map plugin id -> (plugin name, set of hosts, description, cvss, solution)
         let mut plugins: BTreeMap<
@@ -32,7 +34,7 @@ impl Template for NotableDetailedTemplate {
                 String,
                 BTreeSet<String>,
                 String,
-                Option<f32>,
+                Option<(CvssVersion, f32)>,
                 Option<String>,
             ),
         > = BTreeMap::new();
@@ -63,7 +65,7 @@ impl Template for NotableDetailedTemplate {
                 .clone()
                 .unwrap_or_else(|| format!("Plugin {pid}"));
             let desc = item.description.clone().unwrap_or_default();
-            let cvss = item.cvss_base_score;
+            let cvss = item.preferred_cvss(cvss_version);
             let solution = item.solution.clone();
 
             plugins
@@ -90,8 +92,8 @@ impl Template for NotableDetailedTemplate {
             if !desc.is_empty() {
                 renderer.text(&format!("Description: {desc}"))?;
             }
-            if let Some(score) = cvss {
-                renderer.text(&format!("CVSS Base Score: {score}"))?;
+            if let Some((version, score)) = cvss {
+                renderer.text(&format!("CVSS {version} Base Score: {score}"))?;
             }
             if let Some(sol) = solution {
                 if !sol.is_empty() {
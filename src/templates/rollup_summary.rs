@@ -18,14 +18,14 @@ impl Template for RollupSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
             .unwrap_or("Findings Summary Report");
         renderer.text(title)?;
 
-        let mut print_group = |sev: i32, label: &str| -> Result<(), Box<dyn Error>> {
+        let mut print_group = |sev: i32, label: &str| -> Result<(), Box<dyn Error + Send + Sync>> {
             let mut unique: BTreeMap<i32, String> = BTreeMap::new();
             for item in &report.items {
                 if item.rollup_finding == Some(true) {
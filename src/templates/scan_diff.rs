@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::analysis::diff::{DiffStatus, FindingDiff};
+use crate::parser::NessusReport;
+use crate::renderer::{Align, Renderer, TableOptions};
+use crate::template::Template;
+
+/// Delta between a baseline and a current scan produced by the `diff`
+/// command: a summary of new, remediated, severity-changed and persistent
+/// findings followed by one table per category.
+///
+/// Set the `min_severity` argument (0-4) to leave out lower findings.
+pub struct ScanDiffTemplate;
+
+impl ScanDiffTemplate {
+    fn severity_label(sev: Option<i32>) -> &'static str {
+        match sev {
+            Some(4) => "Critical",
+            Some(3) => "High",
+            Some(2) => "Medium",
+            Some(1) => "Low",
+            Some(_) => "Info",
+            None => "-",
+        }
+    }
+}
+
+impl Template for ScanDiffTemplate {
+    fn name(&self) -> &str {
+        "scan_diff"
+    }
+
+    fn generate(
+        &self,
+        report: &NessusReport,
+        renderer: &mut dyn Renderer,
+        args: &HashMap<String, String>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let diff = report
+            .diff
+            .as_ref()
+            .ok_or("the scan_diff template needs two scans; use the `diff` command")?;
+        let min_severity: i32 = args
+            .get("min_severity")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let title = args
+            .get("title")
+            .map(String::as_str)
+            .unwrap_or("Scan Comparison");
+        renderer.heading(1, title)?;
+        renderer.text(&format!(
+            "Baseline: {}",
+            diff.baseline.as_deref().unwrap_or("(untitled)")
+        ))?;
+        renderer.text(&format!(
+            "Current: {}",
+            diff.current.as_deref().unwrap_or("(untitled)")
+        ))?;
+
+        let shown = |f: &&FindingDiff| {
+            f.new_severity.or(f.old_severity).unwrap_or(0) >= min_severity
+        };
+
+        renderer.heading(2, "Summary")?;
+        let summary: Vec<Vec<String>> = DiffStatus::ALL
+            .iter()
+            .map(|s| vec![s.to_string(), diff.with_status(*s).filter(shown).count().to_string()])
+            .collect();
+        renderer.table_with(
+            &["Status", "Findings"],
+            &summary,
+            &TableOptions::default()
+                .widths(&[3.0, 1.0])
+                .align(&[Align::Left, Align::Right]),
+        )?;
+
+        for status in DiffStatus::ALL {
+            let rows: Vec<Vec<String>> = diff
+                .with_status(status)
+                .filter(shown)
+                .map(|f| {
+                    let port = match (f.port, f.protocol.as_deref()) {
+                        (Some(p), Some(proto)) => format!("{p}/{proto}"),
+                        (Some(p), None) => p.to_string(),
+                        _ => String::new(),
+                    };
+                    vec![
+                        f.host.clone(),
+                        f.plugin_id.map(|p| p.to_string()).unwrap_or_default(),
+                        f.plugin_name.clone().unwrap_or_default(),
+                        port,
+                        Self::severity_label(f.old_severity).to_string(),
+                        Self::severity_label(f.new_severity).to_string(),
+                    ]
+                })
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+            renderer.heading(2, &format!("{status} Findings"))?;
+            renderer.table_with(
+                &["Host", "Plugin ID", "Finding", "Port", "Baseline", "Current"],
+                &rows,
+                &TableOptions::default().widths(&[2.0, 1.0, 4.0, 1.0, 1.0, 1.0]),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::diff::ScanDiff;
+    use crate::renderer::CsvRenderer;
+
+    #[test]
+    fn renders_summary_and_sections() {
+        let finding = |status, old, new| FindingDiff {
+            status,
+            host: "10.0.0.1".into(),
+            plugin_id: Some(1),
+            plugin_name: Some("Finding".into()),
+            port: Some(443),
+            protocol: Some("tcp".into()),
+            old_severity: old,
+            new_severity: new,
+        };
+        let report = NessusReport {
+            diff: Some(ScanDiff {
+                baseline: Some("January".into()),
+                current: Some("February".into()),
+                findings: vec![
+                    finding(DiffStatus::New, None, Some(3)),
+                    finding(DiffStatus::Remediated, Some(0), None),
+                ],
+            }),
+            ..NessusReport::default()
+        };
+        let mut renderer = CsvRenderer::new();
+        let args = HashMap::from([("min_severity".to_string(), "1".to_string())]);
+        ScanDiffTemplate.generate(&report, &mut renderer, &args).unwrap();
+        let mut out = Vec::new();
+        renderer.save(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("New,1"));
+        assert!(out.contains("Remediated,0"));
+        assert!(out.contains("10.0.0.1,1,Finding,443/tcp,-,High"));
+        assert!(!out.contains("Remediated Findings"));
+    }
+
+    #[test]
+    fn requires_diff() {
+        let mut renderer = CsvRenderer::new();
+        let err = ScanDiffTemplate
+            .generate(&NessusReport::default(), &mut renderer, &HashMap::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("diff"));
+    }
+}
@@ -3,7 +3,7 @@ use std::error::Error;
 
 use crate::models::ServiceDescription;
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Align, Renderer, TableOptions};
 use crate::template::{Template, template_helper::host};
 
 /// Inventory of detected network services grouped by host.
@@ -19,7 +19,7 @@ impl Template for ServiceInventoryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args
             .get("title")
             .map(String::as_str)
@@ -41,20 +41,29 @@ impl Template for ServiceInventoryTemplate {
                 .map(host::host_label)
                 .unwrap_or_else(|| format!("Host {hid}"));
             renderer.heading(2, &label)?;
-            renderer.text("Service,Port,Banner")?;
             services.sort_by_key(|s| s.port.unwrap_or(0));
-            for svc in services {
-                let name = svc.svc_name.as_deref().unwrap_or("");
-                let port = svc.port.map(|p| p.to_string()).unwrap_or_default();
-                let banner = svc
-                    .description
-                    .as_deref()
-                    .unwrap_or("")
-                    .replace('\n', " ")
-                    .trim()
-                    .to_string();
-                renderer.text(&format!("{name},{port},{banner}"))?;
-            }
+            let rows: Vec<Vec<String>> = services
+                .into_iter()
+                .map(|svc| {
+                    let name = svc.svc_name.clone().unwrap_or_default();
+                    let port = svc.port.map(|p| p.to_string()).unwrap_or_default();
+                    let banner = svc
+                        .description
+                        .as_deref()
+                        .unwrap_or("")
+                        .replace('\n', " ")
+                        .trim()
+                        .to_string();
+                    vec![name, port, banner]
+                })
+                .collect();
+            renderer.table_with(
+                &["Service", "Port", "Banner"],
+                &rows,
+                &TableOptions::default()
+                    .widths(&[2.0, 1.0, 5.0])
+                    .align(&[Align::Left, Align::Right, Align::Left]),
+            )?;
         }
 
         Ok(())
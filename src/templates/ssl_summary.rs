@@ -18,7 +18,7 @@ impl Template for SslSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("SSL Summary")?;
         let (per_host, global) = ssl_template_helper::ssl_stats(report);
         renderer.text(&format!("Total SSL findings: {}", global.total))?;
@@ -3,7 +3,7 @@ use std::error::Error;
 
 use crate::models::Item;
 use crate::parser::NessusReport;
-use crate::renderer::Renderer;
+use crate::renderer::{Renderer, TableOptions};
 use crate::template::Template;
 
 /// Return the STIG category for a given plugin ID.
@@ -31,7 +31,7 @@ impl Template for StigDetailedTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.text("STIG Detailed Findings")?;
 
         for host in &report.hosts {
@@ -60,16 +60,23 @@ impl Template for StigDetailedTemplate {
                         continue;
                     }
                     renderer.text(cat)?;
-                    renderer.text("Plugin ID, Name, Solution")?;
-                    for item in list {
-                        let id = item.plugin_id.unwrap_or(0);
-                        let name = item
-                            .plugin_name
-                            .clone()
-                            .unwrap_or_else(|| format!("Plugin {id}"));
-                        let solution = item.solution.clone().unwrap_or_default();
-                        renderer.text(&format!("{id}, {name}, {solution}"))?;
-                    }
+                    let rows: Vec<Vec<String>> = list
+                        .iter()
+                        .map(|item| {
+                            let id = item.plugin_id.unwrap_or(0);
+                            let name = item
+                                .plugin_name
+                                .clone()
+                                .unwrap_or_else(|| format!("Plugin {id}"));
+                            let solution = item.solution.clone().unwrap_or_default();
+                            vec![id.to_string(), name, solution]
+                        })
+                        .collect();
+                    renderer.table_with(
+                        &["Plugin ID", "Name", "Solution"],
+                        &rows,
+                        &TableOptions::default().widths(&[1.0, 3.0, 4.0]),
+                    )?;
                 }
             }
         }
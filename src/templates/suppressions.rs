@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use chrono::Local;
+
+use crate::analysis::suppression::{self, Suppression};
+use crate::parser::NessusReport;
+use crate::renderer::{Renderer, TableOptions};
+use crate::template::{Template, template_helper::host};
+
+/// Appendix listing the accepted-risk register: the active suppressions with
+/// the findings they hide, then the expired ones whose findings are reported
+/// again.
+pub struct SuppressionsTemplate;
+
+impl SuppressionsTemplate {
+    fn scope(s: &Suppression) -> String {
+        let mut parts = vec![s.host.clone().unwrap_or_else(|| "all hosts".to_string())];
+        if let Some(port) = s.port {
+            parts.push(format!("port {port}"));
+        }
+        if let Some(output) = &s.output {
+            parts.push(format!("output /{output}/"));
+        }
+        parts.join(", ")
+    }
+
+    fn plugin_label(report: &NessusReport, plugin_id: i32) -> String {
+        report
+            .plugins
+            .iter()
+            .find(|p| p.plugin_id == Some(plugin_id))
+            .and_then(|p| p.plugin_name.as_deref())
+            .map_or_else(|| plugin_id.to_string(), |n| format!("{plugin_id} ({n})"))
+    }
+
+    /// Findings marked as suppressed, for reports rendered without a register.
+    fn suppressed_items(report: &NessusReport) -> Vec<Vec<String>> {
+        report
+            .items
+            .iter()
+            .filter(|item| item.suppressed == Some(true))
+            .map(|item| {
+                let host = item
+                    .host_id
+                    .and_then(|id| report.hosts.iter().find(|h| h.id == id))
+                    .map_or_else(|| "-".to_string(), host::host_label);
+                let port = match (item.port, item.protocol.as_deref()) {
+                    (Some(p), Some(proto)) => format!("{p}/{proto}"),
+                    (Some(p), None) => p.to_string(),
+                    _ => String::new(),
+                };
+                vec![host, Self::plugin_label(report, item.plugin_id.unwrap_or(0)), port]
+            })
+            .collect()
+    }
+
+    fn rows(report: &NessusReport, list: &[&Suppression]) -> Vec<Vec<String>> {
+        list.iter()
+            .map(|s| {
+                let plugin = Self::plugin_label(report, s.plugin_id);
+                vec![
+                    s.id.clone(),
+                    plugin,
+                    Self::scope(s),
+                    suppression::matching_items(report, s).to_string(),
+                    s.expires.to_string(),
+                    s.approver.clone(),
+                    s.justification.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Template for SuppressionsTemplate {
+    fn name(&self) -> &str {
+        "suppressions"
+    }
+
+    fn generate(
+        &self,
+        report: &NessusReport,
+        renderer: &mut dyn Renderer,
+        args: &HashMap<String, String>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let title = args
+            .get("title")
+            .map(String::as_str)
+            .unwrap_or("Appendix: Accepted Risks");
+        renderer.heading(1, title)?;
+        if report.suppressions.is_empty() {
+            // The register is not stored with the report; findings saved as
+            // suppressed are still listed when no suppressions file is found.
+            let rows = Self::suppressed_items(report);
+            if rows.is_empty() {
+                renderer.text("No suppressions were applied to this report.")?;
+            } else {
+                renderer.text(
+                    "No suppressions file was found. These findings were suppressed when \
+                     the report was saved:",
+                )?;
+                renderer.table_with(
+                    &["Host", "Plugin", "Port"],
+                    &rows,
+                    &TableOptions::default().widths(&[2.0, 4.0, 1.0]),
+                )?;
+            }
+            return Ok(());
+        }
+
+        let today = Local::now().naive_local().date();
+        let (expired, active): (Vec<&Suppression>, Vec<&Suppression>) =
+            report.suppressions.iter().partition(|s| s.is_expired(today));
+        let headers = [
+            "ID",
+            "Plugin",
+            "Scope",
+            "Findings",
+            "Expires",
+            "Approver",
+            "Justification",
+        ];
+        let options = TableOptions::default().widths(&[1.0, 3.0, 2.0, 1.0, 1.0, 1.5, 3.0]);
+        for (heading, list) in [("Active Suppressions", active), ("Expired Suppressions", expired)] {
+            renderer.heading(2, heading)?;
+            if list.is_empty() {
+                renderer.text("None.")?;
+            } else {
+                renderer.table_with(&headers, &Self::rows(report, &list), &options)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -21,15 +21,15 @@ impl Template for TemplateTemplate {
         _report: &NessusReport,
         renderer: &mut dyn Renderer,
         args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let title = args.get("title").map(String::as_str).unwrap_or("Template");
         renderer.heading(1, title)?;
         // Demonstrate embedding an image from the bundled assets directory.
-        // The image bytes are included in the binary and encoded as a data URI
-        // for renderers that accept inline images.
+        // The image bytes are included in the binary and encoded as a data URI;
+        // renderers without inline images fall back to printing the URI.
         let encoded = general_purpose::STANDARD.encode(assets::nessus_logo_jpg());
         let logo_data_uri = format!("data:image/jpeg;base64,{encoded}");
-        renderer.text(&logo_data_uri)?;
+        renderer.image_data_uri(&logo_data_uri)?;
         Ok(())
     }
 }
@@ -19,7 +19,7 @@ impl Template for UnsupportedOsTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.heading(1, "Unsupported Operating Systems")?;
 
         let windows = unsupported_os_windows(report);
@@ -19,7 +19,7 @@ impl Template for UnsupportedSoftwareTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         renderer.heading(1, "Unsupported Software")?;
 
         let mut by_host: BTreeMap<String, Vec<String>> = BTreeMap::new();
@@ -96,18 +96,22 @@ mod tests {
     }
 
     impl Renderer for TestRenderer {
-        fn text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        fn text(&mut self, text: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
             self.out.push_str(text);
             self.out.push('\n');
             Ok(())
         }
-        fn start_new_page(&mut self) -> Result<(), Box<dyn Error>> {
+        fn start_new_page(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
             Ok(())
         }
-        fn save(&mut self, _w: &mut dyn io::Write) -> Result<(), Box<dyn Error>> {
+        fn save(&mut self, _w: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send + Sync>> {
             Ok(())
         }
-        fn heading(&mut self, _level: usize, text: &str) -> Result<(), Box<dyn Error>> {
+        fn heading(
+            &mut self,
+            _level: usize,
+            text: &str,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
             self.text(text)
         }
     }
@@ -160,6 +164,10 @@ mod tests {
             plugin_preferences: Vec::new(),
             server_preferences: Vec::new(),
             filters: Filters::default(),
+            cvss_version: Default::default(),
+            risk_scoring: Default::default(),
+            diff: None,
+            suppressions: Vec::new(),
             scanner: Scanner::default(),
         }
     }
@@ -40,7 +40,7 @@ impl Template for VirtualMachineSummaryTemplate {
         report: &NessusReport,
         renderer: &mut dyn Renderer,
         _args: &HashMap<String, String>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         const PLUGIN_ID: i32 = 20094;
         renderer.heading(1, "Virtual Machine Summary")?;
         let mut groups: BTreeMap<&'static str, BTreeSet<String>> = BTreeMap::new();
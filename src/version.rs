@@ -1,9 +1,9 @@
 use cargo_lock::Lockfile;
 use diesel::prelude::*;
 use diesel::result::Error;
-use diesel::sqlite::SqliteConnection;
 use tracing::warn;
 
+use crate::db::DbConnection;
 use crate::schema::versions::dsl::{version as version_col, versions};
 
 /// Print application, Rust, and crate versions then exit.
@@ -19,12 +19,12 @@ pub fn print() {
 }
 
 /// Return the database schema version if available.
-pub fn db_version(conn: &mut SqliteConnection) -> Result<Option<String>, Error> {
+pub fn db_version(conn: &mut DbConnection) -> Result<Option<String>, Error> {
     versions.select(version_col).first::<String>(conn).optional()
 }
 
 /// Warn if the database schema version differs from the application version.
-pub fn warn_on_mismatch(conn: &mut SqliteConnection) {
+pub fn warn_on_mismatch(conn: &mut DbConnection) {
     if let Ok(Some(db_ver)) = db_version(conn) {
         let app_ver = env!("CARGO_PKG_VERSION");
         if db_ver != app_ver {
@@ -1,4 +1,5 @@
 use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 use std::fs;
 use tempfile::tempdir;
@@ -75,6 +76,17 @@ fn parse_with_nil_renderer_creates_no_file() {
     assert!(!output.exists());
 }
 
+#[test]
+fn unknown_renderer_lists_registered_renderers() {
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "render", "--report-id", "1", "-o", "out.x", "-t", "simple"])
+        .args(["--renderer", "docx"])
+        .assert()
+        .failure()
+        .stderr(contains("[possible values: pdf, csv, html, markdown, rtf, typst, xlsx, nil]"));
+}
+
 #[test]
 fn parse_with_typst_renderer_writes_file() {
     let tmp = tempdir().unwrap();
@@ -228,3 +240,1036 @@ fn parse_invalid_input_displays_error() {
         .failure()
         .stderr(contains("unsupported root element"));
 }
+
+#[test]
+fn parse_accepts_several_files_and_directories() {
+    let tmp = tempdir().unwrap();
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    let scans = tmp.path().join("scans");
+    fs::create_dir(&scans).unwrap();
+    fs::copy("tests/fixtures/diff_baseline.nessus", scans.join("zone1.nessus")).unwrap();
+    fs::copy("tests/fixtures/multi_host.nessus", scans.join("zone2.nessus")).unwrap();
+    fs::write(scans.join("broken.xml"), "<foo></foo>").unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+    let output = tmp.path().join("out.csv");
+
+    let parse = |extra: &[&str]| {
+        let mut cmd = Command::cargo_bin("risu-rs").unwrap();
+        cmd.current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml", "parse"])
+            .arg(&sample)
+            .arg(&scans)
+            .args(["-t", "host_summary", "--renderer", "csv", "-o"])
+            .arg(&output)
+            .args(extra);
+        cmd.assert()
+    };
+
+    parse(&[]).failure().stderr(contains("unsupported root element"));
+
+    let assert = parse(&["--continue-on-error"]).success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("broken.xml: skipped"));
+    assert!(stdout.contains("zone1.nessus: 1 hosts, 2 items"));
+    assert!(stdout.contains("Parsed 3 of 4 files"));
+    let contents = fs::read_to_string(&output).unwrap();
+    let hosts = 1 + 1 + fs::read_to_string("tests/fixtures/multi_host.nessus")
+        .unwrap()
+        .matches("<ReportHost ")
+        .count();
+    assert!(contents.contains(&format!("Total Hosts: {hosts}")));
+}
+
+#[test]
+fn import_persists_report_for_render() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\n", db.display()),
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args([
+            "--no-banner",
+            "--config-file",
+            "config.yml",
+            "import",
+            sample.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let report_id = stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("Imported report ID: "))
+        .expect("report id printed")
+        .trim()
+        .to_string();
+    assert!(db.exists());
+
+    let output = tmp.path().join("out.csv");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args([
+            "--no-banner",
+            "--config-file",
+            "config.yml",
+            "render",
+            "--report-id",
+            &report_id,
+            "-o",
+            output.to_str().unwrap(),
+            "-t",
+            "host_summary",
+            "--renderer",
+            "csv",
+        ])
+        .assert()
+        .success();
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.contains("Total Hosts: 1"));
+}
+
+#[test]
+fn import_stream_matches_in_memory_import() {
+    let tmp = tempdir().unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\n", db.display()),
+    )
+    .unwrap();
+    let sample = fs::canonicalize("tests/fixtures/multi_host.nessus").unwrap();
+    let run = |args: &[&str]| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml"])
+            .args(args)
+            .assert()
+            .success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+    let report_id = |stdout: &str| {
+        stdout
+            .lines()
+            .find_map(|l| l.strip_prefix("Imported report ID: ").map(str::to_string))
+            .expect("report id printed")
+    };
+    let render = |id: &str, name: &str| {
+        let output = tmp.path().join(name);
+        run(&[
+            "render",
+            "--report-id",
+            id,
+            "-o",
+            output.to_str().unwrap(),
+            "-t",
+            "host_summary",
+            "--renderer",
+            "csv",
+        ]);
+        fs::read_to_string(output).unwrap()
+    };
+
+    let loaded = run(&["import", sample.to_str().unwrap()]);
+    let streamed = run(&["import", "--stream", "--batch-hosts", "2", sample.to_str().unwrap()]);
+    let hosts = fs::read_to_string(&sample).unwrap().matches("<ReportHost ").count();
+    assert!(streamed.contains(&format!("Streamed {hosts} hosts")));
+    assert_eq!(
+        render(&report_id(&loaded), "loaded.csv"),
+        render(&report_id(&streamed), "streamed.csv")
+    );
+}
+
+#[test]
+fn import_stream_applies_plugin_filters_and_rolls_back_on_error() {
+    let tmp = tempdir().unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\n", db.display()),
+    )
+    .unwrap();
+    let sample = fs::canonicalize("tests/fixtures/multi_host.nessus").unwrap();
+    let run = |args: &[&str]| {
+        Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml"])
+            .args(args)
+            .assert()
+    };
+
+    let sample = sample.to_str().unwrap();
+    run(&["--blacklist", "2", "import", "--stream", "--batch-hosts", "1", sample])
+        .success()
+        .stdout(contains("Streamed 2 hosts, 2 items"));
+
+    // The first host is flushed before the parser reaches the broken second one.
+    let broken = tmp.path().join("broken.nessus");
+    let text = fs::read_to_string(sample).unwrap();
+    let text = text.replacen("<ReportHost name=\"h2\">", "<ReportHost name=\"h2\"></Oops>", 1);
+    fs::write(&broken, text).unwrap();
+    run(&["import", "--stream", "--batch-hosts", "1", broken.to_str().unwrap()]).failure();
+    run(&["export", "--report-id", "2"]).failure();
+
+    let assert = run(&["export", "--report-id", "1"]).success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(json["hosts"].as_array().unwrap().len(), 2);
+    let plugins: Vec<i64> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["plugin_id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(plugins, [1, 1]);
+}
+
+#[test]
+fn risk_explains_factors_with_configured_weights() {
+    let tmp = tempdir().unwrap();
+    fs::write(
+        tmp.path().join("config.yml"),
+        "cvss_version: 3\nrisk_scoring:\n  vpr: 1.0\n",
+    )
+    .unwrap();
+    let scan = fs::canonicalize("tests/fixtures/cvss_modern.nessus").unwrap();
+    let run = |args: &[&str]| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml", "risk"])
+            .arg(&scan)
+            .args(args)
+            .assert()
+            .success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+
+    let finding = run(&["--plugin-id", "200001"]);
+    assert!(finding.contains("Finding 200001 OpenSSL Vulnerability on 10.0.0.5:443/tcp: 14.75"));
+    assert!(finding.contains("v3 base 9.8 x 0.8"));
+    assert!(finding.contains("VPR 6.7 x 1"));
+    assert!(finding.contains("no known exploit"));
+
+    let host = run(&["--host", "10.0.0.5"]);
+    assert!(host.contains("Host 10.0.0.5 risk: 14.75 (1 findings, criticality not set)"));
+    assert!(host.contains("200001 OpenSSL Vulnerability"));
+
+    let network = run(&[]);
+    assert!(network.contains("Network risk: 14.75 (1 findings on 1 hosts)"));
+    assert!(network.contains("14.75  10.0.0.5"));
+}
+
+#[test]
+fn merge_correlates_hosts_across_scanner_imports() {
+    let tmp = tempdir().unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\n", db.display()),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml"])
+            .args(args)
+            .assert()
+            .success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+    let import = |fixture: &str| {
+        let path = fs::canonicalize(fixture).unwrap();
+        run(&["import", path.to_str().unwrap()])
+            .lines()
+            .find_map(|l| l.strip_prefix("Imported report ID: ").map(str::to_string))
+            .expect("report id printed")
+    };
+    let nessus = import("tests/fixtures/merge_nessus.nessus");
+    let nmap = import("tests/fixtures/nmap_full.xml");
+
+    // The Nessus host on 10.10.0.2 and the Nmap host on 192.168.56.2 share a MAC.
+    let stdout = run(&["merge", &nessus, &nmap, "--title", "Lab assets"]);
+    assert!(stdout.contains("3 hosts correlated into 2 assets"));
+    let merged = stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("Merged report ID: "))
+        .expect("merged id printed")
+        .trim()
+        .to_string();
+
+    let export: serde_json::Value =
+        serde_json::from_str(&run(&["export", "--report-id", &merged])).unwrap();
+    assert_eq!(export["report"]["title"], "Lab assets");
+    let hosts = export["hosts"].as_array().unwrap();
+    assert_eq!(hosts.len(), 2);
+    assert_eq!(hosts[0]["ip"], "10.10.0.2");
+    assert_eq!(hosts[0]["fqdn"], "router.lab.example");
+    let ports: Vec<_> = export["service_descriptions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|s| s["host_id"] == 0)
+        .map(|s| s["port"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ports, [22, 80]);
+
+    let output = tmp.path().join("assets.csv");
+    let out = output.to_str().unwrap();
+    run(&["render", "--report-id", &merged, "-t", "assets", "--renderer", "csv", "-o", out]);
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.contains("Aliases: 192.168.56.2"));
+}
+
+#[test]
+fn cvss_version_flag_selects_template_scores() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/cvss_modern.nessus").unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\ncvss_version: 3\n", db.display()),
+    )
+    .unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "import"])
+        .arg(&sample)
+        .assert()
+        .success();
+
+    // Scores survive the database round trip; the flag overrides the config.
+    let render = |extra: &[&str], name: &str| {
+        let output = tmp.path().join(name);
+        Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml"])
+            .args(extra)
+            .args(["render", "-t", "notable_detailed", "--renderer", "csv", "-o"])
+            .arg(&output)
+            .assert()
+            .success();
+        fs::read_to_string(output).unwrap()
+    };
+
+    assert!(render(&[], "v3.csv").contains("CVSS v3 Base Score: 9.8"));
+    assert!(render(&["--cvss-version", "4.0"], "v4.csv").contains("CVSS v4 Base Score: 9.3"));
+    assert!(render(&["--cvss-version", "2"], "v2.csv").contains("CVSS v2 Base Score: 5"));
+}
+
+#[test]
+fn service_inventory_renders_from_nmap_xml() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/nmap_full.xml").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("services.csv");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args([
+            "--no-banner",
+            "--config-file",
+            "config.yml",
+            "parse",
+            sample.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            "-t",
+            "service_inventory",
+        ])
+        .assert()
+        .success();
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.contains("192.168.56.2"));
+    assert!(contents.contains("ssh,22,OpenSSH 8.9p1"));
+    assert!(contents.contains("http,80,Apache httpd 2.4.49"));
+}
+
+#[test]
+fn diff_renders_scan_delta() {
+    let tmp = tempdir().unwrap();
+    let baseline = fs::canonicalize("tests/fixtures/diff_baseline.nessus").unwrap();
+    let current = fs::canonicalize("tests/fixtures/diff_current.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("diff.csv");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "diff"])
+        .arg(&baseline)
+        .arg(&current)
+        .args(["--renderer", "csv", "-o"])
+        .arg(&output)
+        .assert()
+        .success()
+        .stdout(contains("1 new, 1 remediated, 0 severity changed, 1 persistent"));
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.contains("10.0.0.5,1003,Expired TLS Certificate,443/tcp,-,Critical"));
+    assert!(contents.contains("10.0.0.5,1002,Outdated Web Server,80/tcp,High,-"));
+    assert!(contents.contains("Persistent,1"));
+}
+
+#[test]
+fn html_extension_selects_html_renderer() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("report.html");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["-t", "top_25", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.starts_with("<!DOCTYPE html>"));
+    assert!(contents.contains("<nav class=\"toc\">"));
+    assert!(contents.contains("<h1 id=\"sec-1-"));
+}
+
+#[test]
+fn markdown_renderer_writes_sidecar_images() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("graphs.md");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["-t", "graphs", "--template-arg", "images=sidecar", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.starts_with("# Graphs\n"));
+    assert!(contents.contains("![Figure 1](graphs-figure-1.png)"));
+    assert!(tmp.path().join("graphs-figure-1.png").exists());
+}
+
+#[test]
+fn create_template_for_markdown_renderer() {
+    let tmp = tempdir().unwrap();
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .env("HOME", tmp.path())
+        .args(["--no-banner", "create-template", "example", "--renderer", "markdown"])
+        .assert()
+        .success();
+    let path = tmp.path().join(".risu").join("templates").join("example.rs");
+    let contents = fs::read_to_string(path).unwrap();
+    assert!(contents.contains("/// Usage: risu-rs render -t example -o report.md"));
+}
+
+#[test]
+fn export_json_keeps_relationships_and_applies_older_than() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let export = |extra: &[&str]| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml", "export"])
+            .arg(&sample)
+            .args(extra)
+            .assert()
+            .success();
+        serde_json::from_slice::<serde_json::Value>(&assert.get_output().stdout).unwrap()
+    };
+
+    let all = export(&[]);
+    assert_eq!(all["hosts"][0]["ip"], "192.168.0.1");
+    assert_eq!(all["items"][0]["host_id"], all["hosts"][0]["id"]);
+    assert_eq!(all["items"][0]["plugin_id"], 100);
+    assert_eq!(all["patches"][0]["host_id"], 0);
+    assert!(all["host_properties"].as_array().is_some_and(|p| !p.is_empty()));
+
+    // The sample host was scanned in 2006: older than a day, newer than a century.
+    assert_eq!(export(&["--older-than", "1"])["items"].as_array().unwrap().len(), 1);
+    let recent = export(&["--older-than", "36500"]);
+    assert!(recent["items"].as_array().unwrap().is_empty());
+    assert!(recent["filters"]["older_than"].is_string());
+}
+
+#[test]
+fn export_to_stdout_is_json_with_the_banner_enabled() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let assert = Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--config-file", "config.yml", "export"])
+        .arg(&sample)
+        .assert()
+        .success()
+        .stderr(predicates::str::is_empty().not());
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(json["hosts"][0]["ip"], "192.168.0.1");
+}
+
+#[test]
+fn export_sarif_maps_plugins_to_rules_and_honours_blacklist() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/custom_findings.json").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let export = |extra: &[&str]| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml", "--cvss-version", "3"])
+            .args(extra)
+            .arg("export")
+            .arg(&sample)
+            .args(["--format", "sarif"])
+            .assert()
+            .success();
+        serde_json::from_slice::<serde_json::Value>(&assert.get_output().stdout).unwrap()
+    };
+
+    let sarif = export(&[]);
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "cloud-audit");
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0]["name"], "S3 bucket allows public read");
+    assert_eq!(rules[0]["properties"]["security-severity"], "7.5");
+    assert_eq!(run["taxonomies"][0]["name"], "CWE");
+    assert_eq!(run["taxonomies"][0]["taxa"][0]["id"], "284");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(results[0]["taxa"][0]["id"], "284");
+    assert_eq!(
+        results[2]["locations"][0]["logicalLocations"][0]["name"],
+        "10.0.0.9:8443/tcp"
+    );
+    assert_eq!(results[2]["ruleIndex"], 1);
+
+    let filtered = export(&["--blacklist", "910002"]);
+    let results = filtered["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(filtered["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn json_findings_render_like_scanner_output() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/custom_findings.json").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("hosts.csv");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse", "--post-process"])
+        .arg(&sample)
+        .args(["-t", "host_summary", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let contents = fs::read_to_string(output).unwrap();
+    assert!(contents.contains("Total Hosts: 2"));
+    assert!(contents.contains("10.0.0.5"));
+}
+
+#[test]
+fn xlsx_renderer_writes_workbook_for_fix_list() {
+    use std::io::Read;
+
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/diff_current.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("fixes.xlsx");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["-t", "fix_list", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    let mut zip = zip::ZipArchive::new(fs::File::open(output).unwrap()).unwrap();
+    let mut read = |name: &str| {
+        let mut s = String::new();
+        zip.by_name(name).unwrap().read_to_string(&mut s).unwrap();
+        s
+    };
+    assert!(read("xl/tables/table1.xml").contains("<autoFilter ref=\"A2:C3\"/>"));
+    assert!(read("xl/worksheets/sheet1.xml").contains("state=\"frozen\""));
+    let strings = read("xl/sharedStrings.xml");
+    assert!(strings.contains(">10.0.0.5</t>"));
+    assert!(strings.contains(">Expired TLS Certificate</t>"));
+}
+
+#[test]
+fn asset_context_tags_hosts_for_filters_and_tier_grouping() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/merge_nessus.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    fs::write(
+        tmp.path().join("assets.toml"),
+        r#"
+[[asset]]
+cidr = "10.10.0.0/16"
+os = "ubuntu"
+tags = ["pci", "linux"]
+tier = "critical"
+
+[[asset]]
+cidr = "192.168.56.0/24"
+tags = ["lab"]
+tier = "low"
+"#,
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "export"])
+        .arg(&sample)
+        .args(["--post-process", "--tag", "PCI"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    let hosts = json["hosts"].as_array().unwrap();
+    assert_eq!(hosts.len(), 1);
+    assert_eq!(hosts[0]["ip"], "10.10.0.2");
+    let props = json["host_properties"].as_array().unwrap();
+    assert!(props
+        .iter()
+        .any(|p| p["name"] == "asset-criticality" && p["value"] == "critical"));
+
+    let output = tmp.path().join("assets.md");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["--post-process", "--renderer", "markdown", "-t", "assets"])
+        .args(["--template-arg", "group_by=tier", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let md = fs::read_to_string(&output).unwrap();
+    let critical = md.find("Tier: critical").unwrap();
+    let low = md.find("Tier: low").unwrap();
+    assert!(critical < low, "{md}");
+    assert!(md.contains("Tags: pci, linux"), "{md}");
+}
+
+const DC_ASSETS: &str = r#"
+[[asset]]
+cidr = "10.0.0.9/32"
+tags = ["domain-controller"]
+tier = "critical"
+"#;
+
+#[test]
+fn asset_tiers_follow_hosts_after_sorting() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/unsorted_hosts.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    fs::write(tmp.path().join("assets.toml"), DC_ASSETS).unwrap();
+
+    let output = tmp.path().join("assets.md");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["--post-process", "--renderer", "markdown", "-t", "assets"])
+        .args(["--template-arg", "group_by=tier", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let md = fs::read_to_string(&output).unwrap();
+    let critical = &md[md.find("Tier: critical").unwrap()..md.find("No Tier").unwrap()];
+    assert!(critical.contains("10.0.0.9"), "{md}");
+    assert!(!critical.contains("10.0.0.1"), "{md}");
+    assert!(critical.contains("Tags: domain-controller"), "{md}");
+}
+
+#[test]
+fn tag_filter_keeps_findings_with_their_sorted_host() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/unsorted_hosts.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    fs::write(tmp.path().join("assets.toml"), DC_ASSETS).unwrap();
+
+    let assert = Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "export"])
+        .arg(&sample)
+        .args(["--post-process", "--tag", "domain-controller"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    let hosts = json["hosts"].as_array().unwrap();
+    assert_eq!(hosts.len(), 1);
+    assert_eq!(hosts[0]["ip"], "10.0.0.9");
+    let plugin_ids: Vec<&serde_json::Value> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| &i["plugin_id"])
+        .collect();
+    assert_eq!(plugin_ids, [1001]);
+    let props = json["host_properties"].as_array().unwrap();
+    assert!(props.iter().any(|p| p["host_id"] == hosts[0]["id"]
+        && p["name"] == "asset-tag"
+        && p["value"] == "domain-controller"));
+}
+
+#[test]
+fn suppressions_hide_accepted_findings_until_they_expire() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/merge_nessus.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    fs::write(
+        tmp.path().join("suppressions.toml"),
+        r#"
+[[suppression]]
+id = "AR-1"
+plugin_id = 1002
+host = "192.168.56.20"
+port = 445
+justification = "Signing breaks the backup appliance"
+approver = "CISO"
+expires = 2099-12-31
+
+[[suppression]]
+plugin_id = 1001
+host = "10.10.0.0/16"
+justification = "Legacy SSH clients"
+approver = "CISO"
+expires = 2020-01-31
+"#,
+    )
+    .unwrap();
+
+    let export = |format: &str| {
+        let assert = Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml", "export"])
+            .arg(&sample)
+            .args(["--post-process", "--format", format])
+            .assert()
+            .success()
+            .stderr(contains("#2 (plugin 1001) expired on 2020-01-31"));
+        serde_json::from_slice::<serde_json::Value>(&assert.get_output().stdout).unwrap()
+    };
+
+    let json = export("json");
+    let items = json["items"].as_array().unwrap();
+    let by_plugin = |pid: i64| items.iter().find(|i| i["plugin_id"] == pid).unwrap();
+    assert_eq!(by_plugin(1002)["suppressed"], true);
+    assert_eq!(by_plugin(1002)["severity"], -1);
+    assert_eq!(by_plugin(1002)["real_severity"], 3);
+    assert_eq!(by_plugin(1001)["severity"], 2);
+    assert!(by_plugin(1001)["suppressed"].is_null());
+
+    let sarif = export("sarif");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let suppressed = results.iter().find(|r| r["ruleId"] == "1002").unwrap();
+    assert_eq!(suppressed["level"], "error");
+    assert_eq!(suppressed["suppressions"][0]["status"], "accepted");
+    assert!(suppressed["suppressions"][0]["justification"]
+        .as_str()
+        .unwrap()
+        .starts_with("Signing breaks the backup appliance"));
+
+    let output = tmp.path().join("appendix.md");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["--post-process", "--renderer", "markdown", "-t", "suppressions", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+    let md = fs::read_to_string(&output).unwrap();
+    let active = md.find("Active Suppressions").unwrap();
+    let expired = md.find("Expired Suppressions").unwrap();
+    let ar1 = md.find("AR-1").unwrap();
+    let second = md.find("#2").unwrap();
+    assert!(active < ar1 && ar1 < expired && expired < second, "{md}");
+}
+
+#[test]
+fn suppressions_appendix_lists_saved_findings_without_a_register() {
+    let tmp = tempdir().unwrap();
+    let db = tmp.path().join("risu.db");
+    fs::write(
+        tmp.path().join("config.yml"),
+        format!("database_url: {}\n", db.display()),
+    )
+    .unwrap();
+    let register = tmp.path().join("suppressions.toml");
+    fs::write(
+        &register,
+        r#"
+[[suppression]]
+plugin_id = 1002
+host = "192.168.56.20"
+justification = "Signing breaks the backup appliance"
+approver = "CISO"
+expires = 2099-12-31
+"#,
+    )
+    .unwrap();
+    let sample = fs::canonicalize("tests/fixtures/merge_nessus.nessus").unwrap();
+    let run = |args: &[&str]| {
+        Command::cargo_bin("risu-rs")
+            .unwrap()
+            .current_dir(&tmp)
+            .args(["--no-banner", "--config-file", "config.yml"])
+            .args(args)
+            .assert()
+            .success()
+    };
+
+    run(&["import", "--post-process", sample.to_str().unwrap()]);
+    fs::remove_file(&register).unwrap();
+    let output = tmp.path().join("appendix.md");
+    let output = output.to_str().unwrap();
+    run(&["render", "-t", "suppressions", "--renderer", "markdown", "-o", output]);
+    let md = fs::read_to_string(output).unwrap();
+    assert!(md.contains("No suppressions file was found"), "{md}");
+    assert!(md.contains("192.168.56.20") && md.contains("1002"), "{md}");
+}
+
+#[test]
+fn rules_file_sets_root_causes_and_severities() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/merge_nessus.nessus").unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+    fs::write(
+        tmp.path().join("rules.toml"),
+        r#"
+defaults = false
+
+[[root_cause]]
+plugin_id = 1002
+cause = "Configuration"
+
+[[severity]]
+name = "weak ssh cipher"
+severity = 0
+"#,
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "export"])
+        .arg(&sample)
+        .arg("--post-process")
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    let plugin = |pid: i64| {
+        json["plugins"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["plugin_id"] == pid)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(plugin(1002)["root_cause"], "Configuration");
+    assert!(plugin(1001)["root_cause"].is_null());
+    let items = json["items"].as_array().unwrap();
+    let item = items.iter().find(|i| i["plugin_id"] == 1001).unwrap();
+    assert_eq!(item["severity"], 0);
+}
+
+#[test]
+fn rollups_lint_reports_problems_and_writes_normalized_file() {
+    let tmp = tempdir().unwrap();
+    let nasl = tmp.path().join("nasl");
+    fs::create_dir(&nasl).unwrap();
+    for (id, name) in [(10, "Zeta 1"), (20, "Zeta 2"), (30, "Alpha 1")] {
+        fs::write(
+            nasl.join(format!("{id}.nasl")),
+            format!("script_id({id});\nscript_name(\"{name}\");\n"),
+        )
+        .unwrap();
+    }
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "plugin-index", "nasl"])
+        .assert()
+        .success();
+
+    fs::write(
+        tmp.path().join("rollups.toml"),
+        r#"
+[[rollup]]
+plugin_id = -1
+plugin_name = "Missing the latest Zeta Patches"
+item_name = "Update to the latest Zeta"
+description = "Zeta Patch Rollup"
+plugin_ids = [20, 10, 20, 30]
+
+[[rollup]]
+plugin_id = -1
+plugin_name = "Missing the latest Alpha Patches"
+item_name = "Update to the latest Alpha"
+description = "Alpha Patch Rollup"
+plugin_ids = [30, 40]
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "rollups", "lint"])
+        .assert()
+        .failure()
+        .stdout(contains("(3 plugins)"))
+        .stdout(contains("lists plugin IDs more than once: 20"))
+        .stdout(contains("lists plugins not in the index: 40"))
+        .stdout(contains("rollup plugin_id -1 is used by"))
+        .stdout(contains("plugin 30 is claimed by"));
+
+    // Normalizing fixes the repeated ID but not the other problems.
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "rollups", "lint", "--write"])
+        .assert()
+        .failure()
+        .stdout(contains("more than once").not());
+    let written = fs::read_to_string(tmp.path().join("rollups.toml")).unwrap();
+    let alpha = written.find("Alpha Patches").unwrap();
+    let zeta = written.find("Zeta Patches").unwrap();
+    assert!(zeta < alpha);
+    assert!(written.contains("plugin_ids = [10, 20, 30]"));
+}
@@ -4,6 +4,7 @@ use diesel::sqlite::SqliteConnection;
 use diesel_migrations::MigrationHarness;
 use tempfile::tempdir;
 
+use risu_rs::db::DbConnection;
 use risu_rs::graphs::os_distribution::{os_distribution, OsDistributionGraph};
 use risu_rs::graphs::windows_os::WindowsOsGraph;
 use risu_rs::graphs::top_vuln::TopVulnGraph;
@@ -101,6 +102,7 @@ fn graph_os_distribution_db() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = OsDistributionGraph::generate(&mut conn, dir.path(), None).unwrap();
     assert!(out.exists());
 }
@@ -130,6 +132,7 @@ fn graph_os_distribution_db_scanner_filter() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = OsDistributionGraph::generate(&mut conn, dir.path(), Some(1)).unwrap();
     assert!(out.exists());
     let out2 = OsDistributionGraph::generate(&mut conn, dir.path(), Some(2)).unwrap();
@@ -151,6 +154,7 @@ fn graph_windows_os_generate() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = WindowsOsGraph::generate(&mut conn, dir.path()).unwrap();
     assert!(out.exists());
 }
@@ -174,6 +178,7 @@ fn graph_top_vuln_generate() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = TopVulnGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     assert!(out.exists());
 }
@@ -207,6 +212,7 @@ fn graph_top_vuln_limit_and_scanner_filter() {
         .unwrap();
     let dir = tempdir().unwrap();
     // Should succeed with scanner 1 and respect limit
+    let mut conn = DbConnection::from(conn);
     let out = TopVulnGraph::generate(&mut conn, dir.path(), 1, Some(1)).unwrap();
     assert!(out.exists());
 }
@@ -243,6 +249,7 @@ fn graph_host_severity_counts_generate() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = HostSeverityCountsGraph::generate(&mut conn, dir.path(), None).unwrap();
     assert!(out.exists());
 }
@@ -266,6 +273,7 @@ fn graph_vulns_by_service_generate() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnsByServiceGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     let meta = std::fs::metadata(&out).unwrap();
     assert!(meta.len() > 0);
@@ -293,6 +301,7 @@ fn graph_vulns_by_service_limit_and_empty() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnsByServiceGraph::generate(&mut conn, dir.path(), 1, Some(1)).unwrap();
     assert!(out.exists());
 }
@@ -329,6 +338,7 @@ fn graph_vuln_category_generate() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnCategoryGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     let meta = std::fs::metadata(&out).unwrap();
     assert!(meta.len() > 0);
@@ -364,6 +374,7 @@ fn graph_vuln_category_scanner_and_empty() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnCategoryGraph::generate(&mut conn, dir.path(), 10, Some(1)).unwrap();
     assert!(out.exists());
 }
@@ -390,6 +401,7 @@ fn graph_top_vuln_truncates_labels() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = TopVulnGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     assert!(out.exists());
 }
@@ -408,6 +420,7 @@ fn graph_vulns_by_service_truncates_labels() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnsByServiceGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     assert!(out.exists());
 }
@@ -434,13 +447,14 @@ fn graph_vuln_category_truncates_labels() {
         .execute(&mut conn)
         .unwrap();
     let dir = tempdir().unwrap();
+    let mut conn = DbConnection::from(conn);
     let out = VulnCategoryGraph::generate(&mut conn, dir.path(), 10, None).unwrap();
     assert!(out.exists());
 }
 
 #[test]
 fn graph_host_severity_counts_empty_err() {
-    let mut conn = setup_db();
+    let mut conn = DbConnection::from(setup_db());
     let dir = tempdir().unwrap();
     assert!(HostSeverityCountsGraph::generate(&mut conn, dir.path(), None).is_err());
 }
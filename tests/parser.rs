@@ -2,7 +2,7 @@ use std::fs;
 use std::io::{Result as IoResult, Write};
 use std::sync::{Arc, Mutex};
 
-use risu_rs::parser::parse_file;
+use risu_rs::parser::{collect_input_files, concat_reports, parse_file, parse_files};
 use tempfile::tempdir;
 use tracing::Level;
 use tracing_subscriber::fmt;
@@ -223,6 +223,39 @@ fn parses_multiple_hosts_and_plugins() {
     assert!(ids.contains(&2));
 }
 
+#[test]
+fn parses_cvss3_cvss4_vpr_and_epss() {
+    let path = fs::canonicalize("tests/fixtures/cvss_modern.nessus").unwrap();
+    let report = parse_file(&path).unwrap();
+
+    let item = report.items.first().unwrap();
+    assert_eq!(item.cvss_base_score, Some(5.0));
+    assert_eq!(item.cvss3_base_score, Some(9.8));
+    assert_eq!(
+        item.cvss3_vector.as_deref(),
+        Some("CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H")
+    );
+    assert_eq!(item.cvss3_temporal_score, Some(8.8));
+    assert_eq!(item.cvss3_temporal_vector.as_deref(), Some("CVSS:3.0/E:P/RL:O/RC:C"));
+    assert_eq!(item.cvss4_base_score, Some(9.3));
+    assert!(item.cvss4_vector.as_deref().unwrap().starts_with("CVSS:4.0/"));
+    assert_eq!(item.vpr_score, Some(6.7));
+    assert_eq!(item.epss_score, Some(0.0421));
+    assert_eq!(item.exploit_code_maturity.as_deref(), Some("Functional"));
+    assert_eq!(item.threat_intensity_last_28.as_deref(), Some("Very Low"));
+
+    let plugin = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == Some(200001))
+        .unwrap();
+    assert_eq!(plugin.cvss3_base_score, Some(9.8));
+    assert_eq!(plugin.cvss4_base_score, Some(9.3));
+    assert_eq!(plugin.vpr_score, Some(6.7));
+    assert_eq!(plugin.epss_score, Some(0.0421));
+    assert_eq!(plugin.exploit_code_maturity.as_deref(), Some("Functional"));
+}
+
 #[test]
 fn parses_policy_block() {
     let path = fs::canonicalize("tests/fixtures/policy.nessus").unwrap();
@@ -422,6 +455,120 @@ fn parses_nessus_sqlite_db() {
     assert_eq!(report.attachments.len(), 1);
 }
 
+#[test]
+fn nessus_sqlite_import_is_fully_populated_and_index_normalized() {
+    use diesel::prelude::*;
+    use diesel_migrations::MigrationHarness;
+
+    use risu_rs::migrate::MIGRATIONS;
+    use risu_rs::parser::parse_nessus_sqlite;
+
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("export.db");
+    let mut conn = diesel::sqlite::SqliteConnection::establish(db_path.to_str().unwrap()).unwrap();
+    conn.run_pending_migrations(MIGRATIONS).unwrap();
+
+    // DB ids deliberately differ from vector indices.
+    for stmt in [
+        "INSERT INTO nessus_reports (id, title) VALUES (7, 'Export')",
+        "INSERT INTO nessus_hosts (id, nessus_report_id, ip) VALUES (10, 7, '10.0.0.1')",
+        "INSERT INTO nessus_hosts (id, nessus_report_id, ip) VALUES (20, 7, '10.0.0.2')",
+        "INSERT INTO nessus_plugins (id, plugin_id, plugin_name) VALUES (5, 19506, 'Scan Info')",
+        "INSERT INTO nessus_plugins (id, plugin_id, plugin_name) VALUES (6, 12345, 'OpenSSL')",
+        "INSERT INTO nessus_items (id, host_id, plugin_id, severity) VALUES (100, 20, 6, 3)",
+        "INSERT INTO nessus_items (id, host_id, plugin_id, severity) VALUES (101, 10, 5, 0)",
+        "INSERT INTO nessus_references (plugin_id, item_id, source, value) \
+         VALUES (6, 100, 'CVE', 'CVE-2024-0001')",
+        "INSERT INTO nessus_host_properties (host_id, name, value) \
+         VALUES (20, 'operating-system', 'Linux')",
+        "INSERT INTO nessus_service_descriptions (host_id, item_id, port, svc_name) \
+         VALUES (20, 100, 443, 'www')",
+        "INSERT INTO nessus_patches (host_id, name, value) VALUES (10, 'MS24-001', 'missing')",
+        "INSERT INTO nessus_policies (id, nessus_report_id, name) VALUES (3, 7, 'Basic')",
+        "INSERT INTO nessus_policy_plugins (policy_id, plugin_id, status) \
+         VALUES (3, 12345, 'enabled')",
+        "INSERT INTO nessus_family_selections (policy_id, family_name, status) \
+         VALUES (3, 'General', 'enabled')",
+        "INSERT INTO nessus_plugin_preferences (policy_id, plugin_id, preference_name) \
+         VALUES (3, 12345, 'timeout')",
+        "INSERT INTO nessus_server_preferences (policy_id, name, value) \
+         VALUES (3, 'max_hosts', '30')",
+    ] {
+        diesel::sql_query(stmt).execute(&mut conn).unwrap();
+    }
+    drop(conn);
+
+    let report = parse_nessus_sqlite(&db_path).unwrap();
+    assert_eq!(report.report.title.as_deref(), Some("Export"));
+    assert_eq!(
+        report.hosts.iter().map(|h| h.id).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+
+    let item = &report.items[0];
+    assert_eq!(item.id, 0);
+    assert_eq!(item.host_id, Some(1));
+    assert_eq!(item.plugin_id, Some(12345));
+    assert_eq!(report.items[1].host_id, Some(0));
+
+    assert_eq!(report.references.len(), 1);
+    assert_eq!(report.references[0].item_id, Some(0));
+    assert_eq!(report.references[0].plugin_id, Some(12345));
+    assert_eq!(report.host_properties[0].host_id, Some(1));
+    assert_eq!(report.service_descriptions[0].host_id, Some(1));
+    assert_eq!(report.service_descriptions[0].item_id, Some(0));
+    assert_eq!(report.patches[0].host_id, Some(0));
+
+    assert_eq!(report.policies.len(), 1);
+    assert_eq!(report.policies[0].id, 0);
+    assert_eq!(report.policy_plugins[0].policy_id, Some(0));
+    assert_eq!(report.family_selections[0].policy_id, Some(0));
+    assert_eq!(report.plugin_preferences[0].policy_id, Some(0));
+    assert_eq!(report.server_preferences[0].policy_id, Some(0));
+}
+
+#[test]
+fn parses_nessus_sqlite_export_with_an_old_schema() {
+    use diesel::prelude::*;
+    use diesel_migrations::MigrationHarness;
+
+    use risu_rs::migrate::MIGRATIONS;
+    use risu_rs::parser::parse_nessus_sqlite;
+
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("export.db");
+    let mut conn = diesel::sqlite::SqliteConnection::establish(db_path.to_str().unwrap()).unwrap();
+    // Stop after migration 12, before the OpenVAS, observation date, CVSS v3
+    // and suppressed columns were added.
+    for _ in 0..13 {
+        conn.run_next_migration(MIGRATIONS).unwrap();
+    }
+    for stmt in [
+        "INSERT INTO nessus_hosts (id, ip) VALUES (4, '10.0.0.9')",
+        "INSERT INTO nessus_plugins (id, plugin_id, plugin_name) VALUES (2, 12345, 'OpenSSL')",
+        "INSERT INTO nessus_items (id, host_id, plugin_id, severity) VALUES (8, 4, 2, 3)",
+    ] {
+        diesel::sql_query(stmt).execute(&mut conn).unwrap();
+    }
+    drop(conn);
+
+    let report = parse_nessus_sqlite(&db_path).unwrap();
+    assert_eq!(report.hosts[0].ip.as_deref(), Some("10.0.0.9"));
+    assert_eq!(report.plugins[0].oid, None);
+    let item = &report.items[0];
+    assert_eq!(item.host_id, Some(0));
+    assert_eq!(item.plugin_id, Some(12345));
+    assert_eq!(item.severity, Some(3));
+    assert_eq!(item.suppressed, None);
+    assert_eq!(item.first_discovered, None);
+
+    // The export itself is left on its old schema.
+    let mut conn = diesel::sqlite::SqliteConnection::establish(db_path.to_str().unwrap()).unwrap();
+    assert!(diesel::sql_query("SELECT suppressed FROM nessus_items")
+        .execute(&mut conn)
+        .is_err());
+}
+
 #[test]
 fn recognizes_host_property_patterns() {
     let xml = r#"<NessusClientData_v2><ReportHost name='h'><HostProperties>
@@ -480,3 +627,404 @@ fn fails_on_unsupported_root_element() {
     let err = parse_file(&path).err().expect("should fail");
     assert!(matches!(err, risu_rs::error::Error::InvalidDocument(_)));
 }
+
+#[test]
+fn parses_qualys_scan_report() {
+    let sample = fs::canonicalize("tests/fixtures/qualys_scan.xml").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.scanner.scanner_type, "Qualys");
+    assert_eq!(report.report.title.as_deref(), Some("Quarterly Scan"));
+    assert_eq!(report.hosts.len(), 1);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("10.0.0.5"));
+    assert_eq!(host.fqdn.as_deref(), Some("web01.example.com"));
+    assert_eq!(host.netbios.as_deref(), Some("WEB01"));
+    assert_eq!(host.os.as_deref(), Some("Linux 3.10"));
+    assert!(host.start.is_some());
+
+    assert_eq!(report.items.len(), 3);
+    let vuln = report
+        .items
+        .iter()
+        .find(|i| i.plugin_id == Some(86002))
+        .unwrap();
+    assert_eq!(vuln.host_id, Some(0));
+    assert_eq!(vuln.severity, Some(4));
+    assert_eq!(vuln.port, Some(443));
+    assert_eq!(vuln.protocol.as_deref(), Some("tcp"));
+    assert_eq!(vuln.plugin_output.as_deref(), Some("Server: Apache/2.4.49"));
+    let info = report
+        .items
+        .iter()
+        .find(|i| i.plugin_id == Some(82023))
+        .unwrap();
+    assert_eq!(info.severity, Some(0));
+
+    let plugin = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == Some(86002))
+        .unwrap();
+    assert_eq!(
+        plugin.plugin_name.as_deref(),
+        Some("Apache HTTP Server Remote Code Execution")
+    );
+    assert_eq!(plugin.family_name.as_deref(), Some("Web server"));
+    assert_eq!(plugin.cvss_base_score, Some(9.8));
+    assert_eq!(plugin.solution.as_deref(), Some("Upgrade to 2.4.51."));
+    let practice = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == Some(38170))
+        .unwrap();
+    assert_eq!(practice.potential_vulnerability, Some(true));
+
+    assert!(report.references.iter().any(|r| r.item_id == Some(vuln.id)
+        && r.source.as_deref() == Some("CVE")
+        && r.value.as_deref() == Some("CVE-2021-41773")));
+    assert!(report.references.iter().any(|r| r.source.as_deref() == Some("BID")
+        && r.value.as_deref() == Some("12345")));
+}
+
+#[test]
+fn parses_qualys_asset_data_report() {
+    let sample = fs::canonicalize("tests/fixtures/qualys_asset_data.xml").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.hosts.len(), 1);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("10.0.0.7"));
+    assert_eq!(host.fqdn.as_deref(), Some("db01.example.com"));
+    assert_eq!(host.netbios.as_deref(), Some("DB01"));
+    assert_eq!(host.os.as_deref(), Some("Windows Server 2019"));
+    assert!(host.start.is_some() && host.end.is_some());
+
+    assert_eq!(report.items.len(), 1);
+    let item = &report.items[0];
+    assert_eq!(item.plugin_id, Some(91234));
+    assert_eq!(item.severity, Some(3));
+    assert_eq!(item.port, Some(445));
+    assert_eq!(
+        item.plugin_name.as_deref(),
+        Some("Microsoft Windows Security Update for September 2021")
+    );
+
+    let plugin = &report.plugins[0];
+    assert_eq!(plugin.risk_factor.as_deref(), Some("High"));
+    assert_eq!(plugin.cvss_base_score, Some(7.8));
+    assert_eq!(plugin.description.as_deref(), Some("Multiple vulnerabilities."));
+    assert_eq!(report.references.len(), 1);
+    assert_eq!(report.references[0].value.as_deref(), Some("CVE-2021-36968"));
+}
+
+#[test]
+fn parses_qualys_host_detection_output() {
+    let sample = fs::canonicalize("tests/fixtures/qualys_detection.xml").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.hosts.len(), 1);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("10.0.0.9"));
+    assert_eq!(host.fqdn.as_deref(), Some("app01.example.com"));
+    assert_eq!(host.os.as_deref(), Some("Ubuntu 20.04"));
+    assert!(host.end.is_some());
+
+    assert_eq!(report.items.len(), 2);
+    assert!(report.items.iter().all(|i| i.host_id == Some(0)));
+    let cert = &report.items[0];
+    assert_eq!(cert.plugin_id, Some(38170));
+    assert_eq!(cert.severity, Some(1));
+    assert_eq!(cert.plugin_name.as_deref(), Some("QID 38170"));
+    assert_eq!(cert.plugin_output.as_deref(), Some("Certificate CN mismatch"));
+
+    let potential = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == Some(105489))
+        .unwrap();
+    assert_eq!(potential.potential_vulnerability, Some(true));
+}
+
+#[test]
+fn parses_openvas_gvm_report() {
+    let sample = fs::canonicalize("tests/fixtures/openvas_report.xml").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.scanner.scanner_type, "OpenVAS");
+    assert_eq!(report.report.title.as_deref(), Some("Weekly Greenbone Scan"));
+    assert_eq!(report.hosts.len(), 2);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("192.168.1.10"));
+    assert_eq!(host.fqdn.as_deref(), Some("srv01.example.com"));
+    assert_eq!(host.os.as_deref(), Some("Ubuntu 22.04"));
+    assert_eq!(host.mac.as_deref(), Some("00:11:22:33:44:55"));
+    assert!(host.start.is_some() && host.end.is_some());
+
+    // The false positive result is dropped.
+    assert_eq!(report.items.len(), 3);
+    let ssh = &report.items[0];
+    assert_eq!(ssh.plugin_id, Some(105611));
+    assert_eq!(ssh.host_id, Some(0));
+    assert_eq!(ssh.port, Some(22));
+    assert_eq!(ssh.protocol.as_deref(), Some("tcp"));
+    assert_eq!(ssh.severity, Some(2));
+    assert_eq!(ssh.confidence, Some(95));
+    assert_eq!(ssh.cvss_vector.as_deref(), Some("AV:N/AC:M/Au:N/C:P/I:N/A:N"));
+
+    let os = &report.items[1];
+    assert_eq!(os.severity, Some(0));
+    assert_eq!(os.svc_name.as_deref(), Some("general"));
+
+    let usn = &report.items[2];
+    assert_eq!(usn.host_id, Some(1));
+    assert_eq!(usn.severity, Some(4));
+    let usn_plugin = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == usn.plugin_id)
+        .unwrap();
+    assert_eq!(
+        usn_plugin.oid.as_deref(),
+        Some("1.3.6.1.4.1.25623.1.1.12.2023.6000.1")
+    );
+
+    let plugin = report
+        .plugins
+        .iter()
+        .find(|p| p.plugin_id == Some(105611))
+        .unwrap();
+    assert_eq!(plugin.oid.as_deref(), Some("1.3.6.1.4.1.25623.1.0.105611"));
+    assert_eq!(plugin.cvss_base_score, Some(4.3));
+    assert_eq!(plugin.family_name.as_deref(), Some("General"));
+    assert_eq!(
+        plugin.solution.as_deref(),
+        Some("Disable the reported weak encryption algorithm(s).")
+    );
+    assert_eq!(plugin.risk_factor.as_deref(), Some("Medium"));
+
+    assert!(report.references.iter().any(|r| r.item_id == Some(0)
+        && r.source.as_deref() == Some("CVE")
+        && r.value.as_deref() == Some("CVE-2008-5161")));
+    assert!(report.references.iter().any(|r| r.source.as_deref() == Some("CERT-BUND")));
+    assert_eq!(
+        report
+            .references
+            .iter()
+            .filter(|r| r.item_id == Some(2))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn parses_security_center_csv_export() {
+    let sample = fs::canonicalize("tests/fixtures/security_center.csv").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.scanner.scanner_type, "SecurityCenter");
+    assert_eq!(report.hosts.len(), 2);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("10.1.1.20"));
+    assert_eq!(host.fqdn.as_deref(), Some("fs01.corp.example"));
+    assert_eq!(host.netbios.as_deref(), Some("FS01"));
+    assert_eq!(host.mac.as_deref(), Some("00:50:56:aa:bb:cc"));
+    let date = |y, m, d, hms: (u32, u32, u32)| {
+        chrono::NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(hms.0, hms.1, hms.2)
+            .unwrap()
+    };
+    assert_eq!(host.start, Some(date(2017, 3, 20, (8, 15, 0))));
+    assert_eq!(host.end, Some(date(2024, 1, 5, (10, 22, 33))));
+
+    assert_eq!(report.items.len(), 3);
+    assert_eq!(report.plugins.len(), 3);
+    let ms17 = &report.items[0];
+    assert_eq!(ms17.plugin_id, Some(97833));
+    assert_eq!(ms17.host_id, Some(0));
+    assert_eq!(ms17.severity, Some(4));
+    assert_eq!(ms17.port, Some(445));
+    assert_eq!(ms17.protocol.as_deref(), Some("tcp"));
+    assert_eq!(ms17.plugin_output.as_deref(), Some("Sent: 00000000 ..."));
+    assert_eq!(
+        ms17.first_discovered.map(|d| d.to_string()).as_deref(),
+        Some("2017-03-20 08:15:00")
+    );
+    assert_eq!(
+        ms17.last_observed.map(|d| d.to_string()).as_deref(),
+        Some("2024-01-05 10:22:33")
+    );
+    assert_eq!(report.items[1].severity, Some(0));
+    assert_eq!(report.items[2].host_id, Some(1));
+
+    let plugin = &report.plugins[0];
+    assert_eq!(plugin.family_name.as_deref(), Some("Windows"));
+    assert_eq!(plugin.cvss_base_score, Some(10.0));
+    assert_eq!(plugin.exploit_available.as_deref(), Some("Yes"));
+
+    let refs: Vec<(&str, &str)> = report
+        .references
+        .iter()
+        .filter(|r| r.item_id == Some(0))
+        .map(|r| (r.source.as_deref().unwrap(), r.value.as_deref().unwrap()))
+        .collect();
+    assert!(refs.contains(&("CVE", "CVE-2017-0144")));
+    assert!(refs.contains(&("BID", "96703")));
+    assert!(refs.contains(&("IAVA", "2017-A-0065")));
+    assert!(refs.contains(&("MSFT", "MS17-010")));
+}
+
+#[test]
+fn parses_nmap_os_hostnames_services_and_scripts() {
+    let sample = fs::canonicalize("tests/fixtures/nmap_full.xml").unwrap();
+    let report = parse_file(&sample).unwrap();
+
+    assert_eq!(report.scanner.scanner_type, "Nmap");
+    assert_eq!(report.scanner.scanner_version.as_deref(), Some("7.94"));
+    // The down host is skipped.
+    assert_eq!(report.hosts.len(), 1);
+    let host = &report.hosts[0];
+    assert_eq!(host.ip.as_deref(), Some("192.168.56.2"));
+    assert_eq!(host.fqdn.as_deref(), Some("router.lab.example"));
+    assert_eq!(host.os.as_deref(), Some("Linux 5.0 - 5.14"));
+    assert_eq!(host.mac.as_deref(), Some("08:00:27:AA:BB:CC"));
+    assert!(host.start.is_some() && host.end.is_some());
+    assert!(report.host_properties.iter().any(|p| p.name.as_deref() == Some("mac-vendor")
+        && p.value.as_deref() == Some("Oracle VirtualBox virtual NIC")));
+
+    // Two open ports; the filtered port is ignored.
+    let open: Vec<_> = report
+        .items
+        .iter()
+        .filter(|i| i.plugin_id == Some(0))
+        .collect();
+    assert_eq!(open.len(), 2);
+    assert!(report.items.iter().all(|i| i.host_id == Some(0)));
+
+    assert_eq!(report.service_descriptions.len(), 2);
+    let ssh = &report.service_descriptions[0];
+    assert_eq!(ssh.port, Some(22));
+    assert_eq!(ssh.svc_name.as_deref(), Some("ssh"));
+    assert_eq!(
+        ssh.description.as_deref(),
+        Some("OpenSSH 8.9p1 Ubuntu 3ubuntu0.6 (Ubuntu Linux; protocol 2.0)")
+    );
+    assert!(report.references.iter().any(|r| r.item_id == ssh.item_id
+        && r.source.as_deref() == Some("CPE")
+        && r.value.as_deref() == Some("cpe:/a:openbsd:openssh:8.9p1")));
+
+    // Port and host scripts each get their own plugin and item.
+    let script_plugins: Vec<_> = report
+        .plugins
+        .iter()
+        .filter_map(|p| p.plugin_name.as_deref())
+        .filter(|n| n.starts_with("Nmap NSE: "))
+        .collect();
+    assert_eq!(script_plugins.len(), 3);
+    let hostkey = report
+        .items
+        .iter()
+        .find(|i| i.plugin_name.as_deref() == Some("Nmap NSE: ssh-hostkey"))
+        .unwrap();
+    assert_eq!(hostkey.port, Some(22));
+    assert!(hostkey.plugin_output.as_deref().unwrap().contains("ED25519"));
+    let smb_time = report
+        .items
+        .iter()
+        .find(|i| i.plugin_name.as_deref() == Some("Nmap NSE: smb2-time"))
+        .unwrap();
+    assert_eq!(smb_time.port, Some(0));
+    let vulners = report
+        .items
+        .iter()
+        .find(|i| i.plugin_name.as_deref() == Some("Nmap NSE: vulners"))
+        .unwrap();
+    let cves: Vec<_> = report
+        .references
+        .iter()
+        .filter(|r| r.item_id == Some(vulners.id) && r.source.as_deref() == Some("CVE"))
+        .collect();
+    assert_eq!(cves.len(), 2);
+}
+
+#[test]
+fn parses_risu_json_findings() {
+    let path = fs::canonicalize("tests/fixtures/custom_findings.json").unwrap();
+    let report = parse_file(&path).unwrap();
+
+    assert_eq!(report.version, "risu-findings/1");
+    assert_eq!(report.report.title.as_deref(), Some("AWS configuration audit"));
+    assert_eq!(report.scanner.scanner_type, "cloud-audit");
+    assert_eq!(report.scanner.scanner_version.as_deref(), Some("2.3"));
+
+    // Findings refer to the declared host by key and by IP; unknown hosts are added.
+    assert_eq!(report.hosts.len(), 2);
+    assert_eq!(report.hosts[0].fqdn.as_deref(), Some("web.example.com"));
+    assert_eq!(report.hosts[1].ip.as_deref(), Some("10.0.0.9"));
+    assert_eq!(
+        report.hosts[1].end.map(|d| d.to_string()).as_deref(),
+        Some("2024-03-02 00:00:00")
+    );
+    let host_ids: Vec<_> = report.items.iter().map(|i| i.host_id).collect();
+    assert_eq!(host_ids, vec![Some(0), Some(0), Some(1)]);
+
+    // String plugin keys get a stable synthetic ID; severity falls back to the
+    // plugin risk factor.
+    let s3 = &report.items[0];
+    let s3_plugin = report.plugins.iter().find(|p| p.plugin_id == s3.plugin_id).unwrap();
+    assert_eq!(s3_plugin.family_name.as_deref(), Some("Cloud"));
+    assert_eq!(s3.severity, Some(3));
+    assert_eq!(s3.cvss3_base_score, Some(7.5));
+    assert_eq!(s3.plugin_output.as_deref(), Some("bucket: logs-archive"));
+    assert_eq!(report.items[1].severity, Some(2));
+    assert_eq!(report.items[1].protocol.as_deref(), Some("tcp"));
+
+    let refs: Vec<_> = report
+        .references
+        .iter()
+        .filter(|r| r.item_id == Some(0))
+        .map(|r| (r.source.as_deref().unwrap(), r.value.as_deref().unwrap()))
+        .collect();
+    assert_eq!(refs, vec![("CWE", "284"), ("URL", "https://docs.example.com/s3")]);
+}
+
+#[test]
+fn rejects_json_without_findings_schema() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("scan.dat");
+    fs::write(&path, r#"{ "hosts": [] }"#).unwrap();
+    let err = parse_file(&path).err().expect("should fail");
+    assert!(matches!(err, risu_rs::error::Error::InvalidDocument(_)));
+    assert!(err.to_string().contains("risu-findings/1"));
+}
+
+#[test]
+fn concatenates_reports_from_a_directory() {
+    let tmp = tempdir().unwrap();
+    let zone_a = tmp.path().join("zone-a");
+    fs::create_dir(&zone_a).unwrap();
+    fs::copy("tests/fixtures/custom_findings.json", zone_a.join("cloud.json")).unwrap();
+    fs::copy("tests/fixtures/attachment_ref.nessus", tmp.path().join("dmz.nessus")).unwrap();
+    fs::write(tmp.path().join("notes.txt"), "not a scan").unwrap();
+
+    let files = collect_input_files(&[tmp.path().to_path_buf()]).unwrap();
+    assert_eq!(files, [tmp.path().join("dmz.nessus"), zone_a.join("cloud.json")]);
+
+    // Files are parsed in parallel but come back in input order.
+    let reports: Vec<_> = parse_files(&files).into_iter().map(Result::unwrap).collect();
+    let report = concat_reports(reports);
+    assert_eq!(report.hosts.len(), 3);
+    assert_eq!(report.items.len(), 4);
+    // Items, references and attachments of the second file are re-based.
+    assert_eq!(report.items[0].host_id, Some(0));
+    assert_eq!(report.items[0].attachment_id, Some(0));
+    assert_eq!(report.items[3].host_id, Some(2));
+    let cwe = report
+        .references
+        .iter()
+        .find(|r| r.value.as_deref() == Some("284"))
+        .unwrap();
+    assert_eq!(cwe.item_id, Some(1));
+    assert_eq!(report.scanner.scanner_type, "Nessus + cloud-audit");
+}
@@ -1,4 +1,4 @@
-use risu_rs::models::{Host, Item, Plugin};
+use risu_rs::models::{CvssVersion, Host, HostProperty, Item, Plugin};
 use risu_rs::parser::{Filters, NessusReport};
 use risu_rs::postprocess;
 use std::collections::HashSet;
@@ -95,6 +95,60 @@ fn risk_score_computes_scores() {
     assert_eq!(report.hosts[0].risk_score, Some(4));
 }
 
+#[test]
+fn risk_score_uses_preferred_cvss_version() {
+    let plugin = Plugin {
+        plugin_id: Some(1),
+        cvss_base_score: Some(5.0),
+        cvss3_base_score: Some(9.0),
+        ..Plugin::default()
+    };
+    let item = Item {
+        plugin_id: Some(1),
+        ..Item::default()
+    };
+    let mut report = NessusReport {
+        hosts: vec![host("host", Some("10.0.0.1"))],
+        plugins: vec![plugin],
+        items: vec![item],
+        cvss_version: CvssVersion::V3,
+        ..NessusReport::default()
+    };
+    postprocess::process(&mut report, &HashSet::new(), &HashSet::new(), &Filters::default());
+    assert_eq!(report.items[0].risk_score, Some(7));
+}
+
+#[test]
+fn risk_score_weighs_exploits_and_configured_criticality() {
+    let plugin = Plugin {
+        plugin_id: Some(1),
+        cvss_base_score: Some(5.0),
+        exploit_available: Some("true".to_string()),
+        ..Plugin::default()
+    };
+    let mut report = NessusReport {
+        hosts: vec![host("host", Some("10.0.0.1"))],
+        plugins: vec![plugin],
+        items: vec![Item {
+            plugin_id: Some(1),
+            host_id: Some(0),
+            ..Item::default()
+        }],
+        host_properties: vec![HostProperty {
+            host_id: Some(0),
+            name: Some("asset-criticality".to_string()),
+            value: Some("crown-jewel".to_string()),
+            ..HostProperty::default()
+        }],
+        ..NessusReport::default()
+    };
+    report.risk_scoring.criticality.insert("crown-jewel".to_string(), 2.0);
+    postprocess::process(&mut report, &HashSet::new(), &HashSet::new(), &Filters::default());
+    // (5.0 * 0.8 + 2 for the exploit) * 2
+    assert_eq!(report.items[0].risk_score, Some(12));
+    assert_eq!(report.hosts[0].risk_score, Some(12));
+}
+
 #[test]
 fn downgrade_plugins_adjusts_severity() {
     let mut item1 = Item::default();
@@ -0,0 +1,92 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tempfile::tempdir;
+
+use risu_rs::{db, loader, parser, persist};
+
+/// Tracks live heap bytes and their high-water mark.
+struct CountingAlloc;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Peak heap growth while running `f`.
+fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = LIVE.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let out = f();
+    (out, PEAK.load(Ordering::Relaxed).saturating_sub(start))
+}
+
+fn generate(path: &Path, hosts: usize) {
+    let out = File::create(path).unwrap();
+    let status = Command::new(assert_cmd::cargo::cargo_bin("nessus-generator"))
+        .arg(hosts.to_string())
+        .args(["10001", "10002", "10003", "10004", "10005"])
+        .stdout(Stdio::from(out))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn stream(file: &Path, db_path: &Path) -> i32 {
+    let mut conn = db::establish(db_path.to_str().unwrap(), "sqlite").unwrap();
+    db::run_pending_migrations(&mut conn).unwrap();
+    let mut sink = persist::DatabaseSink::new(&mut conn).batch_hosts(50);
+    parser::stream_nessus(file, &mut sink).unwrap();
+    sink.report_id().unwrap()
+}
+
+// Everything runs in one test so the allocator counters are not shared with
+// other test threads.
+#[test]
+fn streaming_import_memory_does_not_grow_with_host_count() {
+    let dir = tempdir().unwrap();
+    let small = dir.path().join("small.nessus");
+    let large = dir.path().join("large.nessus");
+    generate(&small, 200);
+    generate(&large, 4000);
+
+    let (_, small_peak) = peak_during(|| stream(&small, &dir.path().join("small.db")));
+    let (report_id, large_peak) = peak_during(|| stream(&large, &dir.path().join("large.db")));
+    let (parsed, parse_peak) = peak_during(|| parser::parse_file(&large).unwrap());
+
+    // Twenty times the hosts must not need much more than the small file.
+    assert!(
+        large_peak < small_peak * 2 + 256 * 1024,
+        "streaming peak grew from {small_peak} to {large_peak} bytes"
+    );
+    assert!(
+        large_peak * 4 < parse_peak,
+        "streaming peak {large_peak} not well below in-memory parse peak {parse_peak}"
+    );
+
+    let mut conn = db::establish(dir.path().join("large.db").to_str().unwrap(), "sqlite").unwrap();
+    let loaded = loader::load_report(&mut conn, Some(report_id)).unwrap();
+    assert_eq!(loaded.hosts.len(), 4000);
+    assert_eq!(loaded.items.len(), parsed.items.len());
+    assert_eq!(loaded.plugins.len(), 5);
+}
@@ -110,13 +110,12 @@ fn render_template_capture_raw(name: &str) -> String {
     let csv_data = render_template_capture(name);
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(false)
+        .flexible(true)
         .from_reader(csv_data.as_bytes());
     let mut lines = Vec::new();
     for rec in rdr.records() {
         let rec = rec.unwrap();
-        if let Some(cell) = rec.get(0) {
-            lines.push(cell.to_string());
-        }
+        lines.push(rec.iter().collect::<Vec<_>>().join(","));
     }
     lines.join("\n")
 }
@@ -155,13 +154,12 @@ fn render_template_capture_raw_fixture(name: &str, fixture: &str) -> String {
     let csv_data = fs::read_to_string(output).unwrap();
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(false)
+        .flexible(true)
         .from_reader(csv_data.as_bytes());
     let mut lines = Vec::new();
     for rec in rdr.records() {
         let rec = rec.unwrap();
-        if let Some(cell) = rec.get(0) {
-            lines.push(cell.to_string());
-        }
+        lines.push(rec.iter().collect::<Vec<_>>().join(","));
     }
     lines.join("\n")
 }
@@ -317,14 +315,14 @@ fn host_summary_template_renders() {
     let contents = render_template_capture("host_summary");
     assert!(contents.contains("Total Hosts: 1"));
     assert!(contents.contains("No network shares found."));
-    assert!(contents.contains("Info: 1"));
+    assert!(contents.contains("Info,1"));
 }
 
 #[test]
 fn exec_summary_template_includes_risk_score() {
     let rust_out = render_template_capture_raw("exec_summary");
     assert!(rust_out.contains("Risk Score:"));
-    assert!(rust_out.contains("Risk scores derived from weighted averages of finding severities",));
+    assert!(rust_out.contains("scored with the `risk_scoring` weights"));
 }
 
 #[test]
@@ -480,3 +478,28 @@ fn virtual_machine_summary_template_lists_hosts() {
     assert!(out.contains("VMware"));
     assert!(out.contains("Hyper-V"));
 }
+
+#[test]
+fn template_logo_renders_as_html_image() {
+    let tmp = tempdir().unwrap();
+    let sample = fs::canonicalize("tests/fixtures/sample.nessus").unwrap();
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .args(["--no-banner", "--create-config-file"])
+        .current_dir(&tmp)
+        .assert()
+        .success();
+
+    let output = tmp.path().join("out.html");
+    Command::cargo_bin("risu-rs")
+        .unwrap()
+        .current_dir(&tmp)
+        .args(["--no-banner", "--config-file", "config.yml", "parse"])
+        .arg(&sample)
+        .args(["-o", output.to_str().unwrap(), "-t", "template", "--renderer", "html"])
+        .assert()
+        .success();
+    let html = fs::read_to_string(output).unwrap();
+    assert!(html.contains("<img src=\"data:image/jpeg;base64,"));
+    assert!(!html.contains("<p>data:image"));
+}
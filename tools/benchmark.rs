@@ -0,0 +1,104 @@
+//! Time parsing and post-processing of generated Nessus files on one thread
+//! and on every core.
+//!
+//! Usage: `risu-bench [hosts] [plugins] [files]` (defaults: 10000 200 8).
+//! Build with `--release`; `nessus-generator` must sit next to this binary.
+
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use risu_rs::parser::{self, Filters};
+use risu_rs::postprocess;
+
+struct Timings {
+    parse: Duration,
+    postprocess: Duration,
+    items: usize,
+}
+
+fn arg(args: &[String], idx: usize, default: usize) -> usize {
+    args.get(idx)
+        .map(|a| a.parse().expect("arguments should be numbers"))
+        .unwrap_or(default)
+}
+
+fn generate(dir: &Path, hosts: usize, plugins: usize, files: usize) -> Vec<PathBuf> {
+    let generator = env::current_exe()
+        .expect("current executable")
+        .with_file_name(format!("nessus-generator{}", env::consts::EXE_SUFFIX));
+    let plugin_ids: Vec<String> = (0..plugins).map(|p| (10000 + p).to_string()).collect();
+    (0..files)
+        .map(|n| {
+            let path = dir.join(format!("bench-{n}.nessus"));
+            let share = hosts / files + usize::from(n < hosts % files);
+            let status = Command::new(&generator)
+                .arg(share.to_string())
+                .args(&plugin_ids)
+                .stdout(Stdio::from(File::create(&path).expect("create input")))
+                .status()
+                .expect("run nessus-generator");
+            assert!(status.success(), "nessus-generator failed");
+            path
+        })
+        .collect()
+}
+
+fn run(files: &[PathBuf]) -> Timings {
+    let start = Instant::now();
+    let reports = parser::parse_files(files)
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("parse generated files");
+    let mut report = parser::concat_reports(reports);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let blacklist: HashSet<i32> = [10000].into_iter().collect();
+    postprocess::process(&mut report, &HashSet::new(), &blacklist, &Filters::default());
+    Timings {
+        parse,
+        postprocess: start.elapsed(),
+        items: report.items.len(),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let hosts = arg(&args, 0, 10_000);
+    let plugins = arg(&args, 1, 200);
+    let files = arg(&args, 2, 8).max(1);
+
+    let dir = env::temp_dir().join(format!("risu-bench-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("create work directory");
+    println!("Generating {hosts} hosts x {plugins} plugins in {files} files");
+    let inputs = generate(&dir, hosts, plugins, files);
+
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut baseline: Option<Timings> = None;
+    let thread_counts = if cores > 1 { vec![1, cores] } else { vec![1] };
+    for threads in thread_counts {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("thread pool");
+        let t = pool.install(|| run(&inputs));
+        print!(
+            "{threads:>3} threads: parse {:>8.2?}  post-process {:>8.2?}  ({} items)",
+            t.parse, t.postprocess, t.items
+        );
+        if let Some(b) = &baseline {
+            print!(
+                "  speedup {:.1}x / {:.1}x",
+                b.parse.as_secs_f64() / t.parse.as_secs_f64(),
+                b.postprocess.as_secs_f64() / t.postprocess.as_secs_f64()
+            );
+        }
+        println!();
+        baseline.get_or_insert(t);
+    }
+    fs::remove_dir_all(&dir).ok();
+}
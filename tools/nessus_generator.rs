@@ -16,7 +16,11 @@ fn main() {
         writeln!(out, "  <ReportHost name=\"h{}\">", i + 1).unwrap();
         writeln!(out, "    <HostProperties></HostProperties>").unwrap();
         for pid in &plugin_ids {
-            writeln!(out, "    <ReportItem pluginID=\"{}\" severity=\"0\" pluginName=\"plug{}\"></ReportItem>", pid, pid).unwrap();
+            writeln!(
+                out,
+                "    <ReportItem pluginID=\"{pid}\" severity=\"0\" pluginName=\"plug{pid}\"></ReportItem>"
+            )
+            .unwrap();
         }
         writeln!(out, "  </ReportHost>").unwrap();
     }